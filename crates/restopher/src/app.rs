@@ -1,33 +1,46 @@
 use crate::{
-    collection::{Action, Collection},
-    components::{error_popup, MultiOptionWidget},
+    collection::{self, Action, Collection},
+    components::{
+        error_popup, CommandPaletteComponent, ExportPopupComponent, ExportTarget, HarAction,
+        HarImportComponent, MultiOptionWidget, PopUpComponent, QuickOpenComponent,
+        ReportPopupComponent, Snippet, TextAreaPopupComponent, VariablesComponent,
+    },
+    defaults::{FolderDefaults, DEFAULTS_FILE},
     environments::{self, Environment, TempEnv},
-    layout::{self, centered_rect},
-    main_windows::{key_registry, ChangeEvent, MainWindows},
-    models::{self, SaveOptions},
-    request::HttpVerb,
+    export::{self, Folder, RequestParts},
+    history::{History, HistoryComponent},
+    http_file::{self, HttpFile},
+    layout::{self, centered_rect, Pane},
+    main_windows::{change_event, key_registry, ChangeEvent, MainWindows},
+    models::{self, ExportOptions, ImportOptions, SaveOptions},
+    request::BodyKind,
     trace_dbg,
+    variables::{Scope, Variables},
 };
 use crate::{
     components::RequestsAction,
     components::{
         AddressBarComponent, RequestTabComponent, RequestsComponent, ResponseTabComponent,
     },
-    keys::keys::{
-        is_navigation, is_quit, transform, Event as AppEvent, CLOSE_COLLECTIONS, NAV_DOWN,
-        NAV_LEFT, NAV_RIGHT, NAV_UP, OPEN_COLLECTIONS, OPEN_ENVIRONMENTS,
-    },
+    keys::keymap::{self, Action as KeyAction},
+    keys::keys::{transform, transform_mouse, Event as AppEvent, Key, Mouse},
 };
 
 use crate::env_replacer::EnvReplacer;
 use crate::graphql::GraphQL;
 use crate::*;
+use copypasta::{ClipboardContext, ClipboardProvider};
 use crossterm::event::{self, Event};
-use ratatui::{backend::Backend, Frame, Terminal};
+use ratatui::{
+    backend::Backend,
+    layout::{Position, Rect},
+    Frame, Terminal,
+};
 use regex::Regex;
-use reqwest::{header::HeaderMap, Response};
+use reqwest::{header::HeaderMap, multipart, Response};
 use serde_json::{self};
 use std::{
+    collections::HashMap,
     fs,
     io::{Error as ioError, Write},
 };
@@ -37,6 +50,9 @@ pub enum Error {
     NoRequestErr(usize),
     ReqwestErr(reqwest::Error),
     JsonErr(serde_json::Error),
+    YamlErr(serde_yaml::Error),
+    /// A saved request that could not be upgraded to the current format.
+    MigrationErr(String),
     HeaderIsNotString,
     FileOperationsErr(std::io::Error),
     InputErr(String),
@@ -48,6 +64,8 @@ impl Error {
             Error::NoRequestErr(idx) => format!("no request on index {}", idx).to_string(),
             Error::ReqwestErr(e) => e.to_string(),
             Error::JsonErr(e) => e.to_string(),
+            Error::YamlErr(e) => e.to_string(),
+            Error::MigrationErr(e) => e.to_string(),
             Error::HeaderIsNotString => "header is not string".to_string(),
             Error::FileOperationsErr(e) => e.to_string(),
             Error::InputErr(e) => e.to_string(),
@@ -65,6 +83,11 @@ impl From<serde_json::Error> for Error {
         Error::JsonErr(e)
     }
 }
+impl From<serde_yaml::Error> for Error {
+    fn from(e: serde_yaml::Error) -> Self {
+        Error::YamlErr(e)
+    }
+}
 
 pub struct App<'a> {
     client: reqwest::Client,
@@ -77,6 +100,17 @@ pub struct App<'a> {
     requests_component: RequestsComponent,
     mutli_option_save_request: Option<MultiOptionWidget<models::SaveOptions>>,
     multi_option_new_request_mode: Option<MultiOptionWidget<request::Mode>>,
+    history_view: Option<HistoryComponent>,
+    export_popup: Option<ExportPopupComponent>,
+    collection_export: Option<(String, MultiOptionWidget<ExportOptions>)>,
+    import_menu: Option<MultiOptionWidget<ImportOptions>>,
+    import_path: Option<(ImportOptions, PopUpComponent)>,
+    curl_import: Option<TextAreaPopupComponent>,
+    har_import: Option<HarImportComponent>,
+    import_report: Option<ReportPopupComponent>,
+    command_palette: Option<CommandPaletteComponent>,
+    quick_open: Option<QuickOpenComponent>,
+    variables_view: Option<VariablesComponent>,
     current_request_idx: usize,
     graphql: graphql::GraphQL,
     gql_ui: graphql::GqlUI,
@@ -84,26 +118,39 @@ pub struct App<'a> {
 
     all_envs: Vec<Environment>,
     temp_envs: Option<environments::TempEnv>,
-    current_env_idx: usize, // index of active environments
+    current_env_idx: usize,          // index of active environments
+    folder_defaults: FolderDefaults, // of the current request
     collections: Collection<'a>,
     regex_replacer: regex::Regex,
+    area: Rect, // frame area of the last draw, for mouse events
 }
 
 impl<'a> App<'a> {
     pub fn new() -> Self {
         let all_envs = App::load_envs().unwrap();
+        let preferences = config::preferences();
+        let current_env_idx = preferences
+            .default_environment
+            .as_ref()
+            .and_then(|name| all_envs.iter().position(|e| e.name == *name))
+            .unwrap_or(0);
+        let mut client = reqwest::Client::builder();
+        if let Some(timeout) = preferences.request_timeout() {
+            client = client.timeout(timeout);
+        }
         let requests = vec![super::request::Request::new(request::Mode::REST)];
-        let cols = Collection::default(format!("{}/{}", DATA_DIRECTORY, COLLECTION_PATH));
+        let cols = Collection::default(format!("{}/{}", config::data_dir(), COLLECTION_PATH));
         App {
-            client: reqwest::Client::new(),
+            client: client.build().unwrap_or_default(),
             requests,
             current_request_idx: 0,
             error_pop_up: (false, None),
-            current_env_idx: 0,
+            current_env_idx,
+            folder_defaults: FolderDefaults::default(),
             all_envs,
             temp_envs: None,
             regex_replacer: Regex::new(&format!(
-                "{}.*{}",
+                "{}.*?{}",
                 regex::escape(START_ENV_TOKEN),
                 regex::escape(END_ENV_TOKEN)
             ))
@@ -113,6 +160,23 @@ impl<'a> App<'a> {
 
             mutli_option_save_request: None,
             multi_option_new_request_mode: None,
+            history_view: None,
+            export_popup: None,
+            collection_export: None,
+            import_menu: None,
+            import_path: None,
+            curl_import: None,
+            har_import: None,
+            command_palette: None,
+            quick_open: None,
+            variables_view: None,
+            import_report: match keymap::get().problems() {
+                [] => None,
+                problems => Some(ReportPopupComponent::new(
+                    "Key bindings".to_string(),
+                    problems.to_vec(),
+                )),
+            },
 
             req_tabs: RequestTabComponent::new(),
             resp_tabs: ResponseTabComponent::new(),
@@ -121,10 +185,11 @@ impl<'a> App<'a> {
 
             graphql: GraphQL::new().unwrap(),
             gql_ui: graphql::GqlUI::new(),
+            area: Rect::default(),
         }
     }
     pub fn load_envs() -> Result<Vec<Environment>, Error> {
-        let path = format!("{}/{}", DATA_DIRECTORY, ENV_PATH);
+        let path = format!("{}/{}", config::data_dir(), ENV_PATH);
         let file = fs::File::open(&path);
         let mut result = Vec::new();
         match file {
@@ -142,6 +207,9 @@ impl<'a> App<'a> {
                 _ => return Err(Error::FileOperationsErr(e)),
             },
         }
+        if result.is_empty() {
+            result.push(Environment::new("default".to_string()));
+        }
         Ok(result)
     }
     pub async fn run<B: Backend>(mut self, term: &mut Terminal<B>) -> () {
@@ -163,11 +231,30 @@ impl<'a> App<'a> {
         }
     }
     pub async fn update(&mut self) -> Result<Option<String>, Error> {
-        if let Event::Key(key) = event::read().unwrap() {
+        let event = event::read().unwrap();
+        if let Some(mouse) = match event {
+            Event::Mouse(mouse) => transform_mouse(mouse),
+            _ => None,
+        } {
+            self.mouse_update(mouse)?;
+            return Ok(None);
+        }
+        if let Event::Key(key) = event {
             let even = transform(key);
-            if is_quit(&even) {
+            let action = keymap::get().action(&self.main_window, &even);
+            if action == Some(KeyAction::Quit) {
                 return Ok(Some("quit".to_string()));
             }
+            if let Some(command_palette) = &mut self.command_palette {
+                let (action, keep_open) = command_palette.update(&even);
+                if !keep_open {
+                    self.command_palette = None;
+                }
+                if let Some(action) = action {
+                    return self.run_action(action).await;
+                }
+                return Ok(None);
+            }
             if let Some(new_request_mode) = &mut self.multi_option_new_request_mode {
                 if let Some(mode) = new_request_mode.update(&even) {
                     self.new_request(mode);
@@ -176,8 +263,14 @@ impl<'a> App<'a> {
                 }
             }
 
-            if is_navigation(&even) && matches!(self.main_window, MainWindows::Main) {
-                self.navigation(&even);
+            if let Some(
+                nav @ (KeyAction::NavUp
+                | KeyAction::NavDown
+                | KeyAction::NavLeft
+                | KeyAction::NavRight),
+            ) = action
+            {
+                self.navigation(nav);
                 return Ok(None);
             }
             match self.main_window {
@@ -193,46 +286,11 @@ impl<'a> App<'a> {
                     self.collection_main_window_update(&even)?;
                 }
             };
-            match key_registry(&even, &self.main_window) {
-                ChangeEvent::ChangeRequestTab => {
-                    self.req_tabs.update_inner_focus();
-                    return Ok(None);
-                }
-                ChangeEvent::ChangeResponseTab => {
-                    self.resp_tabs.update_inner_focus();
-                    return Ok(None);
-                }
-                ChangeEvent::SaveRequest => {
-                    self.save_current_req()?;
-                    return Ok(None);
-                }
-                ChangeEvent::NewRequest => {
-                    self.multi_option_new_request_mode = Some(MultiOptionWidget::new(vec![
-                        request::Mode::REST,
-                        request::Mode::GraphQL,
-                    ]));
-                    return Ok(None);
-                }
-                ChangeEvent::PreRequest => {
-                    self.pre_req();
-                    self.change_request();
-                    return Ok(None);
-                }
-                ChangeEvent::NextRequest => {
-                    self.next_req();
-                    self.change_request();
-                    return Ok(None);
-                }
-                ChangeEvent::CallRequest => {
-                    match self.call_request().await {
-                        Ok(_) => {}
-                        Err(e) => {
-                            self.error_pop_up = (true, Some(e));
-                        }
-                    }
-                    return Ok(None);
-                }
-                ChangeEvent::NoChange => (),
+            if self
+                .handle_change(key_registry(&even, &self.main_window))
+                .await?
+            {
+                return Ok(None);
             }
             if self.req_tabs.is_focused() {
                 if matches!(
@@ -277,6 +335,119 @@ impl<'a> App<'a> {
         }
         return Ok(None);
     }
+    /// Applies a change made by a key or the command palette. Returns false
+    /// when there was nothing to do.
+    async fn handle_change(&mut self, change: ChangeEvent) -> Result<bool, Error> {
+        match change {
+            ChangeEvent::ChangeRequestTab => {
+                self.req_tabs.update_inner_focus();
+                return Ok(true);
+            }
+            ChangeEvent::ChangeResponseTab => {
+                self.resp_tabs.update_inner_focus();
+                return Ok(true);
+            }
+            ChangeEvent::SaveRequest => {
+                self.save_current_req()?;
+                return Ok(true);
+            }
+            ChangeEvent::NewRequest => {
+                self.multi_option_new_request_mode = Some(MultiOptionWidget::new(vec![
+                    request::Mode::REST,
+                    request::Mode::GraphQL,
+                ]));
+                return Ok(true);
+            }
+            ChangeEvent::PreRequest => {
+                self.pre_req();
+                self.change_request();
+                return Ok(true);
+            }
+            ChangeEvent::NextRequest => {
+                self.next_req();
+                self.change_request();
+                return Ok(true);
+            }
+            ChangeEvent::CallRequest => {
+                match self.call_request().await {
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.error_pop_up = (true, Some(e));
+                    }
+                }
+                return Ok(true);
+            }
+            ChangeEvent::ShowHistory => {
+                self.history_view = Some(HistoryComponent::new());
+                return Ok(true);
+            }
+            ChangeEvent::Import => {
+                self.import_menu = Some(MultiOptionWidget::new(vec![
+                    ImportOptions::Curl,
+                    ImportOptions::PostmanCollection,
+                    ImportOptions::PostmanEnvironment,
+                    ImportOptions::OpenApi,
+                    ImportOptions::Har,
+                ]));
+                return Ok(true);
+            }
+            ChangeEvent::ExportRequest => {
+                self.refresh_folder_defaults()?;
+                self.export_popup = Some(self.request_export_popup());
+                return Ok(true);
+            }
+            ChangeEvent::CommandPalette => {
+                self.command_palette = Some(CommandPaletteComponent::new(&self.main_window));
+                return Ok(true);
+            }
+            ChangeEvent::QuickOpen => {
+                let root = format!("{}/{}", config::data_dir(), COLLECTION_PATH);
+                let requests = saved_requests(&root)?;
+                self.quick_open = Some(QuickOpenComponent::new(&root, requests));
+                return Ok(true);
+            }
+            ChangeEvent::ShowVariables => {
+                self.refresh_folder_defaults()?;
+                let variables = self.scoped_variables().resolved();
+                self.variables_view = Some(VariablesComponent::new(variables));
+                return Ok(true);
+            }
+            ChangeEvent::NextEnvironment => {
+                if !self.all_envs.is_empty() {
+                    self.current_env_idx = (self.current_env_idx + 1) % self.all_envs.len();
+                }
+                return Ok(true);
+            }
+            ChangeEvent::ToggleBodyKind => {
+                let req = &mut self.requests[self.current_request_idx];
+                if !matches!(req.mode, request::Mode::GraphQL) {
+                    self.req_tabs.next_body_kind(req);
+                }
+                return Ok(true);
+            }
+            ChangeEvent::NoChange => (),
+        }
+        Ok(false)
+    }
+    /// Runs an action chosen in the command palette, as its key would.
+    async fn run_action(&mut self, action: KeyAction) -> Result<Option<String>, Error> {
+        match action {
+            KeyAction::Quit => return Ok(Some("quit".to_string())),
+            KeyAction::NavUp | KeyAction::NavDown | KeyAction::NavLeft | KeyAction::NavRight => {
+                self.navigation(action)
+            }
+            KeyAction::OpenCollections => self.main_window = MainWindows::Collections,
+            KeyAction::OpenEnvironments => self.open_environments(),
+            _ => {
+                self.handle_change(change_event(Some(action))).await?;
+            }
+        }
+        Ok(None)
+    }
+    fn open_environments(&mut self) {
+        self.main_window = MainWindows::Environments;
+        self.temp_envs = Some(TempEnv::new(self.all_envs.clone(), self.current_env_idx));
+    }
     fn change_request(&mut self) {
         let req = &self.requests[self.current_request_idx];
         let was_focused = self.address_bar.is_focused();
@@ -285,12 +456,31 @@ impl<'a> App<'a> {
             self.address_bar.gain_focus();
         }
         self.req_tabs = RequestTabComponent::from(req, self.req_tabs.is_focused());
+        // a broken defaults file is reported when the request is sent
+        self.folder_defaults = self.load_folder_defaults().unwrap_or_default();
+        self.req_tabs.set_inherited(&self.folder_defaults);
+    }
+    /// Reads the defaults of the current request's folders again, the files
+    /// may have changed since it was opened.
+    fn refresh_folder_defaults(&mut self) -> Result<(), Error> {
+        self.folder_defaults = self.load_folder_defaults()?;
+        self.req_tabs.set_inherited(&self.folder_defaults);
+        Ok(())
+    }
+    /// The defaults of the folders the current request is saved in.
+    fn load_folder_defaults(&self) -> Result<FolderDefaults, Error> {
+        match self.requests[self.current_request_idx].collection_path() {
+            Some(path) => FolderDefaults::resolve(&path),
+            None => Ok(FolderDefaults::default()),
+        }
     }
 
     fn reload_collections(&mut self) {
-        self.collections = Collection::default(format!("{}/{}", DATA_DIRECTORY, COLLECTION_PATH));
+        self.collections =
+            Collection::default(format!("{}/{}", config::data_dir(), COLLECTION_PATH));
     }
     fn ui(&mut self, f: &mut Frame) {
+        self.area = f.area();
         let lay = layout::AppLayout::new(f.area());
 
         if matches!(
@@ -300,6 +490,9 @@ impl<'a> App<'a> {
             self.graphql
                 .draw(f, &self.requests[self.current_request_idx], lay.request);
         } else {
+            // the environment may have been switched since the last frame
+            self.req_tabs
+                .set_inherited_variables(self.inherited_variables().resolved());
             self.req_tabs
                 .draw(f, &self.requests[self.current_request_idx], lay.request);
         }
@@ -326,6 +519,43 @@ impl<'a> App<'a> {
         if let Some(save_req_opt) = &mut self.mutli_option_save_request {
             save_req_opt.draw(f, f.area());
         }
+        if let Some(history_view) = &self.history_view {
+            history_view.draw(
+                f,
+                self.requests[self.current_request_idx].history(),
+                f.area(),
+            );
+        }
+        if let Some(export_popup) = &self.export_popup {
+            export_popup.draw(f, f.area());
+        }
+        if let Some(import_menu) = &mut self.import_menu {
+            import_menu.draw(f, centered_rect(40, 30, f.area()));
+        }
+        if let Some((_, export_menu)) = &mut self.collection_export {
+            export_menu.draw(f, centered_rect(40, 20, f.area()));
+        }
+        if let Some((_, import_path)) = &self.import_path {
+            import_path.draw(f, centered_rect(50, 10, f.area()));
+        }
+        if let Some(curl_import) = &mut self.curl_import {
+            curl_import.draw(f, f.area());
+        }
+        if let Some(har_import) = &self.har_import {
+            har_import.draw(f, f.area());
+        }
+        if let Some(import_report) = &self.import_report {
+            import_report.draw(f, f.area());
+        }
+        if let Some(command_palette) = &self.command_palette {
+            command_palette.draw(f, f.area());
+        }
+        if let Some(quick_open) = &self.quick_open {
+            quick_open.draw(f, f.area());
+        }
+        if let Some(variables_view) = &self.variables_view {
+            variables_view.draw(f, f.area());
+        }
         if matches!(self.main_window, MainWindows::Collections) {
             self.collections.draw(f);
         }
@@ -357,70 +587,166 @@ impl<'a> App<'a> {
         self.current_request_idx -= 1;
     }
     pub async fn call_request(&mut self) -> Result<(), Error> {
+        self.refresh_folder_defaults()?;
         let current_request = &self.requests[self.current_request_idx];
-        let headers = HeaderMap::try_from(&self.replace_envs(current_request.handle_headers()))
-            .unwrap_or(HeaderMap::new());
-        let body: Option<serde_json::Value>;
-        let params = self.replace_envs(current_request.handle_params());
+        let mut headers: HashMap<String, String> =
+            self.folder_defaults.headers().into_iter().collect();
+        for (key, value) in current_request.handle_headers() {
+            headers.retain(|k, _| !k.eq_ignore_ascii_case(&key));
+            headers.insert(key, value);
+        }
+        let headers = HeaderMap::try_from(&self.replace_envs(headers)).unwrap_or(HeaderMap::new());
+        let has_content_type = headers.contains_key(reqwest::header::CONTENT_TYPE);
+        let mut params: HashMap<String, String> =
+            self.folder_defaults.params().into_iter().collect();
+        params.extend(current_request.handle_params());
+        let params = self.replace_envs(params);
         let addr = self.replace_envs(current_request.address().to_string());
-        let resp: Response;
-        match current_request.verb() {
-            HttpVerb::GET => {
-                resp = self
-                    .client
-                    .get(addr)
-                    .query(&params)
-                    .headers(headers)
-                    .send()
-                    .await
-                    .map_err(|e| Error::ReqwestErr(e))?;
-            }
-            HttpVerb::POST => {
-                body = current_request.handle_json_body()?;
-                let mut r = self.client.post(addr).query(&params).headers(headers);
-                if let Some(b) = body {
-                    r = r.json(&b)
-                };
-                resp = r.send().await.map_err(|e| Error::ReqwestErr(e))?;
-            }
-            HttpVerb::PUT => {
-                body = current_request.handle_json_body()?;
-                let mut r = self.client.put(addr).query(&params).headers(headers);
-                if let Some(b) = body {
-                    r = r.json(&b)
-                };
-                resp = r.send().await.map_err(|e| Error::ReqwestErr(e))?;
-            }
-            HttpVerb::DELETE => {
-                resp = self
-                    .client
-                    .get(addr)
-                    .query(&params)
-                    .headers(headers)
-                    .send()
-                    .await
-                    .map_err(|e| Error::ReqwestErr(e))?;
+        let addr = self.replace_envs(self.folder_defaults.address(addr));
+        let mut r = self
+            .client
+            .request(current_request.verb().method(), addr)
+            .query(&params)
+            .headers(headers);
+        if let Some(timeout) = self.folder_defaults.timeout() {
+            r = r.timeout(timeout);
+        }
+        if current_request.verb().sends_body() {
+            let body = current_request.body();
+            match body.kind {
+                BodyKind::JSON => {
+                    // resolved before parsing, a variable may stand for a number
+                    if let Some(payload) = body.payload.filter(|p| !p.is_empty()) {
+                        let b: serde_json::Value =
+                            serde_json::from_str(&self.replace_envs(payload))?;
+                        r = r.json(&b)
+                    }
+                }
+                BodyKind::FORM => {
+                    let pairs = body
+                        .pairs()
+                        .into_iter()
+                        .map(|(k, v)| (self.replace_envs(k), self.replace_envs(v)))
+                        .collect::<Vec<(String, String)>>();
+                    r = r.form(&pairs);
+                }
+                BodyKind::MULTIPART => {
+                    let mut form = multipart::Form::new();
+                    for (k, v) in body.pairs() {
+                        let (k, v) = (self.replace_envs(k), self.replace_envs(v));
+                        form = match v.strip_prefix('@') {
+                            Some(path) => {
+                                let file_name = std::path::Path::new(path)
+                                    .file_name()
+                                    .map(|n| n.to_string_lossy().to_string())
+                                    .unwrap_or_default();
+                                form.part(
+                                    k,
+                                    multipart::Part::bytes(fs::read(path)?).file_name(file_name),
+                                )
+                            }
+                            None => form.text(k, v),
+                        };
+                    }
+                    r = r.multipart(form);
+                }
+                BodyKind::TEXT | BodyKind::XML | BodyKind::GRAPHQL => {
+                    if let Some(payload) = body.payload.filter(|p| !p.is_empty()) {
+                        if let (false, Some(content_type)) =
+                            (has_content_type, export::content_type(&body.kind))
+                        {
+                            r = r.header(reqwest::header::CONTENT_TYPE, content_type);
+                        }
+                        r = r.body(self.replace_envs(payload));
+                    }
+                }
             }
         }
+        let resp: Response = r.send().await.map_err(|e| Error::ReqwestErr(e))?;
         let current_request = &mut self.requests[self.current_request_idx];
         current_request.set_response_status_code(resp.status().as_u16() as i32);
         current_request
             .set_response_headers(&resp.headers().clone())
             .unwrap();
         current_request.set_response_body(resp.text().await.map_err(|e| Error::ReqwestErr(e))?);
+        current_request.push_history(
+            self.all_envs
+                .get(self.current_env_idx)
+                .map_or("-".to_string(), |e| e.name.clone()),
+        )?;
         Ok(())
     }
+    /// The current request as cURL and as client code snippets.
+    fn request_export_popup(&self) -> ExportPopupComponent {
+        let req = &self.requests[self.current_request_idx];
+        let unresolved = RequestParts::from_request(req, &self.folder_defaults, &|s| s);
+        let variables = self.variables();
+        let resolved = RequestParts::from_request(req, &self.folder_defaults, &|s| {
+            s.replace_env(&self.regex_replacer, &variables)
+        });
+        ExportPopupComponent::new(
+            "Export request".to_string(),
+            export::Language::ALL
+                .iter()
+                .map(|language| Snippet {
+                    language: language.name().to_string(),
+                    extension: language.extension().to_string(),
+                    unresolved: export::to_snippet(*language, &unresolved),
+                    resolved: export::to_snippet(*language, &resolved),
+                })
+                .collect(),
+            format!(
+                "{}/{}/{}",
+                config::data_dir(),
+                EXPORT_PATH,
+                req.name()
+                    .replace(|c: char| !c.is_alphanumeric() && c != '-', "_")
+            ),
+        )
+    }
+    /// The variables of the current request by scope: global, collection and
+    /// folders, environment, then the request, the later ones winning.
+    fn scoped_variables(&self) -> Variables {
+        let mut variables = self.inherited_variables();
+        // request variables may refer to the scopes below them
+        let below = variables.values();
+        variables.push(
+            Scope::Request,
+            self.requests[self.current_request_idx]
+                .variables()
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        k.clone(),
+                        v.clone().replace_env(&self.regex_replacer, &below),
+                    )
+                }),
+        );
+        variables
+    }
+    /// The variables of every scope below the current request.
+    fn inherited_variables(&self) -> Variables {
+        let mut variables = Variables::default();
+        variables.push(Scope::Global, config::preferences().variables.clone());
+        for (scope, layer) in &self.folder_defaults.variable_scopes {
+            variables.push(scope.clone(), layer.clone());
+        }
+        if let Some(env) = self.all_envs.get(self.current_env_idx) {
+            variables.push(Scope::Environment(env.name.clone()), env.envs.clone());
+        }
+        variables
+    }
+    fn variables(&self) -> HashMap<String, String> {
+        self.scoped_variables().values()
+    }
     fn replace_envs<T>(&self, to_replace: T) -> T
     where
         T: Clone + EnvReplacer,
     {
-        to_replace.replace_env(
-            &self.regex_replacer,
-            &self.all_envs[self.current_env_idx].envs,
-        )
+        to_replace.replace_env(&self.regex_replacer, &self.variables())
     }
     pub fn save_env(environments: Vec<Environment>) -> Result<(), Error> {
-        let path = format!("{}/{}", DATA_DIRECTORY, ENV_PATH);
+        let path = format!("{}/{}", config::data_dir(), ENV_PATH);
         for env in environments.iter() {
             match fs::metadata(path.clone()) {
                 Ok(f) => {
@@ -460,9 +786,9 @@ impl<'a> App<'a> {
         self.mutli_option_save_request = Some(MultiOptionWidget::new(options));
         Ok(())
     }
-    fn navigation(&mut self, e: &AppEvent) {
-        match e {
-            NAV_UP => {
+    fn navigation(&mut self, action: KeyAction) {
+        match action {
+            KeyAction::NavUp => {
                 if self.req_tabs.is_focused() {
                     self.req_tabs
                         .lose_focus(&mut self.requests[self.current_request_idx]);
@@ -478,7 +804,7 @@ impl<'a> App<'a> {
                     self.resp_tabs.gain_focus();
                 }
             }
-            NAV_DOWN => {
+            KeyAction::NavDown => {
                 if self.req_tabs.is_focused() {
                     self.req_tabs
                         .lose_focus(&mut self.requests[self.current_request_idx]);
@@ -494,39 +820,59 @@ impl<'a> App<'a> {
                     self.address_bar.gain_focus();
                 }
             }
-            NAV_LEFT => (),
-            NAV_RIGHT => (),
             _ => (),
         }
     }
     fn delete_request(&mut self, paths: Vec<String>) -> Result<(), Error> {
         if let Some(path) = paths.last() {
+            if let (file, Some(idx)) = http_file::split_location(path) {
+                return self.remove_http_request(file, idx);
+            }
             match fs::metadata(path.clone()) {
                 Ok(f) => {
+                    // read before deleting, the histories of the requests
+                    // that go are dropped with them
+                    let saved = saved_requests(path)?;
                     if f.is_file() {
                         fs::remove_file(path.clone())?;
-                        return Ok(());
                     } else {
                         let mut is_empty = true;
+                        // the order and defaults of the folder go with it
+                        let mut settings = Vec::new();
                         for entry in fs::read_dir(path.clone())? {
                             let entry = entry?;
-                            match entry.path().extension() {
-                                Some(ext) => {
-                                    if ext == "rph" {
-                                        fs::remove_file(entry.path())?;
-                                    }
-                                }
-                                None => {
-                                    is_empty = false;
-                                    continue;
-                                }
-                            };
+                            if entry.file_name() == collection::ORDER_FILE
+                                || entry.file_name() == DEFAULTS_FILE
+                            {
+                                settings.push(entry.path());
+                                continue;
+                            }
+                            let file = entry.path();
+                            if file.is_file()
+                                && (file.extension().is_some_and(|ext| ext == "rph")
+                                    || http_file::is_http_file(&file.to_string_lossy()))
+                            {
+                                fs::remove_file(file)?;
+                            } else {
+                                is_empty = false;
+                            }
                         }
-                        if is_empty && *path != format!("{}/{}", DATA_DIRECTORY, COLLECTION_PATH) {
+                        if is_empty
+                            && *path != format!("{}/{}", config::data_dir(), COLLECTION_PATH)
+                        {
+                            for file in settings {
+                                fs::remove_file(file)?;
+                            }
                             fs::remove_dir(path.clone())?;
                         }
-                        return Ok(());
                     }
+                    for (location, _) in saved {
+                        let (file, _) = http_file::split_location(&location);
+                        if !std::path::Path::new(file).exists() {
+                            History::remove(&location)?;
+                        }
+                    }
+                    return Ok(());
                 }
                 Err(e) => {
                     self.error_pop_up = (true, Some(Error::FileOperationsErr(e)));
@@ -536,38 +882,57 @@ impl<'a> App<'a> {
         }
         Err(Error::NoRequestErr(1))
     }
+    /// Removes a request of a `.http` file. The requests after it move up
+    /// one block, so their open tabs and histories follow them.
+    fn remove_http_request(&mut self, file: &str, idx: usize) -> Result<(), Error> {
+        let mut http_file = HttpFile::read(file)?;
+        let later: Vec<usize> = http_file
+            .requests()
+            .into_iter()
+            .map(|(k, _)| k)
+            .filter(|k| *k > idx)
+            .collect();
+        http_file.remove_request(idx)?;
+        http_file.write(file)?;
+        History::remove(&http_file::location(file, idx))?;
+        for k in later {
+            History::relocate(
+                &http_file::location(file, k),
+                &http_file::location(file, k - 1),
+            )?;
+        }
+        for req in self.requests.iter_mut() {
+            match req
+                .collection_path()
+                .as_deref()
+                .map(http_file::split_location)
+            {
+                Some((f, Some(k))) if f == file && k == idx => req.clear_collection_path(),
+                Some((f, Some(k))) if f == file && k > idx => {
+                    req.set_collection_path(http_file::location(file, k - 1))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
     fn add_request_from_collection(&mut self, paths: Vec<String>) -> Result<(), Error> {
         if let Some(path) = paths.last() {
+            if let (_, Some(_)) = http_file::split_location(path) {
+                self.requests.extend(load_collection_file(path)?);
+                return Ok(());
+            }
             match fs::metadata(path.clone()) {
                 Ok(f) => {
                     if f.is_file() {
-                        self.requests.push({
-                            let mut req: super::request::Request =
-                                serde_json::from_reader(fs::File::open(path.clone())?)?;
-                            req.set_collection_path(path.to_owned());
-                            //trace_dbg!(level: tracing::Level::INFO, &req);
-                            req
-                        });
+                        self.requests.extend(load_collection_file(path)?);
                         return Ok(());
                     } else {
-                        for entry in fs::read_dir(path.clone())? {
-                            let entry = entry?;
-                            match entry.path().extension() {
-                                Some(ext) => {
-                                    if ext == "rph" {
-                                        self.requests.push({
-                                            let mut req: super::Request = serde_json::from_reader(
-                                                fs::File::open(entry.path())?,
-                                            )?;
-                                            req.set_collection_path(
-                                                entry.path().to_string_lossy().to_string(),
-                                            );
-                                            req
-                                        });
-                                    }
-                                }
-                                None => continue,
-                            };
+                        for entry in collection::ordered_entries(std::path::Path::new(path))? {
+                            if entry.is_file() {
+                                self.requests
+                                    .extend(load_collection_file(&entry.to_string_lossy())?);
+                            }
                         }
                         return Ok(());
                     }
@@ -583,41 +948,433 @@ impl<'a> App<'a> {
 
     fn create_new_collection(&mut self, paths: Vec<String>) -> Result<(), Error> {
         if let Some(path) = paths.last() {
-            fs::create_dir(path.clone())?;
+            if http_file::is_http_file(path) {
+                fs::File::create_new(path)?;
+            } else {
+                fs::create_dir(path.clone())?;
+            }
             return Ok(());
         };
         //trace_dbg!(level: tracing::Level::INFO, ("in NONE", &paths));
         return Err(Error::NoRequestErr(1));
     }
+    /// Renames a file or folder, keeping its extension when none is given,
+    /// or a request of a `.http` file. Returns the new location.
+    fn rename_entry(&mut self, location: &str, name: &str) -> Result<String, Error> {
+        if name.contains('/') {
+            return Err(Error::InputErr(format!("{} is not a valid name", name)));
+        }
+        if let (file, Some(idx)) = http_file::split_location(location) {
+            let mut http_file = HttpFile::read(file)?;
+            let mut req = http_file.request(idx).ok_or(Error::NoRequestErr(idx))?;
+            req.set_name(name.to_string());
+            http_file.set_request(idx, &req)?;
+            http_file.write(file)?;
+            for req in self.requests.iter_mut() {
+                if req.collection_path().as_deref() == Some(location) {
+                    req.set_name(name.to_string());
+                }
+            }
+            return Ok(location.to_string());
+        }
+        let path = std::path::Path::new(location);
+        let name = match path.extension() {
+            Some(ext) if path.is_file() && std::path::Path::new(name).extension().is_none() => {
+                format!("{}.{}", name, ext.to_string_lossy())
+            }
+            _ => name.to_string(),
+        };
+        let target = path.with_file_name(name);
+        self.relocate(path, &target)?;
+        if let Some(name) = rename_rph(&target)? {
+            let location = target.to_string_lossy();
+            for req in self.requests.iter_mut() {
+                if req.collection_path().as_deref() == Some(location.as_ref()) {
+                    req.set_name(name.clone());
+                }
+            }
+        }
+        Ok(target.to_string_lossy().to_string())
+    }
+    /// Moves a file or folder into the selected folder, or next to the
+    /// selected file. Returns the new location.
+    fn move_entry(&mut self, source: &str, paths: Vec<String>) -> Result<String, Error> {
+        if let (_, Some(_)) = http_file::split_location(source) {
+            return Err(Error::InputErr(
+                "requests of a .http file move with their file".to_string(),
+            ));
+        }
+        let selected = paths.last().ok_or(Error::NoRequestErr(1))?;
+        let (selected, _) = http_file::split_location(selected);
+        let selected = std::path::Path::new(selected);
+        let folder = if selected.is_dir() {
+            selected
+        } else {
+            selected.parent().ok_or(Error::NoRequestErr(1))?
+        };
+        let source = std::path::Path::new(source);
+        if folder.starts_with(source) {
+            return Err(Error::InputErr(format!(
+                "{} cannot be moved into itself",
+                source.display()
+            )));
+        }
+        let target = folder.join(source.file_name().ok_or(Error::NoRequestErr(1))?);
+        if target != source {
+            self.relocate(source, &target)?;
+        }
+        Ok(target.to_string_lossy().to_string())
+    }
+    /// Renames or moves a file or folder, along with the history of its
+    /// requests, its place in the folder order and the open tabs saved in it.
+    fn relocate(&mut self, from: &std::path::Path, to: &std::path::Path) -> Result<(), Error> {
+        if to.exists() {
+            return Err(Error::InputErr(format!("{} already exists", to.display())));
+        }
+        let (from_str, to_str) = (from.to_string_lossy(), to.to_string_lossy());
+        let moved = |location: &str| {
+            location
+                .strip_prefix(from_str.as_ref())
+                .filter(|rest| rest.is_empty() || rest.starts_with(['/', '#']))
+                .map(|rest| format!("{}{}", to_str, rest))
+        };
+        let locations = saved_requests(&from_str)?;
+        let parent = from.parent().ok_or(Error::NoRequestErr(1))?;
+        let mut order = collection::ordered_entries(parent)?;
+        fs::rename(from, to)?;
+        for (location, _) in locations {
+            if let Some(new_location) = moved(&location) {
+                History::relocate(&location, &new_location)?;
+            }
+        }
+        for req in self.requests.iter_mut() {
+            if let Some(new_location) = req.collection_path().and_then(|p| moved(&p)) {
+                req.set_collection_path(new_location);
+            }
+        }
+        if parent.join(collection::ORDER_FILE).exists() {
+            match order.iter().position(|p| p == from) {
+                Some(idx) if to.parent() == Some(parent) => order[idx] = to.to_path_buf(),
+                Some(idx) => {
+                    order.remove(idx);
+                }
+                None => (),
+            }
+            collection::save_order(parent, &order)?;
+        }
+        Ok(())
+    }
+    /// Copies a file or folder next to itself, or a request of a `.http` file
+    /// to the end of the file. Returns the location of the copy.
+    fn duplicate_entry(&mut self, location: &str) -> Result<String, Error> {
+        if let (file, Some(idx)) = http_file::split_location(location) {
+            let mut http_file = HttpFile::read(file)?;
+            let mut req = http_file.request(idx).ok_or(Error::NoRequestErr(idx))?;
+            req.set_name(format!("{} copy", req.name()));
+            let copy = http_file.push_request(&req);
+            http_file.write(file)?;
+            return Ok(http_file::location(file, copy));
+        }
+        let path = std::path::Path::new(location);
+        let parent = path
+            .parent()
+            .filter(|_| *location != format!("{}/{}", config::data_dir(), COLLECTION_PATH))
+            .ok_or(Error::InputErr(
+                "the collections folder cannot be duplicated".to_string(),
+            ))?;
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = path
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+        let target = (1..)
+            .map(|n| match n {
+                1 => parent.join(format!("{} copy{}", stem, extension)),
+                n => parent.join(format!("{} copy {}{}", stem, n, extension)),
+            })
+            .find(|p| !p.exists())
+            .expect("some copy name is free");
+        if path.is_dir() {
+            copy_dir(path, &target)?;
+        } else {
+            fs::copy(path, &target)?;
+            rename_rph(&target)?;
+        }
+        if parent.join(collection::ORDER_FILE).exists() {
+            let mut order = collection::ordered_entries(parent)?;
+            order.retain(|p| *p != target);
+            let idx = order
+                .iter()
+                .position(|p| p == path)
+                .map_or(order.len(), |i| i + 1);
+            order.insert(idx, target.clone());
+            collection::save_order(parent, &order)?;
+        }
+        Ok(target.to_string_lossy().to_string())
+    }
+    /// Swaps a file or folder with its neighbour in the folder order.
+    fn reorder_entry(&mut self, location: &str, up: bool) -> Result<(), Error> {
+        if let (_, Some(_)) = http_file::split_location(location) {
+            return Err(Error::InputErr(
+                "requests of a .http file keep the order of the file".to_string(),
+            ));
+        }
+        if *location == format!("{}/{}", config::data_dir(), COLLECTION_PATH) {
+            return Ok(());
+        }
+        let path = std::path::Path::new(location);
+        let parent = path.parent().ok_or(Error::NoRequestErr(1))?;
+        let mut order = collection::ordered_entries(parent)?;
+        let Some(idx) = order.iter().position(|p| p == path) else {
+            return Ok(());
+        };
+        let other = if up {
+            idx.checked_sub(1)
+        } else {
+            Some(idx + 1)
+        };
+        if let Some(other) = other.filter(|other| *other < order.len()) {
+            order.swap(idx, other);
+            collection::save_order(parent, &order)?;
+        }
+        Ok(())
+    }
 
     pub fn collection_main_window_update(&mut self, even: &AppEvent) -> Result<Option<()>, Error> {
-        if even == CLOSE_COLLECTIONS {
+        if keymap::get().is(&self.main_window, even, KeyAction::Close) {
             self.main_window = MainWindows::Main;
             return Ok(None);
         };
         if let Some((caller, action, paths)) = self.collections.update(&even) {
-            match action {
-                Action::Delete => self.delete_request(paths)?,
-                Action::Create => self.create_new_collection(paths)?,
-                Action::AddRequest => {
-                    match caller {
-                        Some(_) => self::update_request_collection(
-                            &mut self.requests[self.current_request_idx],
-                            paths,
-                        )?,
-                        None => {
-                            self.add_request_from_collection(paths)?;
-                            self.change_request();
-                        }
-                    }
-                    self.main_window = MainWindows::Main;
-                }
-            };
-            self.reload_collections();
+            self.collection_action(caller, action, paths)?;
         }
         Ok(None)
     }
+    fn collection_action(
+        &mut self,
+        caller: Option<String>,
+        action: Action,
+        paths: Vec<String>,
+    ) -> Result<(), Error> {
+        let location = paths.last().cloned().unwrap_or_default();
+        match action {
+            // the tree keeps its unfolded folders and follows the entry
+            Action::Rename(name) => {
+                let location = self.rename_entry(&location, &name)?;
+                self.collections.reload(&location);
+                return Ok(());
+            }
+            Action::Duplicate => {
+                let location = self.duplicate_entry(&location)?;
+                self.collections.reload(&location);
+                return Ok(());
+            }
+            Action::Move(source) => {
+                let location = self.move_entry(&source, paths)?;
+                self.collections.reload(&location);
+                return Ok(());
+            }
+            Action::MoveUp | Action::MoveDown => {
+                self.reorder_entry(&location, matches!(action, Action::MoveUp))?;
+                self.collections.reload(&location);
+                return Ok(());
+            }
+            Action::Delete => self.delete_request(paths)?,
+            Action::Create => self.create_new_collection(paths)?,
+            Action::Export => {
+                if let Some(path) = paths.last() {
+                    self.collection_export = Some((
+                        path.clone(),
+                        MultiOptionWidget::new(vec![
+                            ExportOptions::Postman,
+                            ExportOptions::OpenApi,
+                        ]),
+                    ));
+                    self.main_window = MainWindows::Main;
+                }
+            }
+            Action::Migrate => {
+                if let Some(path) = paths.last() {
+                    let (file, _) = http_file::split_location(path);
+                    self.import_report = Some(ReportPopupComponent::new(
+                        "Migrate collection".to_string(),
+                        migrate_collection(file)?,
+                    ));
+                    self.main_window = MainWindows::Main;
+                }
+            }
+            Action::AddRequest => {
+                match caller {
+                    Some(_) => self::update_request_collection(
+                        &mut self.requests[self.current_request_idx],
+                        paths,
+                    )?,
+                    None => {
+                        self.add_request_from_collection(paths)?;
+                        self.change_request();
+                    }
+                }
+                self.main_window = MainWindows::Main;
+            }
+        };
+        self.reload_collections();
+        Ok(())
+    }
     pub fn main_window_update(&mut self, even: &AppEvent) -> Result<Option<()>, Error> {
+        if let Some(history_view) = &mut self.history_view {
+            if !history_view.update(self.requests[self.current_request_idx].history(), even) {
+                self.history_view = None;
+            }
+            return Ok(Some(()));
+        }
+        if let Some(import_report) = &mut self.import_report {
+            if !import_report.update(even) {
+                self.import_report = None;
+            }
+            return Ok(Some(()));
+        }
+        if let Some((path, export_menu)) = &mut self.collection_export {
+            if matches!(even.key, Key::Esc) {
+                self.collection_export = None;
+                return Ok(Some(()));
+            }
+            if let Some(option) = export_menu.update(even) {
+                let path = path.clone();
+                self.collection_export = None;
+                let lines = self.export_collection(&path, &option)?;
+                self.import_report = Some(ReportPopupComponent::new(
+                    format!("Export as {}", option.to_string()),
+                    lines,
+                ));
+            }
+            return Ok(Some(()));
+        }
+        if let Some(import_menu) = &mut self.import_menu {
+            if matches!(even.key, Key::Esc) {
+                self.import_menu = None;
+                return Ok(Some(()));
+            }
+            if let Some(option) = import_menu.update(even) {
+                self.import_menu = None;
+                match option {
+                    ImportOptions::Curl => {
+                        self.curl_import =
+                            Some(TextAreaPopupComponent::new("Import cURL".to_string()));
+                    }
+                    _ => {
+                        let popup = PopUpComponent::new(
+                            format!("{} file path", option.to_string()),
+                            String::new(),
+                        );
+                        self.import_path = Some((option, popup));
+                    }
+                }
+            }
+            return Ok(Some(()));
+        }
+        if let Some((option, import_path)) = &mut self.import_path {
+            let (path, keep_open) = import_path.update(even);
+            let option = option.clone();
+            if !keep_open {
+                self.import_path = None;
+            }
+            if let Some(path) = path {
+                let path = path.trim();
+                if let ImportOptions::Har = option {
+                    let entries =
+                        import::parse_har(&fs::read_to_string(path)?).map_err(Error::InputErr)?;
+                    let folder = std::path::Path::new(path)
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    self.har_import = Some(HarImportComponent::new(entries, folder));
+                } else {
+                    let report = self.import_file(&option, path)?;
+                    self.import_report = Some(ReportPopupComponent::new(
+                        report.title.clone(),
+                        report.lines(),
+                    ));
+                }
+            }
+            return Ok(Some(()));
+        }
+        if let Some(har_import) = &mut self.har_import {
+            let (action, keep_open) = har_import.update(even);
+            if !keep_open {
+                self.har_import = None;
+            }
+            if let Some(action) = action {
+                if let Some(report) = self.import_har(action)? {
+                    self.import_report = Some(ReportPopupComponent::new(
+                        report.title.clone(),
+                        report.lines(),
+                    ));
+                }
+            }
+            return Ok(Some(()));
+        }
+        if let Some(quick_open) = &mut self.quick_open {
+            let (location, keep_open) = quick_open.update(even);
+            if !keep_open {
+                self.quick_open = None;
+            }
+            if let Some(location) = location {
+                self.add_request_from_collection(vec![location])?;
+                self.current_request_idx = self.requests.len() - 1;
+                self.change_request();
+            }
+            return Ok(Some(()));
+        }
+        if let Some(variables_view) = &mut self.variables_view {
+            if !variables_view.update(even) {
+                self.variables_view = None;
+            }
+            return Ok(Some(()));
+        }
+        if let Some(curl_import) = &mut self.curl_import {
+            let (content, keep_open) = curl_import.update(even);
+            let imported = match content.map(|c| import::from_curl(&c)) {
+                Some(Ok(req)) => Some(req),
+                Some(Err(e)) => {
+                    curl_import.set_error(Some(e));
+                    None
+                }
+                None => None,
+            };
+            if !keep_open || imported.is_some() {
+                self.curl_import = None;
+            }
+            if let Some(req) = imported {
+                self.requests.push(req);
+                self.current_request_idx = self.requests.len() - 1;
+                self.change_request();
+            }
+            return Ok(Some(()));
+        }
+        if let Some(export_popup) = &mut self.export_popup {
+            let (target, keep_open) = export_popup.update(even);
+            if !keep_open {
+                self.export_popup = None;
+            }
+            match target {
+                Some(ExportTarget::Clipboard(content)) => {
+                    ClipboardContext::new()
+                        .and_then(|mut ctx| ctx.set_contents(content))
+                        .map_err(|e| Error::InputErr(e.to_string()))?;
+                }
+                Some(ExportTarget::File(path, content)) => {
+                    if let Some(parent) = std::path::Path::new(&path).parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(path, content)?;
+                }
+                None => (),
+            }
+            return Ok(Some(()));
+        }
         if let Some(multi_option) = &mut self.mutli_option_save_request {
             let result = multi_option.update(&even);
             if let Some(s) = result {
@@ -635,17 +1392,129 @@ impl<'a> App<'a> {
             }
             return Ok(Some(()));
         }
-        if matches!(even, OPEN_COLLECTIONS) {
+        if keymap::get().is(&self.main_window, even, KeyAction::OpenCollections) {
             self.main_window = MainWindows::Collections;
             return Ok(Some(()));
         };
-        if matches!(even, OPEN_ENVIRONMENTS) {
-            self.main_window = MainWindows::Environments;
-            self.temp_envs = Some(TempEnv::new(self.all_envs.clone(), self.current_env_idx));
+        if keymap::get().is(&self.main_window, even, KeyAction::OpenEnvironments) {
+            self.open_environments();
             return Ok(Some(()));
         }
         Ok(None)
     }
+    /// Clicks focus and select what is under the pointer, the wheel scrolls
+    /// it. Popups only take the keyboard, so the mouse waits until they close.
+    fn mouse_update(&mut self, mouse: Mouse) -> Result<(), Error> {
+        if self.multi_option_new_request_mode.is_some()
+            || self.mutli_option_save_request.is_some()
+            || self.history_view.is_some()
+            || self.export_popup.is_some()
+            || self.collection_export.is_some()
+            || self.import_menu.is_some()
+            || self.import_path.is_some()
+            || self.curl_import.is_some()
+            || self.har_import.is_some()
+            || self.import_report.is_some()
+            || self.command_palette.is_some()
+            || self.quick_open.is_some()
+            || self.variables_view.is_some()
+        {
+            return Ok(());
+        }
+        match self.main_window {
+            MainWindows::Collections => match mouse {
+                Mouse::Click(position) => {
+                    if let Some((caller, action, paths)) = self.collections.click(position) {
+                        self.collection_action(caller, action, paths)?;
+                    }
+                }
+                Mouse::ScrollUp(_) => self.collections.scroll(false),
+                Mouse::ScrollDown(_) => self.collections.scroll(true),
+            },
+            MainWindows::Environments => {
+                if let Some(temp) = &mut self.temp_envs {
+                    match mouse {
+                        Mouse::Click(position) => temp.click(self.area, position),
+                        Mouse::ScrollUp(position) => temp.scroll(self.area, position, false),
+                        Mouse::ScrollDown(position) => temp.scroll(self.area, position, true),
+                    }
+                }
+            }
+            MainWindows::Main => {
+                let position = mouse.position();
+                let Some((pane, rect)) = layout::AppLayout::new(self.area).pane_at(position) else {
+                    return Ok(());
+                };
+                match mouse {
+                    Mouse::Click(_) => self.click_pane(pane, rect, position),
+                    Mouse::ScrollUp(_) => self.scroll_pane(pane, rect, position, false),
+                    Mouse::ScrollDown(_) => self.scroll_pane(pane, rect, position, true),
+                }
+            }
+        }
+        Ok(())
+    }
+    fn click_pane(&mut self, pane: Pane, rect: Rect, position: Position) {
+        self.focus_pane(pane);
+        match pane {
+            Pane::Requests => {
+                let names = self.requests.iter().map(|r| r.name().clone()).collect();
+                if let Some(RequestsAction::RequestIndexChanged) = self.requests_component.click(
+                    names,
+                    &mut self.current_request_idx,
+                    rect,
+                    position,
+                ) {
+                    self.change_request();
+                }
+            }
+            Pane::AddressVerb => self.address_bar.click(rect, position),
+            Pane::Request => {
+                let req = &mut self.requests[self.current_request_idx];
+                if !matches!(req.mode, request::Mode::GraphQL) {
+                    self.req_tabs.click(req, rect, position);
+                }
+            }
+            Pane::Response => {
+                self.resp_tabs
+                    .click(&self.requests[self.current_request_idx], rect, position)
+            }
+        }
+    }
+    fn scroll_pane(&mut self, pane: Pane, rect: Rect, position: Position, down: bool) {
+        let req = &mut self.requests[self.current_request_idx];
+        match pane {
+            Pane::Requests => self.requests_component.scroll(
+                &mut self.all_envs,
+                &mut self.current_env_idx,
+                rect,
+                position,
+                down,
+            ),
+            Pane::AddressVerb => self.address_bar.scroll(req, rect, position, down),
+            Pane::Request => {
+                if !matches!(req.mode, request::Mode::GraphQL) {
+                    self.req_tabs.scroll(req, down);
+                }
+            }
+            Pane::Response => self.resp_tabs.scroll(down),
+        }
+    }
+    fn focus_pane(&mut self, pane: Pane) {
+        if self.req_tabs.is_focused() && !matches!(pane, Pane::Request) {
+            self.req_tabs
+                .lose_focus(&mut self.requests[self.current_request_idx]);
+        }
+        self.address_bar.lose_focus();
+        self.resp_tabs.lose_focus();
+        self.requests_component.lose_focus();
+        match pane {
+            Pane::Requests => self.requests_component.gain_focus(),
+            Pane::AddressVerb => self.address_bar.gain_focus(),
+            Pane::Request => self.req_tabs.gain_focus(),
+            Pane::Response => self.resp_tabs.gain_focus(),
+        }
+    }
     pub fn environment_main_window_update(&mut self, even: &AppEvent) -> Result<Option<()>, Error> {
         if let Some(temp) = &mut self.temp_envs {
             let result = temp.update(&even);
@@ -661,21 +1530,365 @@ impl<'a> App<'a> {
         }
         Ok(None)
     }
+    /// Imports a Postman export or an OpenAPI spec into the collections or
+    /// the environments.
+    fn import_file(&mut self, option: &ImportOptions, path: &str) -> Result<import::Report, Error> {
+        let content = fs::read_to_string(path)?;
+        let report = match option {
+            ImportOptions::PostmanCollection | ImportOptions::OpenApi => {
+                let root = format!("{}/{}", config::data_dir(), COLLECTION_PATH);
+                let root = std::path::Path::new(&root);
+                let (report, environment) = match option {
+                    ImportOptions::OpenApi => import::import_openapi(&content, root),
+                    _ => import::import_postman_collection(&content, root),
+                }
+                .map_err(Error::InputErr)?;
+                self.reload_collections();
+                if let Some(environment) = environment {
+                    App::save_env(vec![environment])?;
+                }
+                report
+            }
+            ImportOptions::PostmanEnvironment => {
+                let (report, environment) =
+                    import::import_postman_environment(&content).map_err(Error::InputErr)?;
+                App::save_env(vec![environment])?;
+                report
+            }
+            ImportOptions::Curl | ImportOptions::Har => {
+                return Err(Error::InputErr("not a file import".to_string()))
+            }
+        };
+        self.reload_envs()?;
+        Ok(report)
+    }
+    /// Opens HAR entries as tabs or saves them under a collection folder. A
+    /// report is returned for saved entries, and for tabs only when
+    /// something could not be mapped.
+    fn import_har(&mut self, action: HarAction) -> Result<Option<import::Report>, Error> {
+        let (folder, entries) = match action {
+            HarAction::OpenTabs(entries) => (None, entries),
+            HarAction::SaveToFolder(folder, entries) => (Some(folder), entries),
+        };
+        let mut report = import::Report::new("HAR import".to_string());
+        let folder = folder.map(|folder| folder.trim().trim_matches('/').to_string());
+        if let Some(folder) = &folder {
+            // the folder must stay inside the collections
+            let inside = std::path::Path::new(folder)
+                .components()
+                .all(|c| matches!(c, std::path::Component::Normal(_)));
+            if !inside {
+                return Err(Error::InputErr(format!("{} is not a valid folder", folder)));
+            }
+        }
+        let dir = folder.map(|folder| {
+            std::path::Path::new(&format!("{}/{}", config::data_dir(), COLLECTION_PATH))
+                .join(folder)
+        });
+        if let Some(dir) = &dir {
+            fs::create_dir_all(dir)?;
+        }
+        for entry in entries {
+            for warning in entry.warnings {
+                report.warn(warning);
+            }
+            match &dir {
+                Some(dir) => {
+                    let mut req = entry.request;
+                    let path = import::unique_path(dir, &import::file_name(&req.name()), ".rph");
+                    fs::write(&path, req.to_rph()?)?;
+                    // the recorded response is kept in the history, not in the file
+                    req.set_collection_path(path.to_string_lossy().to_string());
+                    req.push_history("HAR".to_string())?;
+                }
+                None => self.requests.push(entry.request),
+            }
+            report.imported += 1;
+        }
+        match &dir {
+            Some(dir) => {
+                report.note(format!("saved to {}", dir.to_string_lossy()));
+                self.reload_collections();
+            }
+            None if report.imported > 0 => {
+                self.current_request_idx = self.requests.len() - 1;
+                self.change_request();
+            }
+            None => (),
+        }
+        Ok((dir.is_some() || report.has_warnings()).then_some(report))
+    }
+    /// Writes the collection folder at `path` to the exports directory and
+    /// returns the lines of a report.
+    fn export_collection(&self, path: &str, option: &ExportOptions) -> Result<Vec<String>, Error> {
+        let folder = load_folder(path)?;
+        let env = self
+            .all_envs
+            .get(self.current_env_idx)
+            .map(|env| env.envs.clone())
+            .unwrap_or_default();
+        let (document, extension) = match option {
+            ExportOptions::Postman => {
+                let mut variables = env.clone().into_iter().collect::<Vec<(String, String)>>();
+                variables.sort();
+                for (_, req) in folder.flatten() {
+                    for (k, v) in req.variables() {
+                        if !variables.iter().any(|(name, _)| name == k) {
+                            variables.push((k.clone(), v.clone()));
+                        }
+                    }
+                }
+                (
+                    export::to_postman(&folder, &variables),
+                    "postman_collection.json",
+                )
+            }
+            ExportOptions::OpenApi => (
+                export::to_openapi(&folder, &|s| s.replace_env(&self.regex_replacer, &env)),
+                "openapi.json",
+            ),
+        };
+        let dir = format!("{}/{}", config::data_dir(), EXPORT_PATH);
+        fs::create_dir_all(&dir)?;
+        let file = format!("{}/{}.{}", dir, import::file_name(&folder.name), extension);
+        fs::write(&file, serde_json::to_vec_pretty(&document)?)?;
+        let mut lines = vec![
+            format!("{} requests exported", folder.flatten().len()),
+            format!("saved to {}", file),
+        ];
+        if let ExportOptions::Postman = option {
+            for env in &self.all_envs {
+                let mut variables = env
+                    .envs
+                    .clone()
+                    .into_iter()
+                    .collect::<Vec<(String, String)>>();
+                variables.sort();
+                let file = format!(
+                    "{}/{}.postman_environment.json",
+                    dir,
+                    import::file_name(&env.name)
+                );
+                let document = export::to_postman_environment(&env.name, &variables);
+                fs::write(&file, serde_json::to_vec_pretty(&document)?)?;
+                lines.push(format!("environment {} saved to {}", env.name, file));
+            }
+        }
+        Ok(lines)
+    }
     pub fn reload_envs(&mut self) -> Result<(), Error> {
         self.all_envs = Self::load_envs()?;
         Ok(())
     }
 }
 
+/// Loads the requests saved at `path`: a `.rph` file, every request of a
+/// `.http` file, or a single one addressed as `path#index`.
+pub fn load_collection_file(path: &str) -> Result<Vec<super::request::Request>, Error> {
+    let requests = match http_file::split_location(path) {
+        (file, Some(idx)) => {
+            let req = HttpFile::read(file)?
+                .request(idx)
+                .ok_or(Error::NoRequestErr(idx))?;
+            vec![(path.to_string(), req)]
+        }
+        (file, None) if http_file::is_http_file(file) => HttpFile::read(file)?
+            .requests()
+            .into_iter()
+            .map(|(idx, req)| (http_file::location(file, idx), req))
+            .collect(),
+        (file, None) => vec![(
+            file.to_string(),
+            super::request::Request::from_rph(&fs::read_to_string(file)?)
+                .map_err(|e| Error::MigrationErr(format!("{}: {}", file, e.to_string())))?,
+        )],
+    };
+    requests
+        .into_iter()
+        .map(|(location, mut req)| {
+            let history = History::load(&location)?;
+            if req.response().is_none() {
+                req.set_response(history.entries().first().map(|e| e.response.clone()));
+            }
+            req.set_history(history);
+            req.set_collection_path(location);
+            Ok(req)
+        })
+        .collect()
+}
+/// Loads a collection folder with its sub folders for export. A single file,
+/// or a single request of a `.http` file, makes a folder of its own.
+pub fn load_folder(path: &str) -> Result<Folder, Error> {
+    let (file, idx) = http_file::split_location(path);
+    let name = std::path::Path::new(file)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if idx.is_some() || !fs::metadata(file)?.is_dir() {
+        return Ok(Folder {
+            name,
+            folders: Vec::new(),
+            requests: load_collection_file(path)?,
+        });
+    }
+    let entries = collection::ordered_entries(std::path::Path::new(file))?;
+    let mut folder = Folder {
+        name,
+        folders: Vec::new(),
+        requests: Vec::new(),
+    };
+    for entry in entries {
+        let entry_path = entry.to_string_lossy().to_string();
+        if entry.is_dir() || http_file::is_http_file(&entry_path) {
+            folder.folders.push(load_folder(&entry_path)?);
+        } else if entry_path.ends_with(".rph") {
+            folder.requests.extend(load_collection_file(&entry_path)?);
+        }
+    }
+    Ok(folder)
+}
+/// Upgrades every `.rph` file under `path` to the current format version and
+/// returns the lines of a report. Files that cannot be upgraded are left as
+/// they are and listed with the reason.
+pub fn migrate_collection(path: &str) -> Result<Vec<String>, Error> {
+    let mut files = Vec::new();
+    collection_files(std::path::Path::new(path), &mut files, |p| {
+        p.extension().is_some_and(|e| e == "rph")
+    })?;
+    let mut upgraded = Vec::new();
+    let mut failed = Vec::new();
+    for file in &files {
+        let location = file.to_string_lossy().to_string();
+        match migrate_file(&location) {
+            Ok(Some(version)) => upgraded.push(format!("  - {} (version {})", location, version)),
+            Ok(None) => (),
+            Err(e) => failed.push(format!("  - {}: {}", location, e.to_string())),
+        }
+    }
+    let mut lines = vec![
+        format!(
+            "{} requests checked, {} upgraded to version {}",
+            files.len(),
+            upgraded.len(),
+            super::request::RPH_VERSION
+        ),
+        String::new(),
+    ];
+    lines.extend(upgraded);
+    if failed.is_empty() {
+        lines.push("Every request is up to date.".to_string());
+    } else {
+        lines.push(String::new());
+        lines.push(format!(
+            "{} requests could not be upgraded and were left as they are:",
+            failed.len()
+        ));
+        lines.extend(failed);
+    }
+    Ok(lines)
+}
+/// Every saved request under `path` with its location. Files that cannot be
+/// read are left out, the collection tree still lists them.
+pub fn saved_requests(path: &str) -> Result<Vec<(String, super::request::Request)>, Error> {
+    let mut files = Vec::new();
+    collection_files(std::path::Path::new(path), &mut files, |p| {
+        p.extension().is_some_and(|e| e == "rph") || http_file::is_http_file(&p.to_string_lossy())
+    })?;
+    let mut requests = Vec::new();
+    for file in files {
+        let location = file.to_string_lossy().to_string();
+        if http_file::is_http_file(&location) {
+            if let Ok(http) = HttpFile::read(&location) {
+                requests.extend(
+                    http.requests()
+                        .into_iter()
+                        .map(|(idx, req)| (http_file::location(&location, idx), req)),
+                );
+            }
+        } else if let Ok(req) = fs::read_to_string(&file)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                super::request::Request::from_rph(&content).map_err(|e| e.to_string())
+            })
+        {
+            requests.push((location, req));
+        }
+    }
+    Ok(requests)
+}
+/// Collects the files under `path` that `keep` accepts, in the saved order.
+fn collection_files(
+    path: &std::path::Path,
+    files: &mut Vec<std::path::PathBuf>,
+    keep: fn(&std::path::Path) -> bool,
+) -> Result<(), Error> {
+    if !path.is_dir() {
+        if keep(path) {
+            files.push(path.to_path_buf());
+        }
+        return Ok(());
+    }
+    for entry in collection::ordered_entries(path)? {
+        collection_files(&entry, files, keep)?;
+    }
+    Ok(())
+}
+/// Copies a folder with everything in it.
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) -> Result<(), Error> {
+    fs::create_dir(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().ok_or(Error::NoRequestErr(1))?);
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+/// Names the request of the `.rph` file at `path` after the file, so that a
+/// renamed or copied file and its request agree. Returns the new name.
+fn rename_rph(path: &std::path::Path) -> Result<Option<String>, Error> {
+    if path.extension().is_none_or(|ext| ext != "rph") {
+        return Ok(None);
+    }
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    // keeps the response of a version 1 file in its history
+    migrate_file(&path.to_string_lossy())?;
+    let mut req = super::request::Request::from_rph(&fs::read_to_string(path)?)?;
+    req.set_name(name.clone());
+    fs::write(path, req.to_rph()?)?;
+    Ok(Some(name))
+}
+/// Rewrites a single `.rph` file in the current format, returning the version
+/// it had, or `None` when it was up to date.
+fn migrate_file(path: &str) -> Result<Option<u64>, Error> {
+    let (mut req, version) = super::request::Request::migrate_rph(&fs::read_to_string(path)?)?;
+    if version == super::request::RPH_VERSION {
+        return Ok(None);
+    }
+    // a version 1 file embeds its last response, keep it in the history
+    let history = History::load(path)?;
+    if history.entries().is_empty() && req.response().is_some() {
+        req.set_collection_path(path.to_string());
+        req.push_history("-".to_string())?;
+    }
+    fs::write(path, req.to_rph()?)?;
+    Ok(Some(version))
+}
 pub fn handle_overwrite_request(req: &super::request::Request) -> Result<(), Error> {
     let path_str = req.collection_path().unwrap().clone();
-    let mut path = std::path::PathBuf::from(&path_str);
-    fs::remove_file(path.clone())?;
-    if !path.ends_with(req.name()) {
-        path = path.parent().unwrap().join(format!("{}.rph", req.name()));
+    if let (file, Some(idx)) = http_file::split_location(&path_str) {
+        let mut http_file = HttpFile::read(file)?;
+        http_file.set_request(idx, req)?;
+        return http_file.write(file);
     }
-    let mut f = fs::File::create(path)?;
-    f.write(serde_json::to_vec(req).unwrap().as_slice())?;
+    // the file keeps its place, renaming it is done from the collections
+    fs::write(&path_str, req.to_rph()?)?;
     Ok(())
 }
 pub fn update_request_collection(
@@ -684,12 +1897,21 @@ pub fn update_request_collection(
 ) -> Result<(), Error> {
     if let Some(path) = paths.last() {
         //trace_dbg!(level: tracing::Level::INFO, ("in update", &path));
+        let (file, _) = http_file::split_location(path);
+        if http_file::is_http_file(file) {
+            let mut http_file = HttpFile::read(file)?;
+            let location = http_file::location(file, http_file.push_request(req));
+            http_file.write(file)?;
+            req.history().save(&location)?;
+            req.set_collection_path(location);
+            return Ok(());
+        }
         if !fs::metadata(path.clone())?.is_dir() {
             return Err(Error::InputErr("not a directory".to_string()));
         }
         let path = format!("{}/{}.rph", path, req.name());
-        let mut f = fs::File::create(path.clone())?;
-        f.write(serde_json::to_vec(req).unwrap().as_slice())?;
+        fs::write(&path, req.to_rph()?)?;
+        req.history().save(&path)?;
         req.set_collection_path(path);
         return Ok(());
     }
@@ -1,14 +1,19 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::{
     components::{PopUpComponent, YesNoPopupComponent},
-    keys::keys::{Event, Key, Modifier as keyModifier},
+    http_file::{self, HttpFile},
+    keys::keymap::{self, Action as KeyAction},
+    keys::keys::{Event, Key, SCROLL_LINES},
     layout::centered_rect,
+    main_windows::MainWindows,
+    theme,
 };
+use ratatui::layout::Position;
 use ratatui::widgets::{Block, Clear, Scrollbar, ScrollbarOrientation};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     Frame,
 };
 
@@ -19,6 +24,51 @@ pub enum Action {
     Delete,
     Create,
     AddRequest,
+    Export,
+    /// Upgrade the saved requests to the current file format.
+    Migrate,
+    Rename(String),
+    Duplicate,
+    /// Move the entry at the given location into the selected folder.
+    Move(String),
+    MoveUp,
+    MoveDown,
+}
+
+/// Lists the names of a folder's entries in the order they are shown, one
+/// per line. Entries missing from it follow in name order.
+pub const ORDER_FILE: &str = ".order";
+
+/// The folders and request files of `dir` in their saved order.
+pub fn ordered_entries(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<PathBuf>>>()?;
+    entries.retain(|p| {
+        p.is_dir()
+            || p.extension().is_some_and(|e| e == "rph")
+            || http_file::is_http_file(&p.to_string_lossy())
+    });
+    entries.sort();
+    let order = fs::read_to_string(dir.join(ORDER_FILE)).unwrap_or_default();
+    // the sort is stable, so unlisted entries keep their name order
+    entries.sort_by_key(|p| {
+        order
+            .lines()
+            .position(|name| p.file_name().is_some_and(|n| n == name))
+            .unwrap_or(usize::MAX)
+    });
+    Ok(entries)
+}
+
+/// Saves `entries` as the order of `dir`.
+pub fn save_order(dir: &Path, entries: &[PathBuf]) -> std::io::Result<()> {
+    let mut order = String::new();
+    for name in entries.iter().filter_map(|p| p.file_name()) {
+        order.push_str(&name.to_string_lossy());
+        order.push('\n');
+    }
+    fs::write(dir.join(ORDER_FILE), order)
 }
 
 #[derive(Clone, Debug)]
@@ -42,6 +92,9 @@ pub struct Collection<'a> {
     caller: Option<String>,
     delete_pop_up: Option<YesNoPopupComponent<'a>>,
     create_pop_up: Option<PopUpComponent>,
+    rename_pop_up: Option<PopUpComponent>,
+    /// The location marked by cut, moved on paste.
+    cut: Option<String>,
 }
 
 impl<'a> Collection<'a> {
@@ -53,19 +106,46 @@ impl<'a> Collection<'a> {
             caller: None,
             delete_pop_up: None,
             create_pop_up: None,
+            rename_pop_up: None,
+            cut: None,
+        }
+    }
+    /// Reads the tree again after a change on disk, keeping the unfolded
+    /// folders, and selects `location`.
+    pub fn reload(&mut self, location: &str) {
+        let root = self.items.identifier().clone();
+        if let Some(items) = Self::create_tree(Node::new(root.clone()), 0) {
+            self.items = items;
+        }
+        // every folder on the way down is an identifier of its own
+        let (file, idx) = http_file::split_location(location);
+        let mut identifiers: Vec<String> = Path::new(file)
+            .ancestors()
+            .map(|p| p.to_string_lossy().to_string())
+            .take_while(|p| p.starts_with(&root))
+            .collect();
+        identifiers.reverse();
+        if idx.is_some() {
+            identifiers.push(location.to_string());
         }
+        for depth in 1..identifiers.len() {
+            self.state.open(identifiers[..depth].to_vec());
+        }
+        self.state.select(identifiers);
     }
     fn create_tree(node: Node, mut depth: usize) -> Option<TreeItem<'a, String>> {
         let mut result = TreeItem::new_leaf(node.file_path.clone(), node.file_name.clone());
         if depth > 10 || !fs::metadata(node.file_path.clone()).unwrap().is_dir() {
+            if http_file::is_http_file(&node.file_name) {
+                return Some(Self::http_file_tree(node));
+            }
             if !node.file_name.ends_with(".rph") {
                 return None;
             };
             return Some(result);
         }
-        for entry in fs::read_dir(node.file_path.clone()).unwrap() {
-            let ent = entry.unwrap();
-            let f_path = ent.path().to_string_lossy().to_string();
+        for entry in ordered_entries(Path::new(&node.file_path)).unwrap() {
+            let f_path = entry.to_string_lossy().to_string();
             let new_path = Node::new(f_path);
             depth += 1;
             if let Some(r) = Self::create_tree(new_path, depth) {
@@ -74,12 +154,33 @@ impl<'a> Collection<'a> {
         }
         Some(result)
     }
+    /// A `.http` file is shown as a folder of its requests. A file that cannot
+    /// be read is still listed so it can be deleted.
+    fn http_file_tree(node: Node) -> TreeItem<'a, String> {
+        let children = HttpFile::read(&node.file_path)
+            .map(|file| file.requests())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(idx, req)| {
+                TreeItem::new_leaf(http_file::location(&node.file_path, idx), req.name())
+            })
+            .collect();
+        TreeItem::new(node.file_path.clone(), node.file_name.clone(), children)
+            .unwrap_or_else(|_| TreeItem::new_leaf(node.file_path, node.file_name))
+    }
     pub fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
         let all_items = &[self.items.clone()];
+        let mut block = Block::bordered().title_bottom(format!("{:?}", self.state));
+        if let Some(cut) = &self.cut {
+            block = block.title(format!(
+                "Cut {} (paste: move into the selected folder)",
+                cut.rsplit('/').next().unwrap_or(cut)
+            ));
+        }
         let widget = Tree::new(all_items)
             .expect("all item identifiers are unique")
-            .block(Block::bordered().title_bottom(format!("{:?}", self.state)))
+            .block(block)
             .experimental_scrollbar(Some(
                 Scrollbar::new(ScrollbarOrientation::VerticalRight)
                     .begin_symbol(None)
@@ -88,8 +189,8 @@ impl<'a> Collection<'a> {
             ))
             .highlight_style(
                 Style::new()
-                    .fg(Color::Black)
-                    .bg(Color::LightGreen)
+                    .fg(theme::get().highlight_fg)
+                    .bg(theme::get().highlight_bg)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol(">> ");
@@ -102,6 +203,40 @@ impl<'a> Collection<'a> {
             let rect = centered_rect(40, 20, frame.area());
             popup.draw(frame, rect);
         };
+        if let Some(popup) = &self.rename_pop_up {
+            let rect = centered_rect(40, 20, frame.area());
+            popup.draw(frame, rect);
+        };
+    }
+    /// Selects the clicked entry and folds or unfolds it. Clicking the
+    /// selected request again opens it, like enter.
+    pub fn click(&mut self, position: Position) -> Option<(Option<String>, Action, Vec<String>)> {
+        if self.delete_pop_up.is_some()
+            || self.create_pop_up.is_some()
+            || self.rename_pop_up.is_some()
+        {
+            return None;
+        }
+        let clicked = self.state.rendered_at(position)?.to_vec();
+        let is_request = clicked.last().is_some_and(|path| {
+            let (file, idx) = http_file::split_location(path);
+            idx.is_some() || file.ends_with(".rph")
+        });
+        if is_request && clicked == self.state.selected() {
+            return Some((self.caller.clone(), Action::AddRequest, clicked));
+        }
+        self.state.select(clicked.clone());
+        if !is_request {
+            self.state.toggle(clicked);
+        }
+        None
+    }
+    pub fn scroll(&mut self, down: bool) {
+        if down {
+            self.state.scroll_down(SCROLL_LINES);
+        } else {
+            self.state.scroll_up(SCROLL_LINES);
+        }
     }
     pub fn get_selected(&self) -> Vec<String> {
         self.state
@@ -132,8 +267,9 @@ impl<'a> Collection<'a> {
             }
             if let Some(filename) = result {
                 self.create_pop_up = None;
-                let path = self.get_selected();
-                let path = Path::new(path.last().unwrap());
+                let selected = self.get_selected();
+                let (path, _) = http_file::split_location(selected.last().unwrap());
+                let path = Path::new(path);
                 let new_path = if fs::metadata(path).unwrap().is_dir() {
                     path.join(filename)
                 } else {
@@ -150,22 +286,58 @@ impl<'a> Collection<'a> {
                 return None;
             }
         }
-        if let Some(modifier) = &event.modifier {
-            match modifier {
-                keyModifier::Control => match event.key {
-                    Key::Char('d') => {
-                        self.delete_pop_up = Some(YesNoPopupComponent::new("Delete?"));
-                    }
-                    Key::Char('n') => {
-                        self.create_pop_up = Some(PopUpComponent::new(
-                            String::from("new collection"),
-                            String::from("Input the name of the new collection"),
-                        ));
-                    }
-                    _ => (),
-                },
-                _ => (),
+        if let Some(popup) = &mut self.rename_pop_up {
+            let (result, to_continue) = popup.update(event);
+            if to_continue {
+                return None;
             }
+            self.rename_pop_up = None;
+            return match result.map(|name| name.trim().to_string()) {
+                Some(name) if !name.is_empty() => {
+                    Some((None, Action::Rename(name), self.get_selected()))
+                }
+                _ => None,
+            };
+        }
+        match keymap::get().action(&MainWindows::Collections, event) {
+            Some(KeyAction::Delete) => {
+                self.delete_pop_up = Some(YesNoPopupComponent::new("Delete?"));
+            }
+            Some(KeyAction::Create) => {
+                self.create_pop_up = Some(PopUpComponent::new(
+                    String::from("new collection"),
+                    String::from("Input the name of the new collection"),
+                ));
+            }
+            Some(KeyAction::ExportCollection) => {
+                return Some((None, Action::Export, self.get_selected()));
+            }
+            Some(KeyAction::MigrateCollection) => {
+                return Some((None, Action::Migrate, self.get_selected()));
+            }
+            // the root folder keeps its name and place
+            Some(KeyAction::Rename) if self.state.selected().len() > 1 => {
+                self.rename_pop_up = Some(PopUpComponent::new(
+                    String::from("Rename"),
+                    String::from("Input the new name"),
+                ));
+            }
+            Some(KeyAction::Duplicate) => {
+                return Some((None, Action::Duplicate, self.get_selected()));
+            }
+            Some(KeyAction::Cut) if self.state.selected().len() > 1 => {
+                self.cut = self.get_selected().pop();
+            }
+            Some(KeyAction::Paste) if self.cut.is_some() => {
+                return Some((None, Action::Move(self.cut.take()?), self.get_selected()));
+            }
+            Some(KeyAction::MoveUp) => {
+                return Some((None, Action::MoveUp, self.get_selected()));
+            }
+            Some(KeyAction::MoveDown) => {
+                return Some((None, Action::MoveDown, self.get_selected()));
+            }
+            _ => (),
         }
         match event.key {
             Key::Enter => {
@@ -196,3 +368,48 @@ impl<'a> Collection<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(entries: &[PathBuf]) -> Vec<String> {
+        entries
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn entries_follow_the_saved_order() {
+        let root = std::env::temp_dir().join(format!("restopher-order-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("users")).unwrap();
+        for file in ["b.rph", "a.rph", "api.http", "notes.txt", "c.rest"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        // name order without an order file, other files are left out
+        assert_eq!(
+            names(&ordered_entries(&root).unwrap()),
+            vec!["a.rph", "api.http", "b.rph", "c.rest", "users"]
+        );
+
+        // stale names are ignored, missing ones follow in name order
+        fs::write(root.join(ORDER_FILE), "users\ngone.rph\nb.rph\n").unwrap();
+        let entries = ordered_entries(&root).unwrap();
+        assert_eq!(
+            names(&entries),
+            vec!["users", "b.rph", "a.rph", "api.http", "c.rest"]
+        );
+
+        let mut reversed = entries.clone();
+        reversed.reverse();
+        save_order(&root, &reversed).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join(ORDER_FILE)).unwrap(),
+            "c.rest\napi.http\na.rph\nb.rph\nusers\n"
+        );
+        assert_eq!(ordered_entries(&root).unwrap(), reversed);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
     request::Request,
 };
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Position, Rect},
     widgets::{Paragraph, Wrap},
     Frame,
 };
@@ -73,11 +73,30 @@ impl AddressBarComponent {
             }
         }
     }
+    /// Focuses the verb or the address, placing the cursor where the address
+    /// was clicked.
+    pub fn click(&mut self, rect: Rect, position: Position) {
+        let chunks = layout(rect);
+        if chunks[0].contains(position) {
+            self.focus = Focus::Verb;
+        } else if chunks[1].contains(position) {
+            self.focus = Focus::Address;
+            self.address_bar_view.click(chunks[1], position);
+        }
+    }
+    /// Changes the verb when the wheel turns over it.
+    pub fn scroll(&mut self, req: &mut Request, rect: Rect, position: Position, down: bool) {
+        if !layout(rect)[0].contains(position) {
+            return;
+        }
+        if down {
+            req.verb_down();
+        } else {
+            req.verb_up();
+        }
+    }
     pub fn draw(&self, f: &mut Frame, req: &Request, rect: Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
-            .split(rect);
+        let chunks = layout(rect);
         f.render_widget(
             Paragraph::new(req.verb().to_string())
                 .block(default_block(
@@ -96,3 +115,11 @@ impl AddressBarComponent {
         );
     }
 }
+
+/// The verb and the address.
+fn layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(20), Constraint::Percentage(80)])
+        .split(rect)
+}
@@ -1,22 +1,24 @@
 use std::borrow::Cow;
 
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::Span;
 use ratatui::widgets::{Block, Borders, Tabs};
 
+use crate::theme;
 pub fn default_block(name: Option<&str>, is_focused: bool) -> Block {
     let b = Block::default()
         .borders(Borders::ALL)
         .border_style({
             if is_focused {
-                Style::default().fg(Color::Red)
+                Style::default().fg(theme::get().focus)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(theme::get().border)
             }
         })
         .border_type(ratatui::widgets::BorderType::Rounded);
     if let Some(name) = name {
-        b.title(Span::styled(name, Style::default().fg(Color::White)))
+        b.title(Span::styled(name, Style::default().fg(theme::get().title)))
     } else {
         b
     }
@@ -41,10 +43,68 @@ pub fn tabs<'a>(
     )
     .block(default_block(name, is_focused))
     .select(selected)
-    .style(Style::default().fg(Color::White))
+    .style(Style::default().fg(theme::get().text))
     .highlight_style(
         Style::default()
             .add_modifier(Modifier::BOLD)
-            .fg(Color::Yellow),
+            .fg(theme::get().selected),
     )
 }
+
+/// The index of the tab at `position`, for tabs drawn by `tabs` in `rect`.
+/// A tab covers its title, the padding around it and the divider after it.
+pub fn tab_at(titles: &[String], rect: Rect, position: Position) -> Option<usize> {
+    if !rect.contains(position) {
+        return None;
+    }
+    // the tabs start inside the left border
+    let mut x = rect.x + 1;
+    for (idx, title) in titles.iter().enumerate() {
+        let width = match title.as_str() {
+            "" => "Untitled".len(),
+            title => Span::from(title).width(),
+        };
+        let end = x.saturating_add(width as u16 + 2);
+        if position.x >= x && position.x <= end {
+            return Some(idx);
+        }
+        x = end.saturating_add(1);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::buffer::Buffer;
+    use ratatui::widgets::Widget;
+
+    use super::*;
+
+    #[test]
+    fn finds_the_clicked_tab() {
+        theme::init("/nonexistent", "dark").unwrap();
+        let titles = vec!["Body".to_string(), String::new(), "Headers".to_string()];
+        let rect = Rect::new(2, 1, 40, 3);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 50, 5));
+        tabs(
+            titles.iter().map(|t| Span::from(t.as_str())).collect(),
+            None,
+            0,
+            false,
+        )
+        .render(rect, &mut buffer);
+        let line = (0..50)
+            .map(|x| buffer[(x, 2)].symbol().to_string())
+            .collect::<String>();
+        // every letter of a drawn title belongs to its tab
+        for (idx, title) in ["Body", "Untitled", "Headers"].iter().enumerate() {
+            // the borders are wider than a byte
+            let start = line[..line.find(title).unwrap()].chars().count() as u16;
+            for x in start..start + title.len() as u16 {
+                assert_eq!(tab_at(&titles, rect, Position::new(x, 2)), Some(idx));
+            }
+        }
+        assert_eq!(tab_at(&titles, rect, Position::new(41, 2)), None);
+        assert_eq!(tab_at(&titles, rect, Position::new(3, 0)), None);
+    }
+}
@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Cell, Clear, Row},
+    Frame,
+};
+
+use crate::{
+    components::fuzzy_picker::FuzzyPicker,
+    keys::keymap::{self, Action},
+    keys::keys::Event,
+    layout::centered_rect,
+    main_windows::MainWindows,
+    theme,
+};
+
+/// Lists the actions of a window with their keys, filtered by fuzzy search
+/// on the description and the config name.
+pub struct CommandPaletteComponent {
+    picker: FuzzyPicker<(Action, String)>,
+}
+
+impl CommandPaletteComponent {
+    pub fn new(window: &MainWindows) -> Self {
+        let entries = Action::ALL
+            .into_iter()
+            .filter(|a| a.available_in(window) && *a != Action::CommandPalette)
+            .map(|a| (a, keymap::get().keys(window, a).join(", ")))
+            .collect();
+        CommandPaletteComponent {
+            picker: FuzzyPicker::new(entries, |(a, _): &(Action, String)| {
+                format!("{} {}", a.description(), a.name())
+            }),
+        }
+    }
+    /// Returns the action to run once chosen, and false when the palette
+    /// should close.
+    pub fn update(&mut self, event: &Event) -> (Option<Action>, bool) {
+        let (entry, keep_open) = self.picker.update(event);
+        (entry.map(|(action, _)| *action), keep_open)
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(60, 60, area);
+        f.render_widget(Clear, rect);
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(rect);
+
+        self.picker
+            .draw_query(f, "Command (enter: run, esc: close)", chunks[0]);
+        self.picker.draw_list(
+            f,
+            None,
+            vec![Constraint::Fill(1), Constraint::Length(24)],
+            |(action, keys)| {
+                Row::new(vec![
+                    Cell::from(action.description()),
+                    Cell::from(Span::styled(
+                        keys.clone(),
+                        Style::default().fg(theme::get().muted),
+                    )),
+                ])
+            },
+            chunks[1],
+        );
+    }
+}
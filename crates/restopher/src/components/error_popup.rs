@@ -1,22 +1,22 @@
 use ratatui::{
     layout::Rect,
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use crate::layout;
+use crate::{layout, theme};
 
 pub fn error_popup(f: &mut Frame, e: &crate::app::Error, r: Rect) {
     let block = Block::default()
         .title(e.to_string())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Red));
+        .border_style(Style::default().fg(theme::get().popup.error));
     let area = layout::centered_rect(60, 20, r);
     let msg = Paragraph::new(format!("{:?}", e))
         .wrap(Wrap { trim: true })
         .block(block)
-        .style(Style::default().fg(Color::Red));
+        .style(Style::default().fg(theme::get().popup.error));
     f.render_widget(Clear, area);
     f.render_widget(msg, area);
 }
@@ -0,0 +1,153 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    components::{default_block, tabs, text_box::TextBox},
+    keys::keys::{Event, Key, Modifier},
+    layout::centered_rect,
+};
+
+pub enum ExportTarget {
+    Clipboard(String),
+    /// Path and content.
+    File(String, String),
+}
+
+/// One rendering of the exported request.
+pub struct Snippet {
+    pub language: String,
+    pub extension: String,
+    pub unresolved: String,
+    pub resolved: String,
+}
+
+/// Shows an exported request in each of the given languages and lets the user
+/// copy one or write it to a file, with variables either resolved or kept as
+/// `{{var}}`.
+pub struct ExportPopupComponent {
+    title: String,
+    snippets: Vec<Snippet>,
+    selected: usize,
+    resolve_variables: bool,
+    /// Default file path without the extension of the language.
+    default_path: String,
+    path_input: Option<TextBox>,
+}
+
+impl ExportPopupComponent {
+    pub fn new(title: String, snippets: Vec<Snippet>, default_path: String) -> Self {
+        ExportPopupComponent {
+            title,
+            snippets,
+            selected: 0,
+            resolve_variables: true,
+            default_path,
+            path_input: None,
+        }
+    }
+    fn content(&self) -> String {
+        match self.snippets.get(self.selected) {
+            Some(snippet) if self.resolve_variables => snippet.resolved.clone(),
+            Some(snippet) => snippet.unresolved.clone(),
+            None => String::new(),
+        }
+    }
+    fn default_path(&self) -> String {
+        match self.snippets.get(self.selected) {
+            Some(snippet) => format!("{}.{}", self.default_path, snippet.extension),
+            None => self.default_path.clone(),
+        }
+    }
+    /// Returns the chosen target once the user confirms, and false when the
+    /// popup should close.
+    pub fn update(&mut self, event: &Event) -> (Option<ExportTarget>, bool) {
+        if let Some(input) = &mut self.path_input {
+            match event.key {
+                Key::Enter => {
+                    return (
+                        Some(ExportTarget::File(input.get_content(), self.content())),
+                        false,
+                    )
+                }
+                Key::Esc => self.path_input = None,
+                Key::Space => input.push(' '),
+                _ => input.update(event),
+            }
+            return (None, true);
+        }
+        match (&event.modifier, &event.key) {
+            (_, Key::Esc) => (None, false),
+            (_, Key::Enter) => (Some(ExportTarget::Clipboard(self.content())), false),
+            (_, Key::Tab) => {
+                self.resolve_variables = !self.resolve_variables;
+                (None, true)
+            }
+            (None, Key::Right) if !self.snippets.is_empty() => {
+                self.selected = (self.selected + 1) % self.snippets.len();
+                (None, true)
+            }
+            (None, Key::Left) if !self.snippets.is_empty() => {
+                self.selected = (self.selected + self.snippets.len() - 1) % self.snippets.len();
+                (None, true)
+            }
+            (Some(Modifier::Control), Key::Char('s')) => {
+                self.path_input = Some(TextBox::from(self.default_path()));
+                (None, true)
+            }
+            _ => (None, true),
+        }
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(80, 60, area);
+        f.render_widget(Clear, rect);
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+        ])
+        .split(rect);
+        f.render_widget(
+            tabs(
+                self.snippets
+                    .iter()
+                    .map(|s| Span::from(s.language.as_str()))
+                    .collect(),
+                Some("Language (left/right: switch)"),
+                self.selected,
+                self.path_input.is_none(),
+            ),
+            chunks[0],
+        );
+        let title = format!(
+            "{} [variables: {}] (tab: toggle, enter: copy, ctrl-s: save to file, esc: close)",
+            self.title,
+            if self.resolve_variables {
+                "resolved"
+            } else {
+                "{{var}}"
+            }
+        );
+        f.render_widget(
+            Paragraph::new(self.content())
+                .wrap(Wrap { trim: false })
+                .block(default_block(Some(&title), self.path_input.is_none())),
+            chunks[1],
+        );
+        let mut spans = Vec::new();
+        match &self.path_input {
+            Some(input) => input.get_content_styled(&mut spans, true),
+            None => spans.push(Span::from(self.default_path())),
+        }
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(
+                Some("File (enter: write, esc: back)"),
+                self.path_input.is_some(),
+            )),
+            chunks[2],
+        );
+    }
+}
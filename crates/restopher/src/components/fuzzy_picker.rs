@@ -0,0 +1,117 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    components::{default_block, text_box::TextBox},
+    fuzzy,
+    keys::keys::{Event, Key, Modifier},
+    theme,
+};
+
+/// A query box over a list of items, filtered and ranked by fuzzy search on
+/// the text of each item. Shared by the popups that pick one thing by name.
+pub struct FuzzyPicker<T> {
+    items: Vec<T>,
+    /// What the query is matched against, one per item.
+    texts: Vec<String>,
+    query: TextBox,
+    selected: usize,
+}
+
+impl<T> FuzzyPicker<T> {
+    pub fn new(items: Vec<T>, text: impl Fn(&T) -> String) -> Self {
+        let texts = items.iter().map(text).collect();
+        FuzzyPicker {
+            items,
+            texts,
+            query: TextBox::new(),
+            selected: 0,
+        }
+    }
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+    /// Indices of the items matching the query, best match first.
+    pub fn visible(&self) -> Vec<usize> {
+        fuzzy::rank(
+            &self.query.get_content(),
+            self.texts.iter().map(|t| t.as_str()),
+        )
+    }
+    /// The highlighted item, if any matches.
+    pub fn selected(&self) -> Option<&T> {
+        self.visible()
+            .get(self.selected)
+            .map(|idx| &self.items[*idx])
+    }
+    /// Returns the item chosen with enter, and false when the picker should
+    /// close.
+    pub fn update(&mut self, event: &Event) -> (Option<&T>, bool) {
+        let visible = self.visible();
+        match (&event.modifier, &event.key) {
+            (_, Key::Esc) => return (None, false),
+            (_, Key::Enter) => {
+                if let Some(idx) = visible.get(self.selected) {
+                    return (Some(&self.items[*idx]), false);
+                }
+            }
+            (_, Key::Down) if !visible.is_empty() => {
+                self.selected = (self.selected + 1) % visible.len();
+            }
+            (_, Key::Up) if !visible.is_empty() => {
+                self.selected = (self.selected + visible.len() - 1) % visible.len();
+            }
+            (_, Key::Space) => {
+                self.query.push(' ');
+                self.selected = 0;
+            }
+            (None | Some(Modifier::Shift), Key::Char(_) | Key::Left | Key::Right)
+            | (_, Key::Backspace) => {
+                self.query.update(event);
+                self.selected = 0;
+            }
+            _ => (),
+        }
+        (None, true)
+    }
+    pub fn draw_query(&self, f: &mut Frame, title: &str, rect: Rect) {
+        let mut spans = Vec::new();
+        self.query.get_content_styled(&mut spans, true);
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(Some(title), true)),
+            rect,
+        );
+    }
+    /// Draws the matching items, best first, with `row` rendering each one.
+    pub fn draw_list<'a>(
+        &'a self,
+        f: &mut Frame,
+        title: Option<&'a str>,
+        widths: Vec<Constraint>,
+        row: impl Fn(&'a T) -> Row<'a>,
+        rect: Rect,
+    ) {
+        let rows = self
+            .visible()
+            .into_iter()
+            .map(|idx| row(&self.items[idx]))
+            .collect::<Vec<Row>>();
+        let table = Table::new(rows, widths)
+            .block(default_block(title, false))
+            .row_highlight_style(
+                Style::default()
+                    .fg(theme::get().row_fg)
+                    .bg(theme::get().row_bg),
+            );
+        f.render_stateful_widget(
+            table,
+            rect,
+            &mut TableState::new().with_selected(self.selected),
+        );
+    }
+}
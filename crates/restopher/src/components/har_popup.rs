@@ -0,0 +1,228 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    components::{default_block, text_box::TextBox},
+    import::HarEntry,
+    keys::keys::{Event, Key, Modifier},
+    layout::centered_rect,
+    styles::status_color,
+    theme,
+};
+
+pub enum HarAction {
+    /// Open the chosen entries as request tabs.
+    OpenTabs(Vec<HarEntry>),
+    /// Save the chosen entries under the given collection folder.
+    SaveToFolder(String, Vec<HarEntry>),
+}
+
+/// Lists the entries of a HAR file. Typing filters on method, URL and status;
+/// entries marked with space, or the selected one, are imported.
+pub struct HarImportComponent {
+    entries: Vec<HarEntry>,
+    filter: TextBox,
+    selected: usize,
+    marked: Vec<usize>,
+    folder_input: Option<TextBox>,
+    default_folder: String,
+}
+
+impl HarImportComponent {
+    pub fn new(entries: Vec<HarEntry>, default_folder: String) -> Self {
+        HarImportComponent {
+            entries,
+            filter: TextBox::new(),
+            selected: 0,
+            marked: Vec::new(),
+            folder_input: None,
+            default_folder,
+        }
+    }
+    /// Indices of the entries matching the filter.
+    fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.get_content().to_lowercase();
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                filter.is_empty()
+                    || e.url.to_lowercase().contains(&filter)
+                    || e.method.to_lowercase() == filter
+                    || e.status.to_string().starts_with(&filter)
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+    /// The marked entries, or the selected one when nothing is marked.
+    fn take_chosen(&mut self) -> Vec<HarEntry> {
+        let mut chosen = if self.marked.is_empty() {
+            self.visible()
+                .get(self.selected)
+                .copied()
+                .into_iter()
+                .collect()
+        } else {
+            self.marked.clone()
+        };
+        chosen.sort_unstable();
+        let entries = std::mem::take(&mut self.entries);
+        entries
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| chosen.contains(idx))
+            .map(|(_, e)| e)
+            .collect()
+    }
+    /// Returns what to import once the user confirms, and false when the
+    /// popup should close.
+    pub fn update(&mut self, event: &Event) -> (Option<HarAction>, bool) {
+        if let Some(input) = &mut self.folder_input {
+            match event.key {
+                Key::Enter => {
+                    let folder = input.get_content();
+                    return (
+                        Some(HarAction::SaveToFolder(folder, self.take_chosen())),
+                        false,
+                    );
+                }
+                Key::Esc => self.folder_input = None,
+                Key::Space => input.push(' '),
+                _ => input.update(event),
+            }
+            return (None, true);
+        }
+        let visible = self.visible();
+        match (&event.modifier, &event.key) {
+            (_, Key::Esc) => return (None, false),
+            (_, Key::Enter) if !visible.is_empty() => {
+                return (Some(HarAction::OpenTabs(self.take_chosen())), false)
+            }
+            (Some(Modifier::Control), Key::Char('s')) if !visible.is_empty() => {
+                self.folder_input = Some(TextBox::from(self.default_folder.clone()));
+            }
+            (Some(Modifier::Control), Key::Char('a')) => {
+                if visible.iter().all(|idx| self.marked.contains(idx)) {
+                    self.marked.retain(|idx| !visible.contains(idx));
+                } else {
+                    for idx in visible {
+                        if !self.marked.contains(&idx) {
+                            self.marked.push(idx);
+                        }
+                    }
+                }
+            }
+            (_, Key::Down) if !visible.is_empty() => {
+                self.selected = (self.selected + 1) % visible.len();
+            }
+            (_, Key::Up) if !visible.is_empty() => {
+                self.selected = (self.selected + visible.len() - 1) % visible.len();
+            }
+            (_, Key::Space) => {
+                if let Some(idx) = visible.get(self.selected) {
+                    match self.marked.iter().position(|m| m == idx) {
+                        Some(pos) => {
+                            self.marked.remove(pos);
+                        }
+                        None => self.marked.push(*idx),
+                    }
+                }
+            }
+            (None | Some(Modifier::Shift), Key::Char(_)) | (_, Key::Backspace) => {
+                self.filter.update(event);
+                self.selected = 0;
+            }
+            _ => (),
+        }
+        (None, true)
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(90, 80, area);
+        f.render_widget(Clear, rect);
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+        ])
+        .split(rect);
+
+        let mut spans = Vec::new();
+        self.filter
+            .get_content_styled(&mut spans, self.folder_input.is_none());
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(
+                Some("Filter (method, URL or status)"),
+                self.folder_input.is_none(),
+            )),
+            chunks[0],
+        );
+
+        let visible = self.visible();
+        let rows = visible
+            .iter()
+            .map(|idx| {
+                let entry = &self.entries[*idx];
+                Row::new(vec![
+                    Cell::from(if self.marked.contains(idx) { "*" } else { " " }),
+                    Cell::from(entry.method.clone()),
+                    Cell::from(entry.url.clone()),
+                    Cell::from(Span::styled(
+                        entry.status.to_string(),
+                        Style::default().fg(status_color(entry.status)),
+                    )),
+                    Cell::from(format!("{} B", entry.size)),
+                    Cell::from(format!("{:.0} ms", entry.time)),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        let title = format!(
+            "HAR {}/{} entries, {} marked (space: mark, ctrl-a: mark all, enter: open as tabs, ctrl-s: save to collection, esc: close)",
+            visible.len(),
+            self.entries.len(),
+            self.marked.len()
+        );
+        let table = Table::new(
+            rows,
+            vec![
+                Constraint::Length(1),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(9),
+            ],
+        )
+        .header(Row::new(vec![
+            "", "Method", "URL", "Status", "Size", "Time",
+        ]))
+        .block(default_block(Some(&title), self.folder_input.is_none()))
+        .row_highlight_style(
+            Style::default()
+                .fg(theme::get().row_fg)
+                .bg(theme::get().row_bg),
+        );
+        f.render_stateful_widget(
+            table,
+            chunks[1],
+            &mut TableState::new().with_selected(self.selected),
+        );
+
+        let mut spans = Vec::new();
+        match &self.folder_input {
+            Some(input) => input.get_content_styled(&mut spans, true),
+            None => spans.push(Span::from(self.default_folder.clone())),
+        }
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(
+                Some("Collection folder (enter: save, esc: back)"),
+                self.folder_input.is_some(),
+            )),
+            chunks[2],
+        );
+    }
+}
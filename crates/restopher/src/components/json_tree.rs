@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use copypasta::{ClipboardContext, ClipboardProvider};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Scrollbar, ScrollbarOrientation},
+    Frame,
+};
+use serde_json::Value;
+use tui_tree_widget::{Tree, TreeItem, TreeState};
+
+use crate::components::default_block;
+use crate::keys::keys::{Event, Key, SCROLL_LINES};
+use crate::theme;
+
+const ROOT: &str = "$";
+
+/// Collapsible view over a JSON document. Node identifiers are JSONPath
+/// segments, so the selected path joined together is the node's JSONPath.
+pub struct JsonTree {
+    source: String,
+    value: Result<Value, String>,
+    state: TreeState<String>,
+    message: Option<String>,
+}
+
+impl JsonTree {
+    pub fn new() -> Self {
+        Self {
+            source: String::new(),
+            value: Ok(Value::Null),
+            state: TreeState::default(),
+            message: None,
+        }
+    }
+    /// Parses `source` when it differs from the document currently shown.
+    pub fn set_source(&mut self, source: &str) {
+        if self.source == source {
+            return;
+        }
+        self.source = source.to_string();
+        self.value = serde_json::from_str(source).map_err(|e| e.to_string());
+        self.state = TreeState::default();
+        self.state.open(vec![ROOT.to_string()]);
+        self.state.select(vec![ROOT.to_string()]);
+        self.message = None;
+    }
+    pub fn update(&mut self, event: &Event) {
+        let Ok(root) = &self.value else {
+            return;
+        };
+        match event.key {
+            Key::Up => {
+                self.state.key_up();
+            }
+            Key::Down => {
+                self.state.key_down();
+            }
+            Key::Left => {
+                self.state.key_left();
+            }
+            Key::Right => {
+                self.state.key_right();
+            }
+            Key::Enter | Key::Space => {
+                self.state.toggle_selected();
+            }
+            Key::Char('p') => {
+                let mut parent = self.state.selected().to_vec();
+                if parent.len() > 1 {
+                    parent.pop();
+                    self.state.select(parent);
+                }
+            }
+            Key::Char('[') => self.select_sibling(false),
+            Key::Char(']') => self.select_sibling(true),
+            Key::Char('y') => {
+                let path = self.state.selected().concat();
+                self.message = Some(copy_to_clipboard(&path, "path"));
+            }
+            Key::Char('c') => {
+                if let Some(value) = find(root, self.state.selected()) {
+                    let text = serde_json::to_string_pretty(value).unwrap_or_default();
+                    self.message = Some(copy_to_clipboard(&text, "value"));
+                }
+            }
+            _ => (),
+        }
+    }
+    fn select_sibling(&mut self, forward: bool) {
+        let Ok(root) = &self.value else {
+            return;
+        };
+        let selected = self.state.selected().to_vec();
+        let Some((current, parent_path)) = selected.split_last() else {
+            return;
+        };
+        let Some(parent) = find(root, parent_path) else {
+            return;
+        };
+        let siblings = children(parent)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<String>>();
+        if let Some(idx) = siblings.iter().position(|s| s == current) {
+            let next = if forward {
+                (idx + 1).min(siblings.len() - 1)
+            } else {
+                idx.saturating_sub(1)
+            };
+            let mut path = parent_path.to_vec();
+            path.push(siblings[next].clone());
+            self.state.select(path);
+        }
+    }
+    /// Selects the node at `position`, or folds it when it is selected.
+    pub fn click(&mut self, position: Position) {
+        self.message = None;
+        self.state.click_at(position);
+    }
+    pub fn scroll(&mut self, down: bool) {
+        if down {
+            self.state.scroll_down(SCROLL_LINES);
+        } else {
+            self.state.scroll_up(SCROLL_LINES);
+        }
+    }
+    pub fn draw(&mut self, f: &mut Frame, rect: Rect, title: &str, is_focused: bool) {
+        let root = match &self.value {
+            Ok(root) => root,
+            Err(e) => {
+                f.render_widget(
+                    Paragraph::new(format!("Body is not valid JSON: {}", e))
+                        .style(Style::default().fg(theme::get().error))
+                        .block(default_block(Some(title), is_focused)),
+                    rect,
+                );
+                return;
+            }
+        };
+        let items = vec![build_item(
+            vec![ROOT.to_string()],
+            ROOT.to_string(),
+            root,
+            self.state.opened(),
+        )];
+        let title = match &self.message {
+            Some(m) => format!("{} - {}", title, m),
+            None => format!(
+                "{} (y: copy path, c: copy value, p: parent, [ ]: siblings)",
+                title
+            ),
+        };
+        let widget = Tree::new(&items)
+            .expect("json paths are unique")
+            .block(default_block(Some(&title), is_focused))
+            .experimental_scrollbar(Some(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .track_symbol(None)
+                    .end_symbol(None),
+            ))
+            .highlight_style(
+                Style::new()
+                    .fg(theme::get().highlight_fg)
+                    .bg(theme::get().highlight_bg)
+                    .add_modifier(Modifier::BOLD),
+            );
+        f.render_stateful_widget(widget, rect, &mut self.state);
+    }
+}
+
+fn copy_to_clipboard(text: &str, what: &str) -> String {
+    match ClipboardContext::new().and_then(|mut ctx| ctx.set_contents(text.to_string())) {
+        Ok(_) => format!("copied {}", what),
+        Err(e) => format!("could not copy {}: {}", what, e),
+    }
+}
+
+/// Children of a container as (JSONPath segment, value) pairs.
+fn children(value: &Value) -> Vec<(String, &Value)> {
+    match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (key_segment(k), v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("[{}]", i), v))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn key_segment(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        format!(".{}", key)
+    } else {
+        format!("['{}']", key.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+fn find<'v>(root: &'v Value, path: &[String]) -> Option<&'v Value> {
+    let mut current = root;
+    for segment in path.iter().skip(1) {
+        current = children(current)
+            .into_iter()
+            .find(|(id, _)| id == segment)
+            .map(|(_, v)| v)?;
+    }
+    Some(current)
+}
+
+/// Builds the tree item for `value`. Children are only built for opened nodes,
+/// collapsed containers get a hidden placeholder so they still render as
+/// expandable.
+fn build_item(
+    path: Vec<String>,
+    label: String,
+    value: &Value,
+    opened: &HashSet<Vec<String>>,
+) -> TreeItem<'static, String> {
+    let identifier = path.last().cloned().unwrap_or_default();
+    let is_open = opened.contains(&path);
+    let theme = theme::get();
+    let label = Span::styled(label, Style::default().fg(theme.label));
+    let summary = match value {
+        Value::Object(_) if is_open => Span::raw(" {}"),
+        Value::Object(map) => Span::styled(
+            format!(" {{{} keys}}", map.len()),
+            Style::default().fg(theme.muted),
+        ),
+        Value::Array(_) if is_open => Span::raw(" []"),
+        Value::Array(items) => Span::styled(
+            format!(" [{} items]", items.len()),
+            Style::default().fg(theme.muted),
+        ),
+        Value::String(s) => Span::styled(
+            format!(": {:?}", s),
+            Style::default().fg(theme.syntax.string),
+        ),
+        Value::Number(n) => {
+            Span::styled(format!(": {}", n), Style::default().fg(theme.syntax.number))
+        }
+        Value::Bool(b) => Span::styled(
+            format!(": {}", b),
+            Style::default().fg(theme.syntax.keyword),
+        ),
+        Value::Null => Span::styled(": null", Style::default().fg(theme.syntax.keyword)),
+    };
+    let text = Line::from(vec![label, summary]);
+    let kids = children(value);
+    if kids.is_empty() {
+        return TreeItem::new_leaf(identifier, text);
+    }
+    let items = if is_open {
+        kids.into_iter()
+            .map(|(segment, child)| {
+                let mut child_path = path.clone();
+                child_path.push(segment.clone());
+                let label = segment.trim_start_matches('.').to_string();
+                build_item(child_path, label, child, opened)
+            })
+            .collect()
+    } else {
+        vec![TreeItem::new_leaf(String::new(), "")]
+    };
+    TreeItem::new(identifier, text, items).expect("json paths are unique")
+}
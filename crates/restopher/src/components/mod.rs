@@ -1,24 +1,41 @@
 mod address_bar;
 mod blocks;
+mod command_palette;
 mod error_popup;
+mod export_popup;
+mod fuzzy_picker;
+mod har_popup;
+mod json_tree;
 mod kv;
 mod multi_option;
 mod pop_up;
+mod quick_open;
+mod report_popup;
 mod request_tab;
 mod requests;
 mod response_tab;
+mod search;
 mod text_area;
+mod text_area_popup;
 mod text_box;
+mod variables_popup;
 mod yes_no_popup;
 
-pub use blocks::{default_block, tabs};
+pub use blocks::{default_block, tab_at, tabs};
+pub use command_palette::CommandPaletteComponent;
 pub use error_popup::error_popup;
+pub use export_popup::{ExportPopupComponent, ExportTarget, Snippet};
+pub use har_popup::{HarAction, HarImportComponent};
 
 pub use address_bar::AddressBarComponent;
 pub use kv::KV;
 pub use multi_option::MultiOptionWidget;
 pub use pop_up::PopUpComponent;
+pub use quick_open::QuickOpenComponent;
+pub use report_popup::ReportPopupComponent;
 pub use request_tab::RequestTabComponent;
 pub use requests::{Action as RequestsAction, RequestsComponent};
 pub use response_tab::ResponseTabComponent;
+pub use text_area_popup::TextAreaPopupComponent;
+pub use variables_popup::VariablesComponent;
 pub use yes_no_popup::YesNoPopupComponent;
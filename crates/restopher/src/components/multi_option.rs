@@ -1,6 +1,7 @@
 use crate::components::default_block;
 use crate::keys::keys::{Event, Key};
 use crate::layout::centered_rect;
+use crate::theme;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
@@ -57,10 +58,10 @@ impl<T: ToString + Clone> MultiOptionWidget<T> {
         f.render_widget(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::White))
+                .border_style(Style::default().fg(theme::get().popup.border))
                 .title(Span::styled(
                     self.title.as_str(),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme::get().popup.title),
                 ))
                 .title_alignment(Alignment::Center)
                 .add_modifier(Modifier::BOLD),
@@ -1,6 +1,7 @@
 use crate::{
     components::text_box::TextBox,
     keys::keys::{Event, Key},
+    theme,
 };
 use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
@@ -38,6 +39,10 @@ impl PopUpComponent {
                 self.input.update(event);
                 return (None, true);
             }
+            Key::Space => {
+                self.input.push(' ');
+                return (None, true);
+            }
             _ => {
                 return (None, true);
             }
@@ -55,10 +60,10 @@ impl PopUpComponent {
         f.render_widget(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::White))
+                .border_style(Style::default().fg(theme::get().popup.border))
                 .title(Span::styled(
                     self.title.as_str(),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(theme::get().popup.title),
                 ))
                 .title_alignment(Alignment::Center)
                 .add_modifier(Modifier::BOLD),
@@ -0,0 +1,214 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Cell, Clear, Paragraph, Row},
+    Frame,
+};
+
+use crate::{
+    components::{default_block, fuzzy_picker::FuzzyPicker},
+    keys::keys::Event,
+    layout::centered_rect,
+    request::{HttpVerb, Request},
+    theme,
+};
+
+struct Entry {
+    location: String,
+    name: String,
+    verb: HttpVerb,
+    address: String,
+    /// The folder under the collection root, `/` for the root itself.
+    folder: String,
+}
+
+/// Finds a saved request by fuzzy search on its name, URL, method and
+/// folder, and returns its location to open it as a tab.
+pub struct QuickOpenComponent {
+    picker: FuzzyPicker<Entry>,
+}
+
+impl QuickOpenComponent {
+    /// `requests` are the saved requests under `root` with their locations.
+    pub fn new(root: &str, requests: Vec<(String, Request)>) -> Self {
+        let entries = requests
+            .into_iter()
+            .map(|(location, req)| {
+                let relative = location
+                    .strip_prefix(root)
+                    .unwrap_or(&location)
+                    .trim_start_matches('/');
+                let folder = match relative.rsplit_once('/') {
+                    Some((folder, _)) => format!("/{}", folder),
+                    None => "/".to_string(),
+                };
+                Entry {
+                    name: req.name(),
+                    verb: req.verb(),
+                    address: req.address(),
+                    folder,
+                    location,
+                }
+            })
+            .collect();
+        QuickOpenComponent {
+            picker: FuzzyPicker::new(entries, |e: &Entry| {
+                format!(
+                    "{} {} {} {}",
+                    e.name,
+                    e.verb.to_string(),
+                    e.address,
+                    e.folder
+                )
+            }),
+        }
+    }
+    /// Returns the location of the chosen request, and false when the popup
+    /// should close.
+    pub fn update(&mut self, event: &Event) -> (Option<String>, bool) {
+        let (entry, keep_open) = self.picker.update(event);
+        (entry.map(|e| e.location.clone()), keep_open)
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(80, 70, area);
+        f.render_widget(Clear, rect);
+        let chunks = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+        ])
+        .split(rect);
+
+        self.picker
+            .draw_query(f, "Open request (name, URL, method or folder)", chunks[0]);
+        let title = format!(
+            "{}/{} requests (enter: open, esc: close)",
+            self.picker.visible().len(),
+            self.picker.items().len()
+        );
+        self.picker.draw_list(
+            f,
+            Some(&title),
+            vec![
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+            |entry| {
+                Row::new(vec![
+                    Cell::from(Span::styled(entry.verb.to_string(), entry.verb.style())),
+                    Cell::from(entry.name.clone()),
+                    Cell::from(Span::styled(
+                        entry.folder.clone(),
+                        Style::default().fg(theme::get().muted),
+                    )),
+                ])
+            },
+            chunks[1],
+        );
+
+        let preview = match self.picker.selected() {
+            Some(entry) => Line::from(vec![
+                Span::styled(entry.verb.to_string(), entry.verb.style()),
+                Span::from(" "),
+                Span::from(entry.address.clone()),
+            ]),
+            None => Line::from("No request matches"),
+        };
+        f.render_widget(
+            Paragraph::new(preview).block(default_block(Some("Preview"), false)),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::keys::Key;
+    use crate::request::Mode;
+
+    fn request(name: &str, verb: HttpVerb, address: &str) -> Request {
+        let mut req = Request::new(Mode::REST);
+        req.set_name(name.to_string());
+        req.set_verb(verb);
+        req.set_address(address.to_string());
+        req
+    }
+
+    fn popup() -> QuickOpenComponent {
+        QuickOpenComponent::new(
+            "/data/collections",
+            vec![
+                (
+                    "/data/collections/users.rph".to_string(),
+                    request("List users", HttpVerb::GET, "https://api.example.com/users"),
+                ),
+                (
+                    "/data/collections/shop/orders/create.rph".to_string(),
+                    request(
+                        "Create order",
+                        HttpVerb::POST,
+                        "https://shop.example.com/orders",
+                    ),
+                ),
+            ],
+        )
+    }
+
+    fn key(key: Key) -> Event {
+        Event {
+            modifier: None,
+            key,
+        }
+    }
+
+    fn type_query(quick_open: &mut QuickOpenComponent, query: &str) {
+        for c in query.chars() {
+            let event = match c {
+                ' ' => key(Key::Space),
+                c => key(Key::Char(c)),
+            };
+            assert_eq!(quick_open.update(&event), (None, true));
+        }
+    }
+
+    #[test]
+    fn entries_know_their_folder() {
+        let quick_open = popup();
+        assert_eq!(quick_open.picker.items()[0].folder, "/");
+        assert_eq!(quick_open.picker.items()[1].folder, "/shop/orders");
+    }
+
+    #[test]
+    fn matches_name_url_method_and_folder() {
+        for query in ["create", "shop.example", "post", "orders"] {
+            let mut quick_open = popup();
+            type_query(&mut quick_open, query);
+            assert_eq!(
+                quick_open.update(&key(Key::Enter)),
+                (
+                    Some("/data/collections/shop/orders/create.rph".to_string()),
+                    false
+                ),
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn moves_the_selection_and_closes() {
+        let mut quick_open = popup();
+        quick_open.update(&key(Key::Up));
+        assert_eq!(
+            quick_open.update(&key(Key::Enter)).0.as_deref(),
+            Some("/data/collections/shop/orders/create.rph")
+        );
+        let mut quick_open = popup();
+        type_query(&mut quick_open, "nothing like it");
+        assert_eq!(quick_open.update(&key(Key::Enter)), (None, true));
+        assert_eq!(quick_open.update(&key(Key::Esc)), (None, false));
+    }
+}
@@ -0,0 +1,57 @@
+use ratatui::{
+    layout::Rect,
+    text::Line,
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    components::default_block,
+    keys::keys::{Event, Key},
+    layout::centered_rect,
+};
+
+/// A read-only, scrollable list of lines summarizing a long running action
+/// such as an import.
+pub struct ReportPopupComponent {
+    title: String,
+    lines: Vec<String>,
+    scroll: u16,
+}
+
+impl ReportPopupComponent {
+    pub fn new(title: String, lines: Vec<String>) -> Self {
+        ReportPopupComponent {
+            title,
+            lines,
+            scroll: 0,
+        }
+    }
+    /// Returns false when the report is dismissed.
+    pub fn update(&mut self, event: &Event) -> bool {
+        match event.key {
+            Key::Esc | Key::Enter => return false,
+            Key::Down if (self.scroll as usize) + 1 < self.lines.len() => self.scroll += 1,
+            Key::Up => self.scroll = self.scroll.saturating_sub(1),
+            _ => (),
+        }
+        true
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(70, 60, area);
+        f.render_widget(Clear, rect);
+        let title = format!("{} (esc: close)", self.title);
+        f.render_widget(
+            Paragraph::new(
+                self.lines
+                    .iter()
+                    .map(|l| Line::from(l.as_str()))
+                    .collect::<Vec<Line>>(),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0))
+            .block(default_block(Some(&title), true)),
+            rect,
+        );
+    }
+}
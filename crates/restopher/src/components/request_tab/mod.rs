@@ -1,13 +1,10 @@
 mod request_tab;
 mod view;
 
-use crate::{
-    keys::keys::is_ctrl_v,
-    request::{Body, BodyKind},
-};
+use crate::{keys::keys::is_ctrl_v, request::Body};
 use copypasta::{ClipboardContext, ClipboardProvider};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
     prelude::*,
     text::Span,
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
@@ -17,9 +14,14 @@ pub use request_tab::{ReqTabs, RequestTabOptions};
 use serde_json::from_str;
 
 use crate::{
-    components::{default_block, tabs, text_area::TextArea, KV},
+    components::{default_block, tab_at, tabs, text_area::TextArea, KV},
+    defaults::FolderDefaults,
+    highlight::Language,
     keys::keys::{Event, Key, Modifier},
     request::Request,
+    theme,
+    variables::Variable,
+    xml,
 };
 use view::{Focus, RequestBodyOptions};
 
@@ -32,13 +34,25 @@ pub struct RequestTabComponent<'a> {
     header_idx: usize,
     new_param: KV,
     param_idx: usize,
+    new_variable: KV,
+    variable_idx: usize,
     body_view: TextArea,
     temp_body: String,
     request_body_options: RequestBodyOptions,
+    /// Headers and params from the folders of the request, shown below its
+    /// own.
+    inherited_headers: Vec<(String, String)>,
+    inherited_params: Vec<(String, String)>,
+    /// The variables of the scopes below the request.
+    inherited_variables: Vec<Variable>,
+    /// The base URL and timeout of the folders, shown next to the tabs.
+    inherited_settings: Option<String>,
 }
 
 impl<'a> RequestTabComponent<'a> {
     pub fn new() -> Self {
+        let mut body_view = TextArea::new();
+        body_view.set_language(Language::Json);
         RequestTabComponent {
             focus: Focus::Header,
             focused: true,
@@ -47,13 +61,21 @@ impl<'a> RequestTabComponent<'a> {
             param_idx: 0,
             new_header: KV::new(),
             header_idx: 0,
-            body_view: TextArea::new(),
+            new_variable: KV::new(),
+            variable_idx: 0,
+            body_view,
             temp_body: String::from(""),
             request_body_options: RequestBodyOptions::Json,
+            inherited_headers: Vec::new(),
+            inherited_params: Vec::new(),
+            inherited_variables: Vec::new(),
+            inherited_settings: None,
         }
     }
     pub fn from(request: &Request, focus: bool) -> Self {
-        let body_view = TextArea::from(request.body().payload.unwrap_or_default(), false, true);
+        let body = request.body();
+        let mut body_view = TextArea::from(body.payload.unwrap_or_default(), false, true);
+        body_view.set_language(Language::from_body_kind(&body.kind));
         RequestTabComponent {
             focus: Focus::Header,
             focused: focus,
@@ -62,11 +84,35 @@ impl<'a> RequestTabComponent<'a> {
             param_idx: 0,
             new_header: KV::new(),
             header_idx: 0,
+            new_variable: KV::new(),
+            variable_idx: 0,
             temp_body: body_view.get_content(),
             body_view,
-            request_body_options: RequestBodyOptions::Json,
+            request_body_options: RequestBodyOptions::from_kind(&body.kind),
+            inherited_headers: Vec::new(),
+            inherited_params: Vec::new(),
+            inherited_variables: Vec::new(),
+            inherited_settings: None,
         }
     }
+    pub fn set_inherited(&mut self, defaults: &FolderDefaults) {
+        self.inherited_headers = defaults.headers();
+        self.inherited_params = defaults.params();
+        let settings = defaults
+            .base_url
+            .iter()
+            .map(|url| format!("base URL {}", url))
+            .chain(
+                defaults
+                    .timeout()
+                    .map(|t| format!("timeout {}s", t.as_secs())),
+            )
+            .collect::<Vec<String>>();
+        self.inherited_settings = (!settings.is_empty()).then(|| settings.join(", "));
+    }
+    pub fn set_inherited_variables(&mut self, variables: Vec<Variable>) {
+        self.inherited_variables = variables;
+    }
     pub fn update_inner_focus(&mut self) {
         self.focus = self.focus.next();
         self.req_tabs.next();
@@ -80,6 +126,7 @@ impl<'a> RequestTabComponent<'a> {
                     event,
                     Request::add_to_header,
                     &mut self.focus,
+                    Focus::Header,
                 );
             }
             Focus::NewParamKV => {
@@ -89,10 +136,22 @@ impl<'a> RequestTabComponent<'a> {
                     event,
                     Request::add_to_param,
                     &mut self.focus,
+                    Focus::Param,
+                );
+            }
+            Focus::NewVariableKV => {
+                RequestTabComponent::handle_new_header_or_param_update(
+                    req,
+                    &mut self.new_variable,
+                    event,
+                    |req, name, value, _| req.add_variable(name, value),
+                    &mut self.focus,
+                    Focus::Variable,
                 );
             }
             Focus::Header => self.handle_header_update(req, event),
             Focus::Param => self.handle_param_update(req, event),
+            Focus::Variable => self.handle_variable_update(req, event),
             Focus::Body => self.handle_body_update(req, event),
         }
     }
@@ -102,6 +161,7 @@ impl<'a> RequestTabComponent<'a> {
         event: &Event,
         change_fn: impl FnOnce(&mut Request, String, String, bool),
         focus: &mut Focus,
+        back: Focus,
     ) {
         if is_ctrl_v(event) {
             let mut ctx = ClipboardContext::new().unwrap();
@@ -112,7 +172,7 @@ impl<'a> RequestTabComponent<'a> {
             Key::Enter => {
                 change_fn(req, kv.get_key(), kv.get_value(), true);
                 kv.clear();
-                *focus = Focus::Header;
+                *focus = back;
             }
             Key::Tab => {
                 kv.change_active();
@@ -123,7 +183,7 @@ impl<'a> RequestTabComponent<'a> {
             Key::Backspace => {
                 kv.remove_from_active();
             }
-            Key::Esc => *focus = Focus::Header,
+            Key::Esc => *focus = back,
             _ => (),
         }
     }
@@ -242,12 +302,49 @@ impl<'a> RequestTabComponent<'a> {
             _ => (),
         }
     }
+    fn handle_variable_update(&mut self, req: &mut Request, event: &Event) {
+        if let Some(Modifier::Control) = &event.modifier {
+            match event.key {
+                Key::Char('n') => {
+                    self.focus = Focus::NewVariableKV;
+                    self.new_variable = KV::new();
+                }
+                Key::Char('d') => {
+                    req.remove_variable(self.variable_idx);
+                    self.variable_idx = self
+                        .variable_idx
+                        .min(req.variables().len().saturating_sub(1));
+                }
+                _ => (),
+            }
+        }
+        let len = req.variables().len();
+        if len == 0 {
+            return;
+        }
+        match event.key {
+            Key::Down => self.variable_idx = (self.variable_idx + 1) % len,
+            Key::Up => self.variable_idx = (self.variable_idx + len - 1) % len,
+            _ => (),
+        }
+    }
+    /// Switches the body to the next kind, keeping its content.
+    pub fn next_body_kind(&mut self, req: &mut Request) {
+        self.request_body_options.next();
+        self.body_view
+            .set_language(Language::from_body_kind(&self.request_body_options.kind()));
+        self.body_view.set_error(String::from(""));
+        Self::set_req_body(self, req);
+    }
     fn handle_body_update(&mut self, req: &mut Request, event: &Event) {
+        if self.body_view.handle_search(event) {
+            return;
+        }
         if let Some(modifier) = &event.modifier {
             match modifier {
                 Modifier::Control => match event.key {
                     Key::Char('o') => {
-                        self.request_body_options.next();
+                        self.next_body_kind(req);
                         return;
                     }
                     Key::Char('b') => {
@@ -260,7 +357,13 @@ impl<'a> RequestTabComponent<'a> {
                                 self.body_view.prettify_json();
                                 self.temp_body = self.body_view.get_content();
                             }
-                            RequestBodyOptions::Text => (),
+                            RequestBodyOptions::Xml => {
+                                self.body_view
+                                    .set_lines(xml::format(&self.temp_body, false));
+                                self.temp_body = self.body_view.get_content();
+                                Self::set_req_body(self, req);
+                            }
+                            _ => (),
                         };
                         return;
                     }
@@ -313,7 +416,7 @@ impl<'a> RequestTabComponent<'a> {
     fn set_req_body(&mut self, req: &mut Request) {
         req.set_body(Body {
             payload: Some(self.body_view.get_content()),
-            kind: BodyKind::JSON,
+            kind: self.request_body_options.kind(),
         });
     }
     pub fn is_focused(&self) -> bool {
@@ -322,7 +425,7 @@ impl<'a> RequestTabComponent<'a> {
     pub fn lose_focus(&mut self, request: &mut Request) {
         request.set_body(Body {
             payload: Some(self.body_view.get_content()),
-            kind: BodyKind::JSON,
+            kind: self.request_body_options.kind(),
         });
         self.focused = false;
         self.body_view.lose_focus();
@@ -341,6 +444,7 @@ impl<'a> RequestTabComponent<'a> {
                     f,
                     "Headers",
                     &request.headers(),
+                    &inherited(&self.inherited_headers, &request.headers(), true),
                     None,
                     self.focused,
                     chunks_vertical[0],
@@ -352,6 +456,7 @@ impl<'a> RequestTabComponent<'a> {
                     f,
                     "Headers",
                     &request.headers(),
+                    &inherited(&self.inherited_headers, &request.headers(), true),
                     Some(self.header_idx),
                     self.focused,
                     rect,
@@ -360,6 +465,14 @@ impl<'a> RequestTabComponent<'a> {
         };
     }
     pub fn draw_tabs(&mut self, f: &mut Frame, _: &Request, rect: Rect) {
+        if let Some(settings) = &self.inherited_settings {
+            f.render_widget(
+                Paragraph::new(format!("inherited {}", settings))
+                    .style(Style::default().fg(theme::get().muted))
+                    .right_aligned(),
+                rect.inner(Margin::new(2, 1)),
+            );
+        }
         f.render_widget(
             tabs(
                 self.req_tabs
@@ -385,6 +498,7 @@ impl<'a> RequestTabComponent<'a> {
                     f,
                     "Params",
                     &request.params(),
+                    &inherited(&self.inherited_params, &request.params(), false),
                     None,
                     self.focused,
                     chunks_vertical[0],
@@ -396,6 +510,7 @@ impl<'a> RequestTabComponent<'a> {
                     f,
                     "Params",
                     &request.params(),
+                    &inherited(&self.inherited_params, &request.params(), false),
                     Some(self.param_idx),
                     self.focused,
                     rect,
@@ -403,31 +518,130 @@ impl<'a> RequestTabComponent<'a> {
             }
         };
     }
+    /// The request's own variables, above the folders and the environment.
+    fn draw_variables(&mut self, f: &mut Frame, request: &Request, rect: Rect) {
+        let items = variable_items(request);
+        let inherited = self
+            .inherited_variables
+            .iter()
+            .filter(|v| !request.variables().iter().any(|(name, _)| *name == v.name))
+            .map(|v| (v.name.clone(), v.value.clone(), v.scope.to_string()))
+            .collect::<Vec<(String, String, String)>>();
+        match self.focus {
+            Focus::NewVariableKV => {
+                let chunks_vertical = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(80), Constraint::Percentage(20)])
+                    .split(rect);
+                render_items(
+                    f,
+                    "Variables",
+                    &items,
+                    &inherited,
+                    None,
+                    self.focused,
+                    chunks_vertical[0],
+                );
+                self.new_variable.draw(f, chunks_vertical[1]);
+            }
+            _ => {
+                render_items(
+                    f,
+                    "Variables",
+                    &items,
+                    &inherited,
+                    Some(self.variable_idx),
+                    self.focused,
+                    rect,
+                );
+            }
+        };
+    }
     fn draw_body(&mut self, f: &mut Frame, _: &Request, rect: Rect) {
         self.body_view.set_focus(self.is_focused());
-        let body_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Percentage(12), Constraint::Percentage(88)])
-            .split(rect);
+        let body_chunks = body_layout(rect);
         f.render_widget(
             Paragraph::new(format!("Body: {}", self.request_body_options.to_string()))
                 .block(default_block(Some("Body"), self.focused)),
             body_chunks[0],
         );
-        if let Err(e) = from_str::<serde_json::Value>(&self.temp_body) {
-            self.body_view.set_error(e.to_string());
-        } else {
-            self.body_view.set_error(String::from(""));
+        if let RequestBodyOptions::Json = self.request_body_options {
+            if let Err(e) = from_str::<serde_json::Value>(&self.temp_body) {
+                self.body_view.set_error(e.to_string());
+            } else {
+                self.body_view.set_error(String::from(""));
+            }
         }
         self.body_view.draw(f, body_chunks[1]);
     }
 
+    /// Selects the clicked tab, header or param, or places the cursor in the
+    /// body.
+    pub fn click(&mut self, req: &mut Request, rect: Rect, position: Position) {
+        let chunks = layout(rect);
+        if chunks[0].contains(position) {
+            let titles: Vec<String> = self
+                .req_tabs
+                .req_tabs
+                .iter()
+                .map(|t| t.to_string())
+                .collect();
+            if let Some(idx) = tab_at(&titles, chunks[0], position) {
+                self.select_tab(req, idx);
+            }
+            return;
+        }
+        match (&self.focus, self.req_tabs.active()) {
+            (Focus::Header, RequestTabOptions::Headers(_)) => {
+                if let Some(idx) = row_at(chunks[1], position, req.headers()) {
+                    self.header_idx = idx;
+                }
+            }
+            (Focus::Param, RequestTabOptions::Params(_)) => {
+                if let Some(idx) = row_at(chunks[1], position, req.params()) {
+                    self.param_idx = idx;
+                }
+            }
+            (Focus::Variable, RequestTabOptions::Variables(_)) => {
+                if let Some(idx) = row_at(chunks[1], position, variable_items(req)) {
+                    self.variable_idx = idx;
+                }
+            }
+            (Focus::Body, RequestTabOptions::Body(_)) => {
+                self.body_view.click(body_layout(chunks[1])[1], position)
+            }
+            _ => (),
+        }
+    }
+    /// Scrolls the body, or moves through the headers or params.
+    pub fn scroll(&mut self, req: &mut Request, down: bool) {
+        if let Focus::Body = self.focus {
+            self.body_view.scroll(down);
+            return;
+        }
+        let key = if down { Key::Down } else { Key::Up };
+        self.update(
+            req,
+            &Event {
+                modifier: None,
+                key,
+            },
+        );
+    }
+    fn select_tab(&mut self, req: &mut Request, idx: usize) {
+        if let Focus::Body = self.focus {
+            Self::set_req_body(self, req);
+        }
+        self.req_tabs.select(idx);
+        self.focus = match self.req_tabs.active() {
+            RequestTabOptions::Headers(_) => Focus::Header,
+            RequestTabOptions::Body(_) => Focus::Body,
+            RequestTabOptions::Params(_) => Focus::Param,
+            RequestTabOptions::Variables(_) => Focus::Variable,
+        };
+    }
     pub fn draw(&mut self, f: &mut Frame, request: &Request, rect: Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(0)
-            .constraints([Constraint::Percentage(10), Constraint::Percentage(90)])
-            .split(rect);
+        let chunks = layout(rect);
         self.draw_tabs(f, request, chunks[0]);
         match self.req_tabs.active() {
             RequestTabOptions::Headers(_) => {
@@ -439,27 +653,103 @@ impl<'a> RequestTabComponent<'a> {
             RequestTabOptions::Params(_) => {
                 self.draw_params(f, request, chunks[1]);
             }
+            RequestTabOptions::Variables(_) => {
+                self.draw_variables(f, request, chunks[1]);
+            }
         }
     }
 }
 
+/// The tabs and the selected tab's content.
+fn layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .margin(0)
+        .constraints([Constraint::Percentage(10), Constraint::Percentage(90)])
+        .split(rect)
+}
+
+/// The body kind and the body itself.
+fn body_layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(12), Constraint::Percentage(88)])
+        .split(rect)
+}
+
+/// The row of `render_items` at `position`, below the border and the column
+/// names.
+fn row_at(
+    rect: Rect,
+    position: Position,
+    items: Option<Vec<(String, String, bool)>>,
+) -> Option<usize> {
+    let first_row = rect.y + 2;
+    if !rect.contains(position) || position.y < first_row {
+        return None;
+    }
+    let idx = (position.y - first_row) as usize;
+    (idx < items?.len()).then_some(idx)
+}
+
+/// The variables of the request as rows of `render_items`, always active.
+fn variable_items(req: &Request) -> Option<Vec<(String, String, bool)>> {
+    let variables = req.variables();
+    (!variables.is_empty()).then(|| {
+        variables
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone(), true))
+            .collect()
+    })
+}
+
+/// The inherited items that the active ones of the request do not override.
+fn inherited(
+    inherited: &[(String, String)],
+    items: &Option<Vec<(String, String, bool)>>,
+    ignore_case: bool,
+) -> Vec<(String, String, String)> {
+    let overridden = |key: &str| {
+        items.iter().flatten().any(|(k, _, active)| {
+            *active && (k == key || (ignore_case && k.eq_ignore_ascii_case(key)))
+        })
+    };
+    inherited
+        .iter()
+        .filter(|(k, _)| !overridden(k))
+        .map(|(k, v)| (k.clone(), v.clone(), "inherited".to_string()))
+        .collect()
+}
+
 fn render_items(
     f: &mut Frame,
     block_title: &str,
     items: &Option<Vec<(String, String, bool)>>,
+    inherited: &[(String, String, String)],
     state: Option<usize>,
     focused: bool,
     rect: Rect,
 ) {
-    if let Some(items) = items {
+    if items.is_some() || !inherited.is_empty() {
         let mut rows = Vec::new();
-        for item in items {
+        for item in items.iter().flatten() {
             rows.push(Row::new(vec![
                 Cell::from(item.0.clone()),
                 Cell::from(item.1.clone()),
                 Cell::from(format!("{}", item.2)),
             ]));
         }
+        // below the request's own rows, so the selection indices stay theirs
+        for (key, value, from) in inherited {
+            rows.push(
+                Row::new(vec![
+                    Cell::from(key.clone()),
+                    Cell::from(value.clone()),
+                    Cell::from(from.clone()),
+                ])
+                .style(Style::default().fg(theme::get().muted)),
+            );
+        }
         // Create table
         let table = Table::new(
             rows,
@@ -475,7 +765,11 @@ fn render_items(
         match state {
             Some(n) => {
                 f.render_stateful_widget(
-                    table.highlight_style(Style::default().fg(Color::White).bg(Color::Blue)),
+                    table.highlight_style(
+                        Style::default()
+                            .fg(theme::get().row_fg)
+                            .bg(theme::get().row_bg),
+                    ),
                     rect,
                     &mut TableState::new().with_selected(n),
                 );
@@ -3,6 +3,7 @@ pub enum RequestTabOptions<'a> {
     Headers(&'a str),
     Params(&'a str),
     Body(&'a str),
+    Variables(&'a str),
 }
 
 impl<'a> RequestTabOptions<'a> {
@@ -10,7 +11,8 @@ impl<'a> RequestTabOptions<'a> {
         match self {
             RequestTabOptions::Headers(name)
             | RequestTabOptions::Params(name)
-            | RequestTabOptions::Body(name) => name.to_string(),
+            | RequestTabOptions::Body(name)
+            | RequestTabOptions::Variables(name) => name.to_string(),
         }
     }
 }
@@ -25,6 +27,7 @@ impl<'a> ReqTabs<'a> {
             &RequestTabOptions::Headers("Headers"),
             &RequestTabOptions::Body("Body"),
             &RequestTabOptions::Params("Params"),
+            &RequestTabOptions::Variables("Variables"),
         ];
         ReqTabs {
             req_tabs: tabs,
@@ -38,6 +41,11 @@ impl<'a> ReqTabs<'a> {
         }
         self.state += 1;
     }
+    pub fn select(&mut self, idx: usize) {
+        if idx < self.req_tabs.len() {
+            self.state = idx;
+        }
+    }
     pub fn active(&self) -> &RequestTabOptions {
         self.req_tabs[self.state]
     }
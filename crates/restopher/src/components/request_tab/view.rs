@@ -1,18 +1,24 @@
+use crate::request::BodyKind;
+
 pub enum Focus {
     NewHeaderKV,
     NewParamKV,
+    NewVariableKV,
     Header,
     Param,
+    Variable,
     Body,
 }
 impl Focus {
     pub fn next(&self) -> Focus {
         match self {
             Focus::Header => Focus::Body,
-            Focus::Param => Focus::Header,
+            Focus::Param => Focus::Variable,
+            Focus::Variable => Focus::Header,
             Focus::Body => Focus::Param,
             Focus::NewHeaderKV => Focus::NewHeaderKV,
             Focus::NewParamKV => Focus::NewParamKV,
+            Focus::NewVariableKV => Focus::NewVariableKV,
         }
     }
 }
@@ -20,18 +26,50 @@ impl Focus {
 pub enum RequestBodyOptions {
     Json,
     Text,
+    Xml,
+    GraphQL,
+    Form,
+    Multipart,
 }
 impl RequestBodyOptions {
+    pub fn from_kind(kind: &BodyKind) -> Self {
+        match kind {
+            BodyKind::JSON => RequestBodyOptions::Json,
+            BodyKind::TEXT => RequestBodyOptions::Text,
+            BodyKind::XML => RequestBodyOptions::Xml,
+            BodyKind::GRAPHQL => RequestBodyOptions::GraphQL,
+            BodyKind::FORM => RequestBodyOptions::Form,
+            BodyKind::MULTIPART => RequestBodyOptions::Multipart,
+        }
+    }
+    pub fn kind(&self) -> BodyKind {
+        match self {
+            RequestBodyOptions::Json => BodyKind::JSON,
+            RequestBodyOptions::Text => BodyKind::TEXT,
+            RequestBodyOptions::Xml => BodyKind::XML,
+            RequestBodyOptions::GraphQL => BodyKind::GRAPHQL,
+            RequestBodyOptions::Form => BodyKind::FORM,
+            RequestBodyOptions::Multipart => BodyKind::MULTIPART,
+        }
+    }
     pub fn to_string(&self) -> String {
         match self {
             RequestBodyOptions::Json => "JSON".to_string(),
             RequestBodyOptions::Text => "Text".to_string(),
+            RequestBodyOptions::Xml => "XML".to_string(),
+            RequestBodyOptions::GraphQL => "GraphQL".to_string(),
+            RequestBodyOptions::Form => "Form (key=value per line)".to_string(),
+            RequestBodyOptions::Multipart => "Multipart (key=value or key=@file)".to_string(),
         }
     }
     pub fn next(&mut self) {
         *self = match self {
             RequestBodyOptions::Json => RequestBodyOptions::Text,
-            RequestBodyOptions::Text => RequestBodyOptions::Json,
+            RequestBodyOptions::Text => RequestBodyOptions::Xml,
+            RequestBodyOptions::Xml => RequestBodyOptions::GraphQL,
+            RequestBodyOptions::GraphQL => RequestBodyOptions::Form,
+            RequestBodyOptions::Form => RequestBodyOptions::Multipart,
+            RequestBodyOptions::Multipart => RequestBodyOptions::Json,
         }
     }
 }
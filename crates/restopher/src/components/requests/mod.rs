@@ -1,11 +1,11 @@
-use crate::components::{default_block, tabs};
+use crate::components::{default_block, tab_at, tabs};
 use crate::environments::Environment;
 use crate::keys::keys::{Event, Key, Modifier};
 use crate::layout::centered_rect;
 use crate::request::Request;
 use ratatui::widgets::Clear;
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Position, Rect},
     text::Span,
     widgets::Paragraph,
     Frame,
@@ -167,6 +167,49 @@ impl RequestsComponent {
             _ => None,
         }
     }
+    /// Switches to the clicked request, or focuses the environment.
+    pub fn click(
+        &mut self,
+        names: Vec<String>,
+        request_index: &mut usize,
+        rect: Rect,
+        position: Position,
+    ) -> Option<Action> {
+        if self.popup.is_some() {
+            return None;
+        }
+        let chunks = layout(rect);
+        if chunks[1].contains(position) {
+            self.focus = Focus::Env;
+            return None;
+        }
+        self.focus = Focus::Requests;
+        let idx = tab_at(&names, chunks[0], position).filter(|idx| idx != request_index)?;
+        *request_index = idx;
+        Some(Action::RequestIndexChanged)
+    }
+    /// Cycles through the environments when the wheel turns over them.
+    pub fn scroll(
+        &mut self,
+        environments: &mut Vec<Environment>,
+        environment_index: &mut usize,
+        rect: Rect,
+        position: Position,
+        down: bool,
+    ) {
+        if self.popup.is_some() || !layout(rect)[1].contains(position) {
+            return;
+        }
+        let key = if down { Key::Down } else { Key::Up };
+        self.handle_env_update(
+            environments,
+            environment_index,
+            &Event {
+                modifier: None,
+                key,
+            },
+        );
+    }
     pub fn lose_focus(&mut self) {
         self.is_focused = false;
     }
@@ -181,9 +224,7 @@ impl RequestsComponent {
         selected: usize,
         rect: Rect,
     ) {
-        let chunks =
-            Layout::horizontal(vec![Constraint::Percentage(90), Constraint::Percentage(10)])
-                .split(rect);
+        let chunks = layout(rect);
         f.render_widget(
             tabs(
                 names.iter().map(|t| Span::from(t.to_string())).collect(),
@@ -207,3 +248,8 @@ impl RequestsComponent {
         }
     }
 }
+
+/// The open requests and the selected environment.
+fn layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::horizontal(vec![Constraint::Percentage(90), Constraint::Percentage(10)]).split(rect)
+}
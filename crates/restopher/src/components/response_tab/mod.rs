@@ -3,25 +3,101 @@ mod view;
 //use crate::trace_dbg;
 
 use crate::{
-    components::{default_block, tabs, text_area::TextArea},
-    keys::keys::Event,
+    components::{
+        default_block, json_tree::JsonTree, tab_at, tabs, text_area::TextArea, text_box::TextBox,
+    },
+    filter,
+    highlight::Language,
+    keys::keys::{Event, Key, Modifier},
     request::Request,
+    styles::status_color,
+    theme, xml,
 };
 
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
     style::{Color, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
     Frame,
 };
 use response_tab::ResponseOptions;
-use view::Focus;
+use view::{BodyView, Focus};
+
+/// Result of the response filter, depending on the body's content type.
+enum Filtered {
+    Json(Vec<serde_json::Value>),
+    Markup(String, usize),
+}
+
+impl Filtered {
+    fn len(&self) -> usize {
+        match self {
+            Filtered::Json(results) => results.len(),
+            Filtered::Markup(_, count) => *count,
+        }
+    }
+}
+
+/// The filter results of the drawn response, computed again only when the
+/// body or the expression changes rather than on every frame.
+#[derive(Default)]
+struct FilterCache {
+    body: String,
+    /// Whether the body is HTML, when it is markup.
+    html: Option<bool>,
+    /// The body parsed as JSON, once a filter needs it.
+    parsed: Option<Result<serde_json::Value, String>>,
+    /// The expression `filtered` holds the results of, `None` when stale.
+    expression: Option<String>,
+    filtered: Option<Result<Filtered, String>>,
+}
+
+impl FilterCache {
+    fn filtered(
+        &mut self,
+        body: &str,
+        html: Option<bool>,
+        expression: &str,
+    ) -> Option<&Result<Filtered, String>> {
+        if self.body != body || self.html != html {
+            *self = FilterCache {
+                body: body.to_string(),
+                html,
+                ..FilterCache::default()
+            };
+        }
+        if self.expression.as_deref() != Some(expression) {
+            self.filtered = if expression.is_empty() {
+                None
+            } else if let Some(html) = html {
+                Some(
+                    xml::apply_to_body(expression, body, html)
+                        .map(|results| Filtered::Markup(results.join("\n"), results.len())),
+                )
+            } else {
+                let parsed = self.parsed.get_or_insert_with(|| {
+                    serde_json::from_str::<serde_json::Value>(body).map_err(|e| e.to_string())
+                });
+                Some(match parsed {
+                    Ok(value) => filter::apply(expression, value).map(Filtered::Json),
+                    Err(e) => Err(e.clone()),
+                })
+            };
+            self.expression = Some(expression.to_string());
+        }
+        self.filtered.as_ref()
+    }
+}
 
 pub struct ResponseTabComponent {
     focus: Focus,
     is_focused: bool,
     body_view: TextArea,
+    body_view_mode: BodyView,
+    json_tree: JsonTree,
+    filter_input: Option<TextBox>,
+    filter_cache: FilterCache,
     resp_tabs: response_tab::RespTabs,
 }
 
@@ -34,18 +110,51 @@ impl ResponseTabComponent {
             focus: Focus::Header,
             is_focused: false,
             resp_tabs: response_tab::RespTabs::new(),
-            body_view: TextArea::new(),
+            body_view: TextArea::from(String::new(), false, false),
+            body_view_mode: BodyView::Pretty,
+            json_tree: JsonTree::new(),
+            filter_input: None,
+            filter_cache: FilterCache::default(),
         }
     }
     pub fn update_inner_focus(&mut self) {
         self.focus = self.focus.next();
         self.resp_tabs.next();
     }
-    pub fn update(&mut self, _: &mut Request, event: &Event) {
+    pub fn update(&mut self, req: &mut Request, event: &Event) {
         match &self.focus {
             Focus::Header => (),
             Focus::Body => {
-                self.body_view.update(event);
+                if let Some(input) = &mut self.filter_input {
+                    match event.key {
+                        Key::Enter => self.filter_input = None,
+                        Key::Esc => {
+                            req.set_response_filter(None);
+                            self.filter_input = None;
+                        }
+                        Key::Space => input.push(' '),
+                        _ => input.update(event),
+                    }
+                    if let Some(input) = &self.filter_input {
+                        req.set_response_filter(Some(input.get_content()));
+                    }
+                    return;
+                }
+                if event.modifier.is_none() && matches!(event.key, Key::Char('|')) {
+                    self.filter_input =
+                        Some(TextBox::from(req.response_filter().unwrap_or_default()));
+                    return;
+                }
+                if matches!(event.modifier, Some(Modifier::Control))
+                    && matches!(event.key, Key::Char('o'))
+                {
+                    self.body_view_mode = self.body_view_mode.next();
+                    return;
+                }
+                match self.body_view_mode {
+                    BodyView::Tree => self.json_tree.update(event),
+                    BodyView::Raw | BodyView::Pretty => self.body_view.update(event),
+                }
             }
         }
     }
@@ -55,11 +164,65 @@ impl ResponseTabComponent {
     pub fn gain_focus(&mut self) {
         self.is_focused = true;
     }
+    /// Selects the clicked tab, opens the filter bar or places its cursor, and
+    /// passes clicks in the body on to the tree or the text.
+    pub fn click(&mut self, req: &Request, rect: Rect, position: Position) {
+        let chunks = layout(rect);
+        if chunks[0].contains(position) {
+            let titles: Vec<String> = self
+                .resp_tabs
+                .resp_tabs
+                .iter()
+                .map(|t| t.to_string())
+                .collect();
+            if let Some(idx) = tab_at(&titles, chunks[0], position) {
+                self.resp_tabs.select(idx);
+                self.focus = match self.resp_tabs.active() {
+                    ResponseOptions::Headers => Focus::Header,
+                    ResponseOptions::Body => Focus::Body,
+                };
+            }
+            return;
+        }
+        if !matches!(self.focus, Focus::Body) || !chunks[2].contains(position) {
+            return;
+        }
+        let (filter_bar, body) = self.body_layout(req, chunks[2]);
+        match filter_bar {
+            Some(bar) if bar.contains(position) => match &mut self.filter_input {
+                Some(input) => input.click(bar, position),
+                None => {
+                    self.filter_input =
+                        Some(TextBox::from(req.response_filter().unwrap_or_default()))
+                }
+            },
+            _ => match self.body_view_mode {
+                BodyView::Tree => self.json_tree.click(position),
+                BodyView::Raw | BodyView::Pretty => self.body_view.click(body, position),
+            },
+        }
+    }
+    pub fn scroll(&mut self, down: bool) {
+        if !matches!(self.focus, Focus::Body) {
+            return;
+        }
+        match self.body_view_mode {
+            BodyView::Tree => self.json_tree.scroll(down),
+            BodyView::Raw | BodyView::Pretty => self.body_view.scroll(down),
+        }
+    }
+    /// Splits the body tab into the filter bar, shown while a filter is set
+    /// or edited, and the body.
+    fn body_layout(&self, req: &Request, rect: Rect) -> (Option<Rect>, Rect) {
+        let filtering = !req.response_filter().unwrap_or_default().is_empty();
+        if !filtering && self.filter_input.is_none() {
+            return (None, rect);
+        }
+        let chunks = Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).split(rect);
+        (Some(chunks[0]), chunks[1])
+    }
     pub fn draw(&mut self, f: &mut Frame, req: &Request, rect: Rect) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([Constraint::Max(3), Constraint::Max(3), Constraint::Fill(1)])
-            .split(rect);
+        let chunks = layout(rect);
         f.render_widget(
             tabs(
                 self.resp_tabs
@@ -96,13 +259,7 @@ impl ResponseTabComponent {
                     .borders(Borders::ALL)
                     .style(Style::default().fg({
                         if let Some(resp) = &req.response() {
-                            if resp.status_code >= 200 && resp.status_code < 300 {
-                                Color::Green
-                            } else if resp.status_code >= 300 && resp.status_code < 400 {
-                                Color::Yellow
-                            } else {
-                                Color::Red
-                            }
+                            status_color(resp.status_code)
                         } else {
                             Color::Reset
                         }
@@ -141,20 +298,152 @@ impl ResponseTabComponent {
                     );
                 }
             }
-            ResponseOptions::Body => {
-                let formatted_body = req.resp_body_formatted();
-                if !formatted_body.is_empty() {
-                    self.body_view.set_focus(self.is_focused);
-                    self.body_view.set_lines(formatted_body);
-                    self.body_view.draw(f, chunks[2])
-                } else {
-                    f.render_widget(
-                        Paragraph::new("No Body")
-                            .block(default_block(Some("Response Body"), self.is_focused)),
-                        chunks[2],
-                    );
-                }
+            ResponseOptions::Body => self.draw_body(f, req, chunks[2]),
+        }
+    }
+    fn draw_body(&mut self, f: &mut Frame, req: &Request, rect: Rect) {
+        let raw_body = req.response().and_then(|r| r.body).unwrap_or_default();
+        if raw_body.is_empty() {
+            f.render_widget(
+                Paragraph::new("No Body")
+                    .block(default_block(Some("Response Body"), self.is_focused)),
+                rect,
+            );
+            return;
+        }
+        let mut rect = rect;
+        let expression = req.response_filter().unwrap_or_default();
+        let markup = req
+            .response_content_type()
+            .map(|ct| {
+                (
+                    xml::is_xml_content_type(&ct),
+                    xml::is_html_content_type(&ct),
+                )
+            })
+            .filter(|(is_xml, is_html)| *is_xml || *is_html);
+        // taken out while drawing, the results borrow from it
+        let mut cache = std::mem::take(&mut self.filter_cache);
+        let filtered = cache.filtered(&raw_body, markup.map(|(_, html)| html), &expression);
+        if let (Some(bar), body) = self.body_layout(req, rect) {
+            rect = body;
+            let syntax = if markup.is_some() {
+                "XPath"
+            } else {
+                "jq or $.jsonpath"
+            };
+            self.draw_filter_bar(f, &expression, syntax, filtered, bar);
+        }
+        let filtered = filtered.and_then(|r| r.as_ref().ok());
+        let title = format!(
+            "Response Body [{}] (ctrl-o: view, |: filter)",
+            self.body_view_mode
+        );
+        match self.body_view_mode {
+            BodyView::Tree => {
+                let source = match &filtered {
+                    Some(Filtered::Json(results)) if results.len() == 1 => results[0].to_string(),
+                    Some(Filtered::Json(results)) => {
+                        serde_json::Value::Array(results.clone()).to_string()
+                    }
+                    Some(Filtered::Markup(text, _)) => text.clone(),
+                    None => raw_body,
+                };
+                self.json_tree.set_source(&source);
+                self.json_tree.draw(f, rect, &title, self.is_focused);
+            }
+            BodyView::Raw | BodyView::Pretty => {
+                let body = match (&self.body_view_mode, &filtered) {
+                    (BodyView::Raw, _) => raw_body,
+                    (_, Some(Filtered::Json(results))) => filter::render(results),
+                    (_, Some(Filtered::Markup(text, _))) => text.clone(),
+                    _ => req.resp_body_formatted(),
+                };
+                let language = match (&self.body_view_mode, &filtered) {
+                    (BodyView::Pretty, Some(Filtered::Json(_))) => Language::Json,
+                    (BodyView::Pretty, Some(Filtered::Markup(..))) => Language::Xml,
+                    _ => req
+                        .response_content_type()
+                        .map(|ct| Language::from_content_type(&ct))
+                        .unwrap_or_default(),
+                };
+                self.body_view.set_language(language);
+                self.body_view.set_focus(self.is_focused);
+                self.body_view.set_title(title);
+                self.body_view.set_lines(body);
+                self.body_view.draw(f, rect)
             }
         }
+        self.filter_cache = cache;
+    }
+    fn draw_filter_bar(
+        &self,
+        f: &mut Frame,
+        expression: &str,
+        syntax: &str,
+        filtered: Option<&Result<Filtered, String>>,
+        rect: Rect,
+    ) {
+        let mut spans = Vec::new();
+        match &self.filter_input {
+            Some(input) => input.get_content_styled(&mut spans, true),
+            None => spans.push(Span::from(expression.to_string())),
+        }
+        match filtered {
+            Some(Ok(results)) => spans.push(Span::styled(
+                format!("  ({} results)", results.len()),
+                Style::default().fg(theme::get().success),
+            )),
+            Some(Err(e)) => spans.push(Span::styled(
+                format!("  {}", e),
+                Style::default().fg(theme::get().error),
+            )),
+            None => (),
+        }
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(
+                Some(&format!("Filter ({}, enter: apply, esc: clear)", syntax)),
+                self.filter_input.is_some(),
+            )),
+            rect,
+        );
+    }
+}
+
+/// The tabs, the status and the selected tab's content.
+fn layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Max(3), Constraint::Max(3), Constraint::Fill(1)])
+        .split(rect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(filtered: Option<&Result<Filtered, String>>) -> Option<Result<usize, String>> {
+        filtered.map(|r| r.as_ref().map(|f| f.len()).map_err(|e| e.clone()))
+    }
+
+    #[test]
+    fn filter_cache_follows_body_and_expression() {
+        let mut cache = FilterCache::default();
+        let body = "{\"items\": [1, 2, 3]}";
+        assert_eq!(count(cache.filtered(body, None, "")), None);
+        assert_eq!(count(cache.filtered(body, None, ".items[]")), Some(Ok(3)));
+        assert!(cache.parsed.is_some());
+        assert_eq!(count(cache.filtered(body, None, ".items")), Some(Ok(1)));
+
+        // a new body starts over
+        assert!(matches!(
+            count(cache.filtered("not json", None, ".items")),
+            Some(Err(_))
+        ));
+        assert_eq!(
+            count(cache.filtered("<a><b/><b/></a>", Some(false), "//b")),
+            Some(Ok(2))
+        );
+        assert!(cache.parsed.is_none());
     }
 }
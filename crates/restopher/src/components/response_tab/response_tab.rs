@@ -31,6 +31,11 @@ impl RespTabs {
         }
         self.state += 1;
     }
+    pub fn select(&mut self, idx: usize) {
+        if idx < self.resp_tabs.len() {
+            self.state = idx;
+        }
+    }
     pub fn active(&self) -> ResponseOptions {
         self.resp_tabs[self.state].clone()
     }
@@ -12,3 +12,31 @@ impl Focus {
         }
     }
 }
+
+/// How the response body is shown on the Body tab.
+#[derive(Debug, Clone)]
+pub enum BodyView {
+    Raw,
+    Pretty,
+    Tree,
+}
+
+impl BodyView {
+    pub fn next(&self) -> BodyView {
+        match self {
+            BodyView::Raw => BodyView::Pretty,
+            BodyView::Pretty => BodyView::Tree,
+            BodyView::Tree => BodyView::Raw,
+        }
+    }
+}
+
+impl std::fmt::Display for BodyView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyView::Raw => write!(f, "Raw"),
+            BodyView::Pretty => write!(f, "Pretty"),
+            BodyView::Tree => write!(f, "Tree"),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use regex::{Regex, RegexBuilder};
+
+use crate::components::{default_block, text_box::TextBox};
+use crate::keys::keys::{Event, Key, Modifier};
+use crate::theme;
+
+/// A match inside a text area, as (line, start byte, end byte).
+pub type Match = (usize, usize, usize);
+
+/// Incremental search state shared by the text areas. The query is kept while
+/// the search bar is closed so matches stay highlighted until cleared.
+#[derive(Debug)]
+pub struct Search {
+    query: TextBox,
+    editing: bool,
+    case_insensitive: bool,
+    regex: bool,
+    /// Sorted by line then start, so the matches of a line are contiguous.
+    matches: Vec<Match>,
+    current: usize,
+    error: Option<String>,
+    /// Set when the text changed since the matches were computed.
+    stale: bool,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Search {
+            query: TextBox::new(),
+            editing: true,
+            case_insensitive: true,
+            regex: false,
+            matches: Vec::new(),
+            current: 0,
+            error: None,
+            stale: true,
+        }
+    }
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+    pub fn edit(&mut self) {
+        self.editing = true;
+    }
+    /// The matches on line `line`.
+    pub fn line_matches(&self, line: usize) -> &[Match] {
+        let start = self.matches.partition_point(|m| m.0 < line);
+        let end = self.matches.partition_point(|m| m.0 <= line);
+        &self.matches[start..end]
+    }
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+    pub fn current(&self) -> Option<Match> {
+        self.matches.get(self.current).copied()
+    }
+    /// Handles a key while the search bar is being edited. Returns false when
+    /// the search was cancelled.
+    pub fn update_query(&mut self, event: &Event) -> bool {
+        if let Some(Modifier::Alt) = event.modifier {
+            match event.key {
+                Key::Char('c') => self.case_insensitive = !self.case_insensitive,
+                Key::Char('r') => self.regex = !self.regex,
+                _ => (),
+            }
+            return true;
+        }
+        match event.key {
+            Key::Esc => return false,
+            Key::Enter => self.editing = false,
+            Key::Space => self.query.push(' '),
+            _ => self.query.update(event),
+        }
+        true
+    }
+    fn pattern(&self) -> Result<Option<Regex>, regex::Error> {
+        let query = self.query.get_content();
+        if query.is_empty() {
+            return Ok(None);
+        }
+        let pattern = if self.regex {
+            query
+        } else {
+            regex::escape(&query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(self.case_insensitive)
+            .build()
+            .map(Some)
+    }
+    /// Recomputes the matches and keeps the current one on the first match at
+    /// or after `from`.
+    pub fn refresh(&mut self, lines: &[String], from: (usize, usize)) {
+        self.matches.clear();
+        self.stale = false;
+        let pattern = match self.pattern() {
+            Ok(Some(p)) => p,
+            Ok(None) => {
+                self.error = None;
+                return;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+        self.error = None;
+        for (idx, line) in lines.iter().enumerate() {
+            for m in pattern.find_iter(line) {
+                if m.start() == m.end() {
+                    continue;
+                }
+                self.matches.push((idx, m.start(), m.end()));
+            }
+        }
+        self.current = self
+            .matches
+            .iter()
+            .position(|(line, start, _)| (*line, *start) >= (from.1, from.0))
+            .unwrap_or(0);
+    }
+    pub fn next(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+    pub fn previous(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+    pub fn draw(&self, f: &mut Frame, rect: Rect) {
+        let mut spans = Vec::new();
+        self.query.get_content_styled(&mut spans, self.editing);
+        spans.insert(0, Span::from("/"));
+        let status = match &self.error {
+            Some(e) => Span::styled(format!("  {}", e), Style::default().fg(theme::get().error)),
+            None if self.matches.is_empty() => Span::from("  0/0"),
+            None => Span::from(format!("  {}/{}", self.current + 1, self.matches.len())),
+        };
+        spans.push(status);
+        let title = format!(
+            "Search [{}] [{}] (alt-c, alt-r)",
+            if self.case_insensitive { "aA" } else { "Aa" },
+            if self.regex { ".*" } else { "txt" }
+        );
+        f.render_widget(
+            Paragraph::new(Line::from(spans)).block(default_block(Some(&title), self.editing)),
+            rect,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(query: &str) -> Search {
+        let mut search = Search::new();
+        for c in query.chars() {
+            search.update_query(&Event {
+                modifier: None,
+                key: Key::Char(c),
+            });
+        }
+        search
+    }
+
+    fn toggle(search: &mut Search, c: char) {
+        search.update_query(&Event {
+            modifier: Some(Modifier::Alt),
+            key: Key::Char(c),
+        });
+    }
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_every_match_ignoring_case() {
+        let mut search = typed("ab");
+        search.refresh(&lines("ab AB\nxaby"), (0, 0));
+        assert_eq!(search.matches, vec![(0, 0, 2), (0, 3, 5), (1, 1, 3)]);
+        toggle(&mut search, 'c');
+        search.refresh(&lines("ab AB\nxaby"), (0, 0));
+        assert_eq!(search.matches, vec![(0, 0, 2), (1, 1, 3)]);
+    }
+
+    #[test]
+    fn current_match_starts_at_the_cursor_and_wraps() {
+        let mut search = typed("a");
+        search.refresh(&lines("a a\na"), (1, 0));
+        assert_eq!(search.current(), Some((0, 2, 3)));
+        search.next();
+        assert_eq!(search.current(), Some((1, 0, 1)));
+        search.next();
+        assert_eq!(search.current(), Some((0, 0, 1)));
+        search.previous();
+        assert_eq!(search.current(), Some((1, 0, 1)));
+    }
+
+    #[test]
+    fn text_queries_are_literal_unless_regex() {
+        let mut search = typed("a.c");
+        search.refresh(&lines("abc a.c"), (0, 0));
+        assert_eq!(search.matches, vec![(0, 4, 7)]);
+        toggle(&mut search, 'r');
+        search.refresh(&lines("abc a.c"), (0, 0));
+        assert_eq!(search.matches.len(), 2);
+    }
+
+    #[test]
+    fn invalid_regex_reports_an_error() {
+        let mut search = typed("(");
+        toggle(&mut search, 'r');
+        search.refresh(&lines("("), (0, 0));
+        assert!(search.matches.is_empty());
+        assert!(search.error.is_some());
+        assert!(!search.update_query(&Event {
+            modifier: None,
+            key: Key::Esc,
+        }));
+    }
+
+    #[test]
+    fn matches_are_byte_offsets_grouped_by_line() {
+        let mut search = typed("é");
+        assert!(search.is_stale());
+        search.refresh(
+            &lines(
+                "aé é
+x
+É",
+            ),
+            (0, 0),
+        );
+        assert!(!search.is_stale());
+        assert_eq!(search.line_matches(0), &[(0, 1, 3), (0, 4, 6)]);
+        assert!(search.line_matches(1).is_empty());
+        assert_eq!(search.line_matches(2), &[(2, 0, 2)]);
+        search.invalidate();
+        assert!(search.is_stale());
+    }
+}
@@ -1,16 +1,18 @@
 use copypasta::{ClipboardContext, ClipboardProvider};
 use ratatui::{
-    layout::{Constraint, Layout, Margin, Rect},
-    style::{Color, Style},
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    style::Style,
     text::{Line, Span},
     widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
     Frame,
 };
 
 use crate::{
-    components::default_block,
-    keys::keys::{Event, Key, Modifier as keyModifier},
-    styles::cursor_like_span,
+    components::{default_block, search::Search},
+    highlight::{highlight_line, Language},
+    keys::keys::{Event, Key, Modifier as keyModifier, SCROLL_LINES},
+    styles::{cursor_like_span, search_match_style},
+    theme,
 };
 
 pub struct TextArea {
@@ -19,6 +21,9 @@ pub struct TextArea {
     is_focused: bool,
     mutable: bool,
     error: String,
+    title: String,
+    search: Option<Search>,
+    language: Language,
 }
 
 impl TextArea {
@@ -29,19 +34,24 @@ impl TextArea {
             is_focused: true,
             mutable: true,
             error: String::from(""),
+            title: String::from("Body"),
+            search: None,
+            language: Language::Text,
         }
     }
     pub fn from(lines: String, is_focused: bool, mutable: bool) -> Self {
-        TextArea {
-            lines: lines
-                .lines()
-                .map(|l| l.to_string())
-                .collect::<Vec<String>>(),
+        let mut text_area = TextArea {
+            lines: Vec::new(),
             cursor_pos: (0, 0),
             is_focused,
             mutable,
             error: String::from(""),
-        }
+            title: String::from("Body"),
+            search: None,
+            language: Language::Text,
+        };
+        text_area.set_lines(lines);
+        text_area
     }
     pub fn set_focus(&mut self, focus: bool) {
         self.is_focused = focus;
@@ -49,10 +59,23 @@ impl TextArea {
     pub fn lose_focus(&mut self) {
         self.is_focused = false;
     }
+    pub fn set_title(&mut self, title: String) {
+        self.title = title;
+    }
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+    }
     pub fn set_error(&mut self, error: String) {
         self.error = error;
     }
+    /// Marks the search matches out of date after an edit.
+    fn edited(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.invalidate();
+        }
+    }
     pub fn new_line(&mut self) {
+        self.edited();
         if self.is_on_last_line() {
             self.lines.push(String::from(""));
             self.set_cursor_to_begin_of_last_line();
@@ -73,33 +96,35 @@ impl TextArea {
             self.new_line();
             return;
         }
+        self.edited();
         match self.lines.get_mut(self.cursor_pos.1) {
             None => {
                 self.lines.push(String::from(c));
             }
             Some(l) => l.insert(self.cursor_pos.0, c),
         }
-        self.cursor_pos.0 += 1;
+        self.cursor_pos.0 += c.len_utf8();
     }
     pub fn pop(&mut self) {
         if self.cursor_pos.0 == 0 && self.cursor_pos.1 == 0 {
             return;
         }
+        self.edited();
         if self.cursor_pos.0 == 0 {
             self.cursor_pos.1 -= 1;
             self.cursor_pos.0 = self.lines[self.cursor_pos.1].len();
             self.lines[self.cursor_pos.1 + 1].pop();
             return;
         }
-        self.lines
-            .get_mut(self.cursor_pos.1)
-            .unwrap()
-            .remove(self.cursor_pos.0 - 1);
-        self.cursor_pos.0 -= 1;
+        let line = self.lines.get_mut(self.cursor_pos.1).unwrap();
+        let previous = line.floor_char_boundary(self.cursor_pos.0 - 1);
+        line.remove(previous);
+        self.cursor_pos.0 = previous;
     }
     pub fn cursor_pre(&mut self) {
         if self.cursor_pos.0 > 0 {
-            self.cursor_pos.0 -= 1;
+            let line = &self.lines[self.cursor_pos.1];
+            self.cursor_pos.0 = line.floor_char_boundary(self.cursor_pos.0 - 1);
         } else if self.cursor_pos.1 > 0 {
             self.cursor_pos.1 -= 1;
             self.cursor_pos.0 = self.lines[self.cursor_pos.1].len();
@@ -108,7 +133,8 @@ impl TextArea {
 
     pub fn cursor_next(&mut self) {
         if self.cursor_pos.0 < self.lines[self.cursor_pos.1].len() {
-            self.cursor_pos.0 += 1;
+            let line = &self.lines[self.cursor_pos.1];
+            self.cursor_pos.0 = line.ceil_char_boundary(self.cursor_pos.0 + 1);
         } else if self.cursor_pos.1 < self.lines.len() - 1 {
             self.cursor_pos.1 += 1;
             self.cursor_pos.0 = 0;
@@ -117,15 +143,15 @@ impl TextArea {
     pub fn cursor_up(&mut self) {
         if self.cursor_pos.1 > 0 {
             self.cursor_pos.1 -= 1;
-            let line_len = self.lines[self.cursor_pos.1].len();
-            self.cursor_pos.0 = self.cursor_pos.0.min(line_len);
+            let line = &self.lines[self.cursor_pos.1];
+            self.cursor_pos.0 = line.floor_char_boundary(self.cursor_pos.0);
         }
     }
     pub fn cursor_down(&mut self) {
         if self.cursor_pos.1 < self.lines.len() - 1 {
             self.cursor_pos.1 += 1;
-            let line_len = self.lines[self.cursor_pos.1].len();
-            self.cursor_pos.0 = self.cursor_pos.0.min(line_len);
+            let line = &self.lines[self.cursor_pos.1];
+            self.cursor_pos.0 = line.floor_char_boundary(self.cursor_pos.0);
         }
     }
     fn is_on_last_line(&self) -> bool {
@@ -138,19 +164,86 @@ impl TextArea {
         self.lines.join("\n")
     }
     pub fn set_lines(&mut self, lines: String) {
-        self.lines = lines
+        let mut lines = lines
             .lines()
             .map(|l| l.to_string())
             .collect::<Vec<String>>();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        // views set their text on every frame, the search only reruns on a change
+        if lines == self.lines {
+            return;
+        }
+        self.edited();
+        self.lines = lines;
+        self.cursor_pos.1 = self.cursor_pos.1.min(self.lines.len() - 1);
+        let line = &self.lines[self.cursor_pos.1];
+        self.cursor_pos.0 = line.floor_char_boundary(self.cursor_pos.0);
     }
     pub fn add_to_lines(&mut self, s: String) {
+        self.edited();
         s.lines()
             .rev()
             .map(|l| l.to_string())
             .for_each(|l| self.lines.insert(self.cursor_pos.1, l));
         self.cursor_pos = (0, 0);
     }
+    /// Handles the search keys: ctrl-f opens the search bar, alt-n and alt-p move
+    /// between matches. Read-only areas also accept `/`, `n` and `N`.
+    /// Returns true when the event was consumed.
+    pub fn handle_search(&mut self, event: &Event) -> bool {
+        if let Some(search) = &mut self.search {
+            if search.is_editing() {
+                if search.update_query(event) {
+                    search.refresh(&self.lines, self.cursor_pos);
+                    self.jump_to_match();
+                } else {
+                    self.search = None;
+                }
+                return true;
+            }
+        }
+        let read_only = !self.mutable;
+        match (&event.modifier, &event.key) {
+            (Some(keyModifier::Control), Key::Char('f')) => self.start_search(),
+            (None, Key::Char('/')) if read_only => self.start_search(),
+            (Some(keyModifier::Alt), Key::Char('n')) => self.next_match(),
+            (None, Key::Char('n')) if read_only => self.next_match(),
+            (Some(keyModifier::Alt), Key::Char('p')) => self.previous_match(),
+            (None | Some(keyModifier::Shift), Key::Char('N')) if read_only => self.previous_match(),
+            (None, Key::Esc) if self.search.is_some() => self.search = None,
+            _ => return false,
+        }
+        true
+    }
+    fn start_search(&mut self) {
+        match &mut self.search {
+            Some(search) => search.edit(),
+            None => self.search = Some(Search::new()),
+        }
+    }
+    fn next_match(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.next();
+            self.jump_to_match();
+        }
+    }
+    fn previous_match(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.previous();
+            self.jump_to_match();
+        }
+    }
+    fn jump_to_match(&mut self) {
+        if let Some((line, start, _)) = self.search.as_ref().and_then(|s| s.current()) {
+            self.cursor_pos = (start, line);
+        }
+    }
     pub fn update(&mut self, event: &Event) {
+        if self.handle_search(event) {
+            return;
+        }
         if let Some(modif) = &event.modifier {
             match modif {
                 keyModifier::Control => match event.key {
@@ -197,6 +290,7 @@ impl TextArea {
     }
 
     pub fn prettify_json(&mut self) {
+        self.edited();
         self.lines = serde_json::to_string_pretty(
             &serde_json::from_str::<serde_json::Value>(&self.lines.clone().join("\n")).unwrap(),
         )
@@ -210,53 +304,111 @@ impl TextArea {
         self.cursor_pos.1 = self.lines.len() - 1;
         self.cursor_pos.0 = self.lines[self.cursor_pos.1].len();
     }
-    pub fn draw(&mut self, f: &mut Frame, rect: Rect) {
-        let mut chunks: Vec<Rect> = vec![Rect::default(), rect];
-        if !self.error.is_empty() {
+    /// Splits `rect` into the search bar, the error box and the text, as
+    /// they are drawn.
+    fn layout(&self, rect: Rect) -> (Option<Rect>, Option<Rect>, Rect) {
+        let mut rect = rect;
+        let mut search = None;
+        if self.search.is_some() {
             let chk =
-                Layout::vertical(vec![Constraint::Percentage(15), Constraint::Percentage(85)])
-                    .split(rect);
-            chunks = vec![chk[0], chk[1]];
+                Layout::vertical(vec![Constraint::Fill(1), Constraint::Length(3)]).split(rect);
+            rect = chk[0];
+            search = Some(chk[1]);
+        }
+        if self.error.is_empty() {
+            return (search, None, rect);
+        }
+        let chk = Layout::vertical(vec![Constraint::Percentage(15), Constraint::Percentage(85)])
+            .split(rect);
+        (search, Some(chk[0]), chk[1])
+    }
+    /// The first line shown in a text box `height` lines high: the view
+    /// follows the cursor.
+    fn first_line(&self, height: usize) -> usize {
+        if self.cursor_pos.1 > height {
+            self.cursor_pos.1 - height + 1
+        } else {
+            0
+        }
+    }
+    /// Moves the cursor to the character at `position`, for an area drawn in
+    /// `rect`.
+    pub fn click(&mut self, rect: Rect, position: Position) {
+        let (_, _, text) = self.layout(rect);
+        let inner = text.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return;
+        }
+        let width = (text.width as usize).saturating_sub(20).max(1);
+        let mut row = (position.y - inner.y) as usize;
+        let first = self.first_line((text.height as usize).saturating_sub(2));
+        for (idx, line) in self.lines.iter().enumerate().skip(first) {
+            let len = line.chars().count();
+            let rows = len.div_ceil(width).max(1);
+            if row < rows {
+                let column = row * width + (position.x - inner.x) as usize;
+                let byte = line
+                    .char_indices()
+                    .nth(column)
+                    .map_or(line.len(), |(b, _)| b);
+                self.cursor_pos = (byte, idx);
+                return;
+            }
+            row -= rows;
+        }
+        self.go_to_last_line();
+    }
+    /// Moves the cursor, and so the view, by a few lines.
+    pub fn scroll(&mut self, down: bool) {
+        for _ in 0..SCROLL_LINES {
+            if down {
+                self.cursor_down();
+            } else {
+                self.cursor_up();
+            }
+        }
+    }
+    pub fn draw(&mut self, f: &mut Frame, rect: Rect) {
+        let (search_rect, error_rect, text_rect) = self.layout(rect);
+        if let (Some(search), Some(search_rect)) = (&mut self.search, search_rect) {
+            if search.is_stale() {
+                search.refresh(&self.lines, self.cursor_pos);
+            }
+            search.draw(f, search_rect);
+        }
+        if let Some(error_rect) = error_rect {
             let paragraph = Paragraph::new(self.error.clone())
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(theme::get().error))
                 .block(default_block(Some("Error"), self.is_focused))
                 .wrap(Wrap { trim: false });
-            f.render_widget(paragraph, chunks[0]);
+            f.render_widget(paragraph, error_rect);
         }
 
-        let actual_height = chunks[1].height as usize - 2;
-        let actual_width = chunks[1].width as usize - 20;
-        let mut diff = 0;
-        if self.cursor_pos.1 >= actual_height + 1 {
-            diff = self.cursor_pos.1 - actual_height + 1;
-        };
+        let actual_height = text_rect.height as usize - 2;
+        let actual_width = text_rect.width as usize - 20;
+        let diff = self.first_line(actual_height);
         let mut end_show_idx = diff + actual_height;
         if self.lines.len() < end_show_idx {
             end_show_idx = self.lines.len();
         }
-        let show_lines = &self.lines[diff..end_show_idx].to_vec();
-        let paragraph = Paragraph::new(TextArea::style_cursor(
-            show_lines,
-            self.cursor_pos,
-            actual_width,
-        ))
-        .block(default_block(Some("Body"), self.is_focused))
-        .wrap(Wrap { trim: false });
+        let paragraph = Paragraph::new(self.styled_lines(diff, end_show_idx, actual_width))
+            .block(default_block(Some(&self.title), self.is_focused))
+            .wrap(Wrap { trim: false });
         //trace_dbg!(level: tracing::Level::INFO, self.cursor_pos);
         //trace_dbg!(level: tracing::Level::INFO, self.lines.clone());
-        f.render_widget(paragraph, chunks[1]);
+        f.render_widget(paragraph, text_rect);
 
         let mut state = ScrollbarState::default()
             .content_length(self.lines.len())
             .position(self.cursor_pos.1)
-            .viewport_content_length(chunks[1].height as usize);
+            .viewport_content_length(text_rect.height as usize);
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(None)
                 .end_symbol(None)
                 .track_symbol(Some("▒"))
                 .thumb_symbol("▐"),
-            chunks[1].inner(Margin {
+            text_rect.inner(Margin {
                 vertical: 1,
                 horizontal: 2,
             }),
@@ -264,71 +416,67 @@ impl TextArea {
         );
     }
 
-    fn style_cursor(
-        content: &Vec<String>,
-        cursor_position: (usize, usize),
-        line_width: usize,
-    ) -> Vec<Line> {
+    /// Renders the lines in `from..to`, wrapped at `line_width`, with syntax
+    /// and search matches highlighted and the cursor underlined.
+    fn styled_lines(&self, from: usize, to: usize, line_width: usize) -> Vec<Line<'_>> {
         let mut lines: Vec<Line> = Vec::new();
-        let wrapped_pos = Self::calculate_wrapped_position(cursor_position, line_width, content);
-
-        let mut current_wrapped_line = 0;
-        for line in content {
-            // Create chunks as owned Strings
-            let chunks: Vec<String> = line
-                .chars()
-                .collect::<Vec<char>>()
-                .chunks(line_width)
-                .map(|c| c.iter().collect::<String>())
-                .collect();
-
-            for chunk in chunks {
-                if current_wrapped_line == wrapped_pos.1 {
-                    let mut ll = Line::default();
-                    if wrapped_pos.0 >= chunk.len() {
-                        ll.push_span(Span::raw(chunk));
-                        ll.push_span(cursor_like_span(' '));
-                    } else {
-                        let (before, rest) = chunk.split_at(wrapped_pos.0);
-                        ll.push_span(Span::raw(before.to_string()));
-                        let cursor_char = rest.chars().next().unwrap_or(' ');
-                        ll.push_span(cursor_like_span(cursor_char));
-                        if wrapped_pos.0 + 1 < chunk.len() {
-                            ll.push_span(Span::raw(rest[1..].to_string()));
-                        }
-                    }
-                    lines.push(ll);
-                } else {
-                    lines.push(Line::raw(chunk));
+        let current = self.search.as_ref().and_then(|s| s.current());
+        for (idx, line) in self.lines[from..to].iter().enumerate() {
+            let idx = idx + from;
+            let chars = line.chars().collect::<Vec<char>>();
+            // byte offsets to the char indices the styles are kept by
+            let char_idx = |byte: usize| line[..byte].chars().count();
+            let mut styles = highlight_line(self.language, line);
+            let matches = self
+                .search
+                .as_ref()
+                .map_or(&[][..], |s| s.line_matches(idx));
+            for m in matches {
+                let style = search_match_style(Some(*m) == current);
+                for s in styles.iter_mut().take(char_idx(m.2)).skip(char_idx(m.1)) {
+                    *s = style;
                 }
-                current_wrapped_line += 1;
             }
-        }
-        lines
-    }
-
-    fn calculate_wrapped_position(
-        original_pos: (usize, usize),
-        width: usize,
-        lines: &[String],
-    ) -> (usize, usize) {
-        let mut wrapped_line = 0;
-        let mut char_count = original_pos.0;
-
-        for (idx, line) in lines.iter().enumerate() {
-            let num_wrapped_lines = (line.len() + width - 1) / width;
-
-            if idx < original_pos.1 {
-                wrapped_line += num_wrapped_lines;
+            let cursor = if idx == self.cursor_pos.1 {
+                Some(char_idx(self.cursor_pos.0))
             } else {
-                // Determine the exact wrapped position within the current line
-                wrapped_line += char_count / width;
-                char_count %= width;
-                break;
+                None
+            };
+            if chars.is_empty() {
+                let mut ll = Line::default();
+                if cursor.is_some() {
+                    ll.push_span(cursor_like_span(' '));
+                }
+                lines.push(ll);
+                continue;
+            }
+            let width = line_width.max(1);
+            for (chunk_idx, chunk) in chars.chunks(width).enumerate() {
+                let offset = chunk_idx * width;
+                let mut ll = Line::default();
+                let mut run = String::new();
+                let mut run_style = styles[offset];
+                for (i, c) in chunk.iter().enumerate() {
+                    let pos = offset + i;
+                    if cursor == Some(pos) {
+                        ll.push_span(Span::styled(std::mem::take(&mut run), run_style));
+                        ll.push_span(cursor_like_span(*c).patch_style(styles[pos]));
+                        continue;
+                    }
+                    if styles[pos] != run_style {
+                        ll.push_span(Span::styled(std::mem::take(&mut run), run_style));
+                        run_style = styles[pos];
+                    }
+                    run.push(*c);
+                }
+                ll.push_span(Span::styled(run, run_style));
+                if cursor == Some(chars.len()) && offset + chunk.len() == chars.len() {
+                    ll.push_span(cursor_like_span(' '));
+                }
+                lines.push(ll);
             }
         }
-
-        (char_count, wrapped_line)
+        lines
     }
     pub fn get_flattened_cursor_position(&self) -> usize {
         let mut pos = 0;
@@ -340,3 +488,28 @@ impl TextArea {
         pos + self.cursor_pos.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn setting_the_same_text_keeps_the_search() {
+        let mut text_area = TextArea::from("a b\na".to_string(), true, false);
+        text_area.handle_search(&Event {
+            modifier: None,
+            key: Key::Char('/'),
+        });
+        text_area.handle_search(&Event {
+            modifier: None,
+            key: Key::Char('a'),
+        });
+        let stale = |t: &TextArea| t.search.as_ref().unwrap().is_stale();
+        assert!(!stale(&text_area));
+        text_area.set_lines("a b\na".to_string());
+        assert!(!stale(&text_area));
+        text_area.set_lines("a b".to_string());
+        assert!(stale(&text_area));
+        assert_eq!(text_area.get_content(), "a b");
+    }
+}
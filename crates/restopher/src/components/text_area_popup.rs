@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+
+use crate::{
+    components::{default_block, text_area::TextArea},
+    keys::keys::{Event, Key, Modifier},
+    layout::centered_rect,
+};
+
+/// A popup holding a multi-line input, used to paste text such as a cURL
+/// command. The content is returned on ctrl-s.
+pub struct TextAreaPopupComponent {
+    title: String,
+    input: TextArea,
+    error: Option<String>,
+}
+
+impl TextAreaPopupComponent {
+    pub fn new(title: String) -> Self {
+        let mut input = TextArea::new();
+        input.set_title(format!(
+            "{} (ctrl-v: paste, ctrl-s: import, esc: close)",
+            title
+        ));
+        TextAreaPopupComponent {
+            title,
+            input,
+            error: None,
+        }
+    }
+    pub fn set_error(&mut self, error: Option<String>) {
+        self.error = error;
+    }
+    pub fn update(&mut self, event: &Event) -> (Option<String>, bool) {
+        match (&event.modifier, &event.key) {
+            (_, Key::Esc) => return (None, false),
+            (Some(Modifier::Control), Key::Char('s')) => {
+                return (Some(self.input.get_content()), true)
+            }
+            (_, Key::Space) => self.input.push(' '),
+            _ => self.input.update(event),
+        }
+        (None, true)
+    }
+    pub fn draw(&mut self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(70, 60, area);
+        f.render_widget(Clear, rect);
+        let mut rect = rect;
+        if let Some(error) = &self.error {
+            let chunks = Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).split(rect);
+            rect = chunks[0];
+            f.render_widget(
+                Paragraph::new(error.as_str()).block(default_block(Some(&self.title), false)),
+                chunks[1],
+            );
+        }
+        self.input.set_focus(true);
+        self.input.draw(f, rect);
+    }
+}
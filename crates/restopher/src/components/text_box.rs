@@ -86,6 +86,17 @@ impl TextBox {
             _ => {}
         }
     }
+    /// Moves the cursor to the character at `position`, for a box drawn with
+    /// borders in `rect`.
+    pub fn click(&mut self, rect: Rect, position: Position) {
+        let inner = rect.inner(Margin::new(1, 1));
+        if !inner.contains(position) {
+            return;
+        }
+        let offset = (position.y - inner.y) as usize * inner.width as usize
+            + (position.x - inner.x) as usize;
+        self.cursor_pos = offset.min(self.buffer.len());
+    }
     pub fn get_content_styled(&self, to_fill: &mut Vec<Span>, is_focused: bool) {
         to_fill.clear();
         let cont = self.get_content();
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::Style,
+    text::Span,
+    widgets::{Cell, Clear, Row, Table, TableState},
+    Frame,
+};
+
+use crate::{
+    components::default_block,
+    keys::keys::{Event, Key},
+    layout::centered_rect,
+    theme,
+    variables::Variable,
+};
+
+/// Shows the variables of the current request with their values and the
+/// scope each one comes from.
+pub struct VariablesComponent {
+    variables: Vec<Variable>,
+    selected: usize,
+}
+
+impl VariablesComponent {
+    pub fn new(variables: Vec<Variable>) -> Self {
+        VariablesComponent {
+            variables,
+            selected: 0,
+        }
+    }
+    /// Returns false when the inspector is closed.
+    pub fn update(&mut self, event: &Event) -> bool {
+        let len = self.variables.len();
+        match event.key {
+            Key::Esc | Key::Enter => return false,
+            Key::Down if len > 0 => self.selected = (self.selected + 1) % len,
+            Key::Up if len > 0 => self.selected = (self.selected + len - 1) % len,
+            _ => (),
+        }
+        true
+    }
+    pub fn draw(&self, f: &mut Frame, area: Rect) {
+        let rect = centered_rect(80, 60, area);
+        f.render_widget(Clear, rect);
+        let muted = Style::default().fg(theme::get().muted);
+        let rows = self
+            .variables
+            .iter()
+            .map(|v| {
+                let overrides = v
+                    .overrides
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                Row::new(vec![
+                    Cell::from(v.name.clone()),
+                    Cell::from(v.value.clone()),
+                    Cell::from(v.scope.to_string()),
+                    Cell::from(Span::styled(overrides, muted)),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        let title = format!("{} resolved variables (esc: close)", self.variables.len());
+        let table = Table::new(
+            rows,
+            vec![
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(vec!["Name", "Value", "Scope", "Overrides"]).style(muted))
+        .block(default_block(Some(&title), true))
+        .row_highlight_style(
+            Style::default()
+                .fg(theme::get().row_fg)
+                .bg(theme::get().row_bg),
+        );
+        f.render_stateful_widget(
+            table,
+            rect,
+            &mut TableState::new().with_selected(self.selected),
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::keys::keymap::KeyConfig;
+use crate::logger::project_directory;
+use crate::{COLLECTION_PATH, ENV_PATH, EXPORT_PATH, HISTORY_PATH, THEME_PATH};
+
+/// Overrides the data directory, like `--data-dir`. The config file is read
+/// from that directory as well.
+pub(crate) const HOME_ENV: &str = "RESTOPHER_HOME";
+pub(crate) const CONFIG_FILE: &str = "config.toml";
+
+/// Written on first run so the available preferences are easy to find.
+const DEFAULT_CONFIG: &str = r#"# restopher preferences
+
+# Where collections, environments, history and exports are stored.
+# `--data-dir` and RESTOPHER_HOME take precedence.
+# data_dir = "/path/to/restopher"
+
+# Number of responses kept per saved request.
+history_limit = 20
+
+# Seconds to wait for a response, 0 waits forever.
+request_timeout = 30
+
+# Environment selected at startup.
+# default_environment = "default"
+
+# Colors: "dark", "light", "high-contrast" (colorblind safe) or the name of a
+# file in the themes directory, such as "mine" for themes/mine.toml.
+theme = "dark"
+
+# Key bindings, as in `send_request = "ctrl-p"` or a list of keys; "none"
+# removes a binding. Top level bindings apply to every window, the
+# [keys.main], [keys.environments] and [keys.collections] tables override
+# them for one window. Conflicts are reported at startup.
+[keys]
+# open_collections = "alt-b"
+# save_request = ["alt-s", "ctrl-g"]
+# next_environment = "alt-v"   # actions listed in the command palette (alt-x)
+
+# [keys.collections]
+# delete = "ctrl-x"
+
+# Variables available as {{name}} whatever the environment. The defaults.yaml
+# of collection folders, the environment and the request override them.
+# [variables]
+# user_agent = "restopher"
+"#;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Global preferences, read from `config.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Preferences {
+    pub data_dir: Option<PathBuf>,
+    pub history_limit: usize,
+    /// In seconds, 0 disables the timeout.
+    pub request_timeout: u64,
+    pub default_environment: Option<String>,
+    pub theme: String,
+    pub keys: KeyConfig,
+    /// Global variables, below every other scope.
+    pub variables: BTreeMap<String, String>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            data_dir: None,
+            history_limit: 20,
+            request_timeout: 30,
+            default_environment: None,
+            theme: "dark".to_string(),
+            keys: KeyConfig::default(),
+            variables: BTreeMap::new(),
+        }
+    }
+}
+
+impl Preferences {
+    pub fn request_timeout(&self) -> Option<Duration> {
+        (self.request_timeout > 0).then(|| Duration::from_secs(self.request_timeout))
+    }
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub data_dir: String,
+    pub config_file: PathBuf,
+    pub preferences: Preferences,
+}
+
+impl Config {
+    /// Resolves where restopher keeps its files. The data directory comes
+    /// from `data_dir` (the `--data-dir` flag), then `RESTOPHER_HOME`, then
+    /// the config file, then the XDG data directory. The config file lives in
+    /// the data directory when one of the first two is given, and in the XDG
+    /// config directory otherwise.
+    fn resolve(data_dir: Option<PathBuf>) -> Result<Self, String> {
+        let home = data_dir.or_else(|| std::env::var_os(HOME_ENV).map(PathBuf::from));
+        let dirs = project_directory();
+        let config_file = match (&home, &dirs) {
+            (Some(home), _) => home.join(CONFIG_FILE),
+            (None, Some(dirs)) => dirs.config_dir().join(CONFIG_FILE),
+            (None, None) => PathBuf::from(".").join(CONFIG_FILE),
+        };
+        let preferences = match fs::read_to_string(&config_file) {
+            Ok(content) => {
+                toml::from_str(&content).map_err(|e| format!("{}: {}", config_file.display(), e))?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Preferences::default(),
+            Err(e) => return Err(format!("{}: {}", config_file.display(), e)),
+        };
+        let data_dir = home
+            .or_else(|| preferences.data_dir.clone())
+            .or_else(|| dirs.map(|d| d.data_dir().to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from(".").join(".restopher"));
+        Ok(Config {
+            data_dir: data_dir.to_string_lossy().trim_end_matches('/').to_string(),
+            config_file,
+            preferences,
+        })
+    }
+    /// Creates the directories restopher expects, and a commented config
+    /// file, when they do not exist yet.
+    fn create_layout(&self) -> Result<(), std::io::Error> {
+        for dir in [
+            COLLECTION_PATH,
+            ENV_PATH,
+            HISTORY_PATH,
+            EXPORT_PATH,
+            THEME_PATH,
+        ] {
+            let path = Path::new(&self.data_dir).join(dir);
+            // environments may also be a single file
+            if !path.exists() {
+                fs::create_dir_all(path)?;
+            }
+        }
+        if !self.config_file.exists() {
+            if let Some(parent) = self.config_file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&self.config_file, DEFAULT_CONFIG)?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the configuration and prepares the data directory. Called once at
+/// startup, before anything reads `get`.
+pub fn init(data_dir: Option<PathBuf>) -> Result<&'static Config, String> {
+    let config = Config::resolve(data_dir)?;
+    config
+        .create_layout()
+        .map_err(|e| format!("{}: {}", config.data_dir, e))?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+pub fn get() -> &'static Config {
+    CONFIG
+        .get()
+        .expect("the configuration is loaded at startup")
+}
+
+pub fn data_dir() -> &'static str {
+    &get().data_dir
+}
+
+pub fn preferences() -> &'static Preferences {
+    &get().preferences
+}
+
+pub const USAGE: &str = "Usage: restopher [--data-dir <path>]
+
+Options:
+  --data-dir <path>  directory for collections, environments and config.toml,
+                     defaults to $RESTOPHER_HOME or the XDG data directory
+  -h, --help         print this help";
+
+#[derive(Default)]
+pub struct Args {
+    pub data_dir: Option<PathBuf>,
+    pub help: bool,
+}
+
+/// Reads the command line, without the program name.
+pub fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => parsed.help = true,
+            "--data-dir" => match args.next() {
+                Some(path) => parsed.data_dir = Some(PathBuf::from(path)),
+                None => return Err("--data-dir needs a path".to_string()),
+            },
+            _ => match arg.strip_prefix("--data-dir=") {
+                Some(path) => parsed.data_dir = Some(PathBuf::from(path)),
+                None => return Err(format!("unknown argument {}, see --help", arg)),
+            },
+        }
+    }
+    Ok(parsed)
+}
+
+/// Loads a configuration rooted in a temporary directory, for tests of code
+/// that reads `get`.
+#[cfg(test)]
+pub(crate) fn init_for_tests() -> &'static Config {
+    let root = std::env::temp_dir().join(format!("restopher-config-{}", std::process::id()));
+    CONFIG.get_or_init(|| Config::resolve(Some(root)).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("restopher-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn parses_the_command_line() {
+        let parsed = args(&["--data-dir", "/tmp/a"]).unwrap();
+        assert_eq!(parsed.data_dir, Some(PathBuf::from("/tmp/a")));
+        assert!(!parsed.help);
+        let parsed = args(&["--data-dir=/tmp/b", "-h"]).unwrap();
+        assert_eq!(parsed.data_dir, Some(PathBuf::from("/tmp/b")));
+        assert!(parsed.help);
+        assert!(args(&["--data-dir"]).is_err());
+        assert!(args(&["--verbose"]).is_err());
+        assert!(args(&[]).unwrap().data_dir.is_none());
+    }
+
+    #[test]
+    fn reads_preferences_from_the_data_dir() {
+        let root = temp_root("config");
+        let config = Config::resolve(Some(root.join("data/"))).unwrap();
+        assert_eq!(config.data_dir, root.join("data").to_string_lossy());
+        assert_eq!(config.config_file, root.join("data").join(CONFIG_FILE));
+        assert_eq!(config.preferences.history_limit, 20);
+
+        config.create_layout().unwrap();
+        assert!(root.join("data").join(COLLECTION_PATH).is_dir());
+        assert!(root.join("data").join(HISTORY_PATH).is_dir());
+        // the commented default file parses to the defaults
+        let config = Config::resolve(Some(root.join("data"))).unwrap();
+        assert_eq!(config.preferences.request_timeout, 30);
+
+        fs::write(
+            root.join("data").join(CONFIG_FILE),
+            "history_limit = 5\nrequest_timeout = 0\ndefault_environment = \"prod\"\n",
+        )
+        .unwrap();
+        let config = Config::resolve(Some(root.join("data"))).unwrap();
+        assert_eq!(config.preferences.history_limit, 5);
+        assert_eq!(config.preferences.request_timeout(), None);
+        assert_eq!(
+            config.preferences.default_environment.as_deref(),
+            Some("prod")
+        );
+
+        fs::write(root.join("data").join(CONFIG_FILE), "histry_limit = 5\n").unwrap();
+        assert!(Config::resolve(Some(root.join("data"))).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn timeout_in_seconds() {
+        assert_eq!(
+            Preferences::default().request_timeout(),
+            Some(Duration::from_secs(30))
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+
+use crate::app::Error;
+use crate::config::data_dir;
+use crate::http_file;
+use crate::variables::Scope;
+use crate::COLLECTION_PATH;
+
+/// The file of a collection folder holding its defaults.
+pub const DEFAULTS_FILE: &str = "defaults.yaml";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Auth {
+    Basic { user: String, password: String },
+    Bearer(String),
+}
+
+impl Auth {
+    /// The value of the `Authorization` header.
+    fn header(&self) -> String {
+        match self {
+            Auth::Basic { user, password } => {
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{}:{}", user, password))
+                )
+            }
+            Auth::Bearer(token) => format!("Bearer {}", token),
+        }
+    }
+}
+
+/// What a collection folder passes down to the requests beneath it, read
+/// from its `defaults.yaml`:
+///
+/// ```yaml
+/// base_url: https://api.example.com
+/// headers:
+///   Accept: application/json
+/// params:
+///   api-version: "2"
+/// auth:
+///   bearer: "{{token}}"
+/// variables:
+///   token: secret
+/// timeout: 10
+/// ```
+///
+/// The nearest folder wins field by field, and a request overrides any
+/// header or param with its own active ones.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FolderDefaults {
+    /// Put in front of addresses without a scheme, such as `/users`.
+    pub base_url: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub params: BTreeMap<String, String>,
+    /// Written as a map, `bearer: <token>` or `basic: {user, password}`.
+    #[serde(with = "serde_yaml::with::singleton_map")]
+    pub auth: Option<Auth>,
+    /// Below the environment's variables, which take precedence.
+    pub variables: BTreeMap<String, String>,
+    /// In seconds, in place of the `request_timeout` preference. 0 keeps the
+    /// preference.
+    pub timeout: Option<u64>,
+    /// The variables of each folder with a defaults file, from the root down.
+    #[serde(skip)]
+    pub variable_scopes: Vec<(Scope, BTreeMap<String, String>)>,
+}
+
+impl FolderDefaults {
+    /// Merges the defaults of the folders from the collection root down to
+    /// the request saved at `location`.
+    pub fn resolve(location: &str) -> Result<Self, Error> {
+        let root = PathBuf::from(format!("{}/{}", data_dir(), COLLECTION_PATH));
+        let (file, _) = http_file::split_location(location);
+        let mut folders: Vec<&Path> = Path::new(file)
+            .ancestors()
+            .skip(1)
+            .take_while(|folder| folder.starts_with(&root))
+            .collect();
+        folders.reverse();
+        let mut defaults = FolderDefaults::default();
+        for folder in folders {
+            let path = folder.join(DEFAULTS_FILE);
+            match fs::read_to_string(&path) {
+                Ok(content) => defaults.merge(
+                    folder_scope(&root, folder),
+                    serde_yaml::from_str(&content)
+                        .map_err(|e| Error::InputErr(format!("{}: {}", path.display(), e)))?,
+                ),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+                Err(e) => return Err(Error::FileOperationsErr(e)),
+            }
+        }
+        Ok(defaults)
+    }
+    fn merge(&mut self, scope: Scope, nearer: FolderDefaults) {
+        self.base_url = nearer.base_url.or(self.base_url.take());
+        for (key, value) in nearer.headers {
+            // header names are not case sensitive
+            self.headers.retain(|k, _| !k.eq_ignore_ascii_case(&key));
+            self.headers.insert(key, value);
+        }
+        self.params.extend(nearer.params);
+        self.auth = nearer.auth.or(self.auth.take());
+        self.variable_scopes.push((scope, nearer.variables.clone()));
+        self.variables.extend(nearer.variables);
+        self.timeout = nearer.timeout.or(self.timeout);
+    }
+    /// The inherited headers, with `Authorization` made from `auth` unless a
+    /// folder sets it directly.
+    pub fn headers(&self) -> Vec<(String, String)> {
+        let mut headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if let Some(auth) = &self.auth {
+            if !headers
+                .iter()
+                .any(|(k, _)| k.eq_ignore_ascii_case("authorization"))
+            {
+                headers.push(("Authorization".to_string(), auth.header()));
+            }
+        }
+        headers
+    }
+    pub fn params(&self) -> Vec<(String, String)> {
+        self.params
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+    /// `address` on top of `base_url`, when it has no scheme of its own and
+    /// does not start with a variable, which may hold a full URL.
+    pub fn address(&self, address: String) -> String {
+        match &self.base_url {
+            Some(base) if !address.contains("://") && !address.starts_with("{{") => format!(
+                "{}/{}",
+                base.trim_end_matches('/'),
+                address.trim_start_matches('/')
+            ),
+            _ => address,
+        }
+    }
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.filter(|t| *t > 0).map(Duration::from_secs)
+    }
+}
+
+/// Top level folders are collections; the root and nested folders are named
+/// by their path under the root.
+fn folder_scope(root: &Path, folder: &Path) -> Scope {
+    let relative = folder.strip_prefix(root).unwrap_or(folder);
+    match relative.components().count() {
+        0 => Scope::Folder("/".to_string()),
+        1 => Scope::Collection(relative.to_string_lossy().to_string()),
+        _ => Scope::Folder(relative.to_string_lossy().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults(yaml: &str) -> FolderDefaults {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn nearest_folder_wins() {
+        let mut merged = defaults(
+            r#"
+base_url: https://api.example.com
+headers:
+  Accept: application/json
+  X-Team: core
+params:
+  api-version: "1"
+auth:
+  bearer: "{{token}}"
+timeout: 10
+"#,
+        );
+        merged.merge(
+            Scope::Folder("/users".to_string()),
+            defaults(
+                r#"
+base_url: https://eu.example.com/v2
+headers:
+  accept: text/plain
+params:
+  page: "1"
+timeout: 0
+"#,
+            ),
+        );
+        assert_eq!(
+            merged.base_url.as_deref(),
+            Some("https://eu.example.com/v2")
+        );
+        assert_eq!(
+            merged.headers(),
+            vec![
+                ("X-Team".to_string(), "core".to_string()),
+                ("accept".to_string(), "text/plain".to_string()),
+                ("Authorization".to_string(), "Bearer {{token}}".to_string()),
+            ]
+        );
+        assert_eq!(
+            merged.params(),
+            vec![
+                ("api-version".to_string(), "1".to_string()),
+                ("page".to_string(), "1".to_string()),
+            ]
+        );
+        // 0 falls back to the preference
+        assert_eq!(merged.timeout(), None);
+        merged.merge(Scope::Folder("/users".to_string()), defaults("timeout: 5"));
+        assert_eq!(merged.timeout(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn authorization_header_beats_auth() {
+        let merged = defaults(
+            r#"
+headers:
+  authorization: Token abc
+auth:
+  basic:
+    user: ada
+    password: secret
+"#,
+        );
+        assert_eq!(
+            merged.headers(),
+            vec![("authorization".to_string(), "Token abc".to_string())]
+        );
+        let basic = defaults("auth:\n  basic:\n    user: ada\n    password: secret\n");
+        assert_eq!(
+            basic.headers(),
+            vec![(
+                "Authorization".to_string(),
+                "Basic YWRhOnNlY3JldA==".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn addresses_without_a_scheme_use_the_base_url() {
+        let base = defaults("base_url: https://api.example.com/v1/");
+        assert_eq!(
+            base.address("/users".to_string()),
+            "https://api.example.com/v1/users"
+        );
+        assert_eq!(
+            base.address("http://localhost/users".to_string()),
+            "http://localhost/users"
+        );
+        assert_eq!(base.address("{{host}}/users".to_string()), "{{host}}/users");
+        assert_eq!(
+            FolderDefaults::default().address("/users".to_string()),
+            "/users"
+        );
+        assert!(serde_yaml::from_str::<FolderDefaults>("base: x").is_err());
+    }
+
+    #[test]
+    fn resolves_from_the_collection_root_down() {
+        let root = Path::new(&crate::config::init_for_tests().data_dir)
+            .join(COLLECTION_PATH)
+            .join("defaults-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("users")).unwrap();
+        fs::write(
+            root.join(DEFAULTS_FILE),
+            "base_url: https://api.example.com\nvariables:\n  token: outer\n  page: '1'\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("users").join(DEFAULTS_FILE),
+            "variables:\n  token: inner\n",
+        )
+        .unwrap();
+
+        let location = root.join("users/list.http");
+        let resolved =
+            FolderDefaults::resolve(&http_file::location(&location.to_string_lossy(), 2)).unwrap();
+        assert_eq!(
+            resolved.base_url.as_deref(),
+            Some("https://api.example.com")
+        );
+        assert_eq!(
+            resolved
+                .variable_scopes
+                .iter()
+                .map(|(scope, _)| scope.clone())
+                .collect::<Vec<Scope>>(),
+            vec![
+                Scope::Collection("defaults-test".to_string()),
+                Scope::Folder("defaults-test/users".to_string()),
+            ]
+        );
+        assert_eq!(
+            resolved.variables,
+            BTreeMap::from([
+                ("page".to_string(), "1".to_string()),
+                ("token".to_string(), "inner".to_string()),
+            ])
+        );
+        let resolved = FolderDefaults::resolve(&root.join("get.rph").to_string_lossy()).unwrap();
+        assert_eq!(
+            resolved.variables.get("token").map(String::as_str),
+            Some("outer")
+        );
+
+        fs::write(root.join("users").join(DEFAULTS_FILE), "timeout: soon\n").unwrap();
+        assert!(FolderDefaults::resolve(&location.to_string_lossy()).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
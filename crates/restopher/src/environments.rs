@@ -6,7 +6,7 @@ use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
     prelude::*,
-    style::{Color, Style},
+    style::Style,
     widgets::{Cell, Clear, List, ListState, Row, Table, TableState},
     Frame,
 };
@@ -16,7 +16,10 @@ use std::fs::{self, File};
 use std::io::{BufReader, Read};
 use std::str::from_utf8;
 
+use crate::keys::keymap::{self, Action};
 use crate::keys::keys::{Event, Key, Modifier::*};
+use crate::main_windows::MainWindows;
+use crate::theme;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
@@ -103,31 +106,29 @@ impl TempEnv {
             };
             return (None, true);
         };
-        if matches!(event.key, Key::Esc) {
+        let action = keymap::get().action(&MainWindows::Environments, event);
+        if action == Some(Action::Close) {
             self.sync_envs();
             return (Some(self.all_envs.clone()), false);
         }
         match self.environment_sub_selection {
             EnvironmentSubSection::Name => {
-                match event.modifier {
-                    Some(Control) => match event.key {
-                        Key::Char('n') => {
-                            self.popup = Some(PopUpComponent::new(
-                                "New Environment".to_string(),
-                                "Enter the name of the new environment".to_string(),
-                            ));
-                            return (None, true);
-                        }
-                        Key::Char('d') => {
-                            self.all_envs.remove(self.selected);
-                            if self.selected > 0 {
-                                self.selected -= 1;
-                            }
-                            self.current_kvs = into(self.all_envs[self.selected].envs.clone());
-                            return (None, true);
+                match action {
+                    Some(Action::Create) => {
+                        self.popup = Some(PopUpComponent::new(
+                            "New Environment".to_string(),
+                            "Enter the name of the new environment".to_string(),
+                        ));
+                        return (None, true);
+                    }
+                    Some(Action::Delete) => {
+                        self.all_envs.remove(self.selected);
+                        if self.selected > 0 {
+                            self.selected -= 1;
                         }
-                        _ => (),
-                    },
+                        self.current_kvs = into(self.all_envs[self.selected].envs.clone());
+                        return (None, true);
+                    }
                     _ => (),
                 }
                 match event.key {
@@ -163,17 +164,20 @@ impl TempEnv {
                 }
             }
             EnvironmentSubSection::KVs => {
+                match action {
+                    Some(Action::Create) => {
+                        self.create_new_kv();
+                        return (None, true);
+                    }
+                    Some(Action::Delete) => {
+                        self.remove_kv();
+                        return (None, true);
+                    }
+                    _ => (),
+                }
                 if let Some(modifier) = &event.modifier {
                     match modifier {
                         Control => match event.key {
-                            Key::Char('n') => {
-                                self.create_new_kv();
-                                return (None, true);
-                            }
-                            Key::Char('d') => {
-                                self.remove_kv();
-                                return (None, true);
-                            }
                             Key::Char('l') | Key::Char('h') | Key::Right | Key::Left => {
                                 self.current_kvs[self.selected_kv].change_active();
                                 return (None, true);
@@ -240,10 +244,50 @@ impl TempEnv {
             self.selected_kv -= 1
         };
     }
+    /// Selects the clicked environment or variable.
+    pub fn click(&mut self, rect: Rect, position: Position) {
+        if self.popup.is_some() {
+            return;
+        }
+        let chunks = layout(rect);
+        // rows start below the border, and below the column names in the table
+        if chunks[0].contains(position) {
+            self.environment_sub_selection = EnvironmentSubSection::Name;
+            if let Some(idx) = row_at(chunks[0], position, 1, self.all_envs.len()) {
+                self.sync_envs();
+                self.selected = idx;
+                self.selected_kv = 0;
+                self.current_kvs = into(self.all_envs[self.selected].envs.clone());
+            }
+        } else if chunks[1].contains(position) {
+            self.environment_sub_selection = EnvironmentSubSection::KVs;
+            if let Some(idx) = row_at(chunks[1], position, 2, self.current_kvs.len()) {
+                self.selected_kv = idx;
+            }
+        }
+    }
+    /// Moves through the list under the pointer, like the arrow keys.
+    pub fn scroll(&mut self, rect: Rect, position: Position, down: bool) {
+        if self.popup.is_some() {
+            return;
+        }
+        let chunks = layout(rect);
+        if chunks[0].contains(position) {
+            self.environment_sub_selection = EnvironmentSubSection::Name;
+        } else if chunks[1].contains(position) {
+            self.environment_sub_selection = EnvironmentSubSection::KVs;
+        } else {
+            return;
+        }
+        let key = if down { Key::Down } else { Key::Up };
+        self.update(&Event {
+            modifier: None,
+            key,
+        });
+    }
     pub fn draw(&mut self, f: &mut Frame, rect: Rect) {
         let title = format!("Environment: {}", self.all_envs[self.selected].name);
-        let chunks = Layout::horizontal(&[Constraint::Percentage(20), Constraint::Percentage(80)])
-            .split(rect);
+        let chunks = layout(rect);
         let vertical_scroll = 0; // from app state
         let mut scrollbar_state =
             ScrollbarState::new(self.all_envs.len()).position(vertical_scroll);
@@ -260,7 +304,7 @@ impl TempEnv {
                     Some("Environments"),
                     matches!(self.environment_sub_selection, EnvironmentSubSection::Name),
                 ))
-                .highlight_style(Style::default().fg(Color::Green))
+                .highlight_style(Style::default().fg(theme::get().selected))
                 .highlight_symbol(">>"),
             chunks[0],
             &mut list_state,
@@ -286,10 +330,10 @@ impl TempEnv {
                             Cell::new(Line::from(item.get_key_spans())),
                             Cell::new(Line::from(item.get_value_spans())),
                         ])
-                        .style(Style::default().fg(Color::LightBlue))
+                        .style(Style::default().fg(theme::get().label))
                     } else {
                         Row::new(vec![Cell::new(item.get_key()), Cell::new(item.get_value())])
-                            .style(Style::default().fg(Color::LightBlue))
+                            .style(Style::default().fg(theme::get().label))
                     }
                 })
                 .collect::<Vec<Row>>(),
@@ -300,7 +344,11 @@ impl TempEnv {
             Some(&title),
             matches!(self.environment_sub_selection, EnvironmentSubSection::KVs),
         ))
-        .highlight_style(Style::new().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .highlight_style(
+            Style::new()
+                .fg(theme::get().selected)
+                .add_modifier(Modifier::BOLD),
+        )
         .highlight_symbol(">>");
 
         let mut scrollbar_state =
@@ -326,6 +374,22 @@ impl TempEnv {
     }
 }
 
+/// The environments and the variables of the selected one.
+fn layout(rect: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::horizontal(&[Constraint::Percentage(20), Constraint::Percentage(80)]).split(rect)
+}
+
+/// The index of the row at `position` in a list of `len` rows drawn in
+/// `rect`, the first one `skip` lines below its top.
+fn row_at(rect: Rect, position: Position, skip: u16, len: usize) -> Option<usize> {
+    let first_row = rect.y + skip;
+    if !rect.contains(position) || position.y < first_row {
+        return None;
+    }
+    let idx = (position.y - first_row) as usize;
+    (idx < len).then_some(idx)
+}
+
 fn into(data: HashMap<String, String>) -> Vec<KV> {
     let mut result = Vec::new();
     for (key, value) in data {
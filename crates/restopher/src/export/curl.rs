@@ -0,0 +1,168 @@
+use super::RequestParts;
+
+/// Renders the request as a POSIX shell `curl` command, one option per line.
+pub fn to_curl(parts: &RequestParts) -> String {
+    let mut args = Vec::new();
+    // -X HEAD makes curl wait for a body that never comes
+    if parts.method == "HEAD" {
+        args.push("-I".to_string());
+    } else if parts.method != "GET" || parts.body.is_some() || !parts.form.is_empty() {
+        args.push(format!("-X {}", parts.method));
+    }
+    args.push(quote(&parts.url));
+    for (key, value) in &parts.headers {
+        args.push(format!("-H {}", quote(&format!("{}: {}", key, value))));
+    }
+    if let Some((user, password)) = &parts.basic_auth {
+        args.push(format!("-u {}", quote(&format!("{}:{}", user, password))));
+    }
+    if let Some(body) = &parts.body {
+        args.push(format!("--data-raw {}", quote(body)));
+    }
+    for (key, value) in &parts.form {
+        args.push(format!("-F {}", quote(&format!("{}={}", key, value))));
+    }
+    format!("curl {}", args.join(" \\\n  "))
+}
+
+/// Quotes `s` for a POSIX shell. Single quotes keep everything literal; an
+/// embedded single quote is closed, escaped and reopened.
+pub fn quote(s: &str) -> String {
+    let safe = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:,=@%+".contains(c));
+    if safe {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::defaults::FolderDefaults;
+    use crate::request::{Body, BodyKind, HttpVerb, Mode, Request};
+
+    #[test]
+    fn quotes_for_the_shell() {
+        assert_eq!(
+            quote("https://example.com/a?b=c"),
+            "'https://example.com/a?b=c'"
+        );
+        assert_eq!(quote("plain-word_1.0"), "plain-word_1.0");
+        assert_eq!(quote(""), "''");
+        assert_eq!(quote("it's"), "'it'\\''s'");
+        assert_eq!(quote("a\nb"), "'a\nb'");
+        assert_eq!(quote("$HOME `id` \"x\""), "'$HOME `id` \"x\"'");
+    }
+
+    #[test]
+    fn renders_a_json_request() {
+        let mut req = Request::new(Mode::REST);
+        // GET is followed by POST
+        req.verb_down();
+        req.set_address("https://example.com/users".to_string());
+        req.add_to_param("q".to_string(), "a b".to_string(), true);
+        req.add_to_param("off".to_string(), "1".to_string(), false);
+        req.add_to_header("X-Name".to_string(), "{{name}}".to_string(), true);
+        req.add_to_header(
+            "Authorization".to_string(),
+            "Basic dXNlcjpwYXNz".to_string(),
+            true,
+        );
+        req.set_body(Body {
+            kind: BodyKind::JSON,
+            payload: Some("{\"it's\": 1}".to_string()),
+        });
+        let parts = RequestParts::from_request(&req, &FolderDefaults::default(), &|s| {
+            s.replace("{{name}}", "ada")
+        });
+        assert_eq!(
+            to_curl(&parts),
+            "curl -X POST \\\n  \
+             'https://example.com/users?q=a%20b' \\\n  \
+             -H 'X-Name: ada' \\\n  \
+             -H 'Content-Type: application/json' \\\n  \
+             -u user:pass \\\n  \
+             --data-raw '{\"it'\\''s\": 1}'"
+        );
+    }
+
+    #[test]
+    fn plain_get_has_no_method() {
+        let mut req = Request::new(Mode::REST);
+        req.set_address("https://example.com".to_string());
+        req.set_body(Body {
+            kind: BodyKind::JSON,
+            payload: Some("{}".to_string()),
+        });
+        // GET does not send the body
+        assert_eq!(
+            to_curl(&RequestParts::from_request(
+                &req,
+                &FolderDefaults::default(),
+                &|s| s
+            )),
+            "curl https://example.com"
+        );
+    }
+
+    #[test]
+    fn head_asks_for_the_headers_only() {
+        let mut req = Request::new(Mode::REST);
+        req.set_address("https://example.com".to_string());
+        req.set_verb(HttpVerb::HEAD);
+        assert_eq!(
+            to_curl(&RequestParts::from_request(
+                &req,
+                &FolderDefaults::default(),
+                &|s| s
+            )),
+            "curl -I \\\n  https://example.com"
+        );
+    }
+
+    #[test]
+    fn renders_a_multipart_request() {
+        let mut req = Request::new(Mode::REST);
+        req.verb_down();
+        req.set_address("https://example.com/upload".to_string());
+        req.set_body(Body {
+            kind: BodyKind::MULTIPART,
+            payload: Some("title=my report\nfile=@/tmp/report.pdf".to_string()),
+        });
+        assert_eq!(
+            to_curl(&RequestParts::from_request(
+                &req,
+                &FolderDefaults::default(),
+                &|s| s
+            )),
+            "curl -X POST \\\n  \
+             https://example.com/upload \\\n  \
+             -F 'title=my report' \\\n  \
+             -F file=@/tmp/report.pdf"
+        );
+    }
+
+    #[test]
+    fn inherits_folder_defaults() {
+        let mut req = Request::new(Mode::REST);
+        req.set_address("/users".to_string());
+        req.add_to_header("accept".to_string(), "text/csv".to_string(), true);
+        req.add_to_param("page".to_string(), "2".to_string(), true);
+        let inherited = FolderDefaults {
+            base_url: Some("https://api.example.com/".to_string()),
+            headers: [("Accept".to_string(), "application/json".to_string())].into(),
+            params: [
+                ("page".to_string(), "1".to_string()),
+                ("v".to_string(), "2".to_string()),
+            ]
+            .into(),
+            ..FolderDefaults::default()
+        };
+        assert_eq!(
+            to_curl(&RequestParts::from_request(&req, &inherited, &|s| s)),
+            "curl 'https://api.example.com/users?v=2&page=2' \\\n  -H 'accept: text/csv'"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+mod curl;
+mod openapi;
+mod postman;
+mod snippet;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::defaults::FolderDefaults;
+use crate::request::{BodyKind, Request};
+
+pub use openapi::to_openapi;
+pub use postman::{to_postman, to_postman_environment};
+pub use snippet::{to_snippet, Language};
+
+/// A collection folder loaded for export. A `.http` file is a folder of its
+/// own, as in the collection tree.
+pub struct Folder {
+    pub name: String,
+    pub folders: Vec<Folder>,
+    pub requests: Vec<Request>,
+}
+
+impl Folder {
+    /// Requests of this folder and its sub folders, with the path of folder
+    /// names leading to each, starting below this folder.
+    pub fn flatten(&self) -> Vec<(Vec<&str>, &Request)> {
+        let mut requests = self
+            .requests
+            .iter()
+            .map(|req| (Vec::new(), req))
+            .collect::<Vec<(Vec<&str>, &Request)>>();
+        for sub in &self.folders {
+            for (mut path, req) in sub.flatten() {
+                path.insert(0, &sub.name);
+                requests.push((path, req));
+            }
+        }
+        requests
+    }
+}
+
+/// A request flattened into what is actually sent over the wire, the shared
+/// input of every exporter.
+#[derive(Debug, Clone)]
+pub struct RequestParts {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// Multipart fields, sent instead of `body`.
+    pub form: Vec<(String, String)>,
+    pub basic_auth: Option<(String, String)>,
+}
+
+impl RequestParts {
+    /// Builds the parts of `req` with what its folders pass down, as it is
+    /// sent. Every user supplied string goes through `resolve` so callers
+    /// choose whether variables are substituted.
+    pub fn from_request(
+        req: &Request,
+        inherited: &FolderDefaults,
+        resolve: &dyn Fn(String) -> String,
+    ) -> Self {
+        let own_params = req
+            .params()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|p| p.2 && !p.0.is_empty())
+            .collect::<Vec<(String, String, bool)>>();
+        let mut params = inherited
+            .params()
+            .into_iter()
+            .filter(|(k, _)| !own_params.iter().any(|p| p.0 == *k))
+            .collect::<Vec<(String, String)>>();
+        params.extend(own_params.into_iter().map(|p| (p.0, p.1)));
+        let params = params
+            .into_iter()
+            .map(|(k, v)| format!("{}={}", encode(&resolve(k)), encode(&resolve(v))))
+            .collect::<Vec<String>>();
+        // as when sending, the address is resolved before the base URL is added
+        let mut url = resolve(inherited.address(resolve(req.address())));
+        if !params.is_empty() {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(&params.join("&"));
+        }
+        let own_headers = req
+            .headers()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|h| h.2 && !h.0.is_empty())
+            .collect::<Vec<(String, String, bool)>>();
+        let mut sent = inherited
+            .headers()
+            .into_iter()
+            .filter(|(k, _)| !own_headers.iter().any(|h| h.0.eq_ignore_ascii_case(k)))
+            .collect::<Vec<(String, String)>>();
+        sent.extend(own_headers.into_iter().map(|h| (h.0, h.1)));
+        let mut headers = Vec::new();
+        let mut basic_auth = None;
+        for (key, value) in sent {
+            let (key, value) = (resolve(key), resolve(value));
+            if key.eq_ignore_ascii_case("authorization") {
+                if let Some(credentials) = decode_basic_auth(&value) {
+                    basic_auth = Some(credentials);
+                    continue;
+                }
+            }
+            headers.push((key, value));
+        }
+        let request_body = req.body();
+        let pairs = || {
+            request_body
+                .pairs()
+                .into_iter()
+                .map(|(k, v)| (resolve(k), resolve(v)))
+        };
+        let mut form = Vec::new();
+        let body = match request_body.kind {
+            _ if !req.verb().sends_body() => None,
+            BodyKind::MULTIPART => {
+                form = pairs().collect();
+                None
+            }
+            BodyKind::FORM => Some(
+                pairs()
+                    .map(|(k, v)| format!("{}={}", encode(&k), encode(&v)))
+                    .collect::<Vec<String>>()
+                    .join("&"),
+            ),
+            _ => request_body.payload.clone().map(resolve),
+        }
+        .filter(|b| !b.trim().is_empty());
+        if body.is_some()
+            && !headers
+                .iter()
+                .any(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        {
+            if let Some(content_type) = content_type(&req.body().kind) {
+                headers.push(("Content-Type".to_string(), content_type.to_string()));
+            }
+        }
+        RequestParts {
+            method: req.verb().to_string(),
+            url,
+            headers,
+            body,
+            form,
+            basic_auth,
+        }
+    }
+}
+
+/// The content type restopher sends for a body kind when none is set.
+pub fn content_type(kind: &BodyKind) -> Option<&'static str> {
+    match kind {
+        BodyKind::JSON => Some("application/json"),
+        BodyKind::XML => Some("application/xml"),
+        BodyKind::GRAPHQL => Some("application/graphql"),
+        BodyKind::FORM => Some("application/x-www-form-urlencoded"),
+        BodyKind::TEXT | BodyKind::MULTIPART => None,
+    }
+}
+
+fn decode_basic_auth(value: &str) -> Option<(String, String)> {
+    let encoded = value.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+/// Percent-encodes a query component. Variable placeholders are left as is so
+/// unresolved exports stay readable.
+pub fn encode(s: &str) -> String {
+    let mut result = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'{' | b'}' => {
+                result.push(b as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    result
+}
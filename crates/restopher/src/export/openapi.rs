@@ -0,0 +1,411 @@
+use serde_json::{json, Map, Value};
+
+use super::Folder;
+use crate::request::{BodyKind, Request};
+
+/// Headers described by other parts of the document, or set by the client.
+const SKIPPED_HEADERS: &[&str] = &[
+    "authorization",
+    "content-type",
+    "accept",
+    "content-length",
+    "user-agent",
+    "host",
+];
+
+/// Builds a best-effort OpenAPI 3 document from the saved requests of a
+/// folder. URLs go through `resolve` so `{{baseUrl}}` style prefixes become
+/// servers. Request and response schemas are inferred from the saved bodies
+/// and the last recorded responses; the result is a starting point for API
+/// docs, not a complete spec.
+pub fn to_openapi(folder: &Folder, resolve: &dyn Fn(String) -> String) -> Value {
+    let mut servers = Vec::<String>::new();
+    let mut paths = Map::new();
+    let mut tags = Vec::<String>::new();
+    let mut schemes = Map::new();
+    for (folders, req) in folder.flatten() {
+        let url = resolve(req.address());
+        let (server, path) = split_url(&url);
+        if !server.is_empty() && !servers.contains(&server) {
+            servers.push(server);
+        }
+        let (path, path_params) = templated_path(&path);
+        let method = req.verb().to_string().to_lowercase();
+        let item = paths
+            .entry(path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .unwrap();
+        match item.get_mut(&method) {
+            // the same endpoint saved twice: keep the first description and
+            // collect the response status codes of both
+            Some(operation) => {
+                if let Some((status, response)) = response(req) {
+                    operation["responses"]
+                        .as_object_mut()
+                        .unwrap()
+                        .entry(status)
+                        .or_insert(response);
+                }
+            }
+            None => {
+                let tag = folders.last().map(|t| t.to_string());
+                if let Some(tag) = &tag {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                }
+                let operation = operation(req, tag, &path_params, &resolve, &mut schemes);
+                item.insert(method, operation);
+            }
+        }
+    }
+    let mut document = json!({
+        "openapi": "3.0.3",
+        "info": {"title": folder.name, "version": "0.1.0"},
+        "servers": servers.iter().map(|s| json!({"url": s})).collect::<Vec<Value>>(),
+        "tags": tags.iter().map(|t| json!({"name": t})).collect::<Vec<Value>>(),
+        "paths": paths,
+    });
+    if !schemes.is_empty() {
+        document["components"] = json!({"securitySchemes": schemes});
+    }
+    document
+}
+
+/// Splits `https://host/path` into server and path.
+fn split_url(url: &str) -> (String, String) {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    match url.split_once("://") {
+        Some((scheme, rest)) => match rest.find('/') {
+            Some(idx) => (
+                format!("{}://{}", scheme, &rest[..idx]),
+                rest[idx..].to_string(),
+            ),
+            None => (url.to_string(), "/".to_string()),
+        },
+        None => (String::new(), url.to_string()),
+    }
+}
+
+/// Replaces the segments of a path that look like ids or unresolved
+/// variables with path parameters, as in `/users/42` -> `/users/{userId}`.
+fn templated_path(path: &str) -> (String, Vec<(String, Value)>) {
+    let mut params = Vec::new();
+    let mut previous = "";
+    let segments = path
+        .split('/')
+        .map(|segment| {
+            let name = if let Some(var) = segment
+                .strip_prefix("{{")
+                .and_then(|s| s.strip_suffix("}}"))
+            {
+                Some((var.to_string(), json!("string")))
+            } else if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                Some((id_name(previous), json!("integer")))
+            } else if is_uuid(segment) {
+                Some((id_name(previous), json!("string")))
+            } else {
+                None
+            };
+            previous = segment;
+            match name {
+                Some((name, kind)) => {
+                    let name = unique_name(name, &params);
+                    let templated = format!("{{{}}}", name);
+                    params.push((name, kind));
+                    templated
+                }
+                None => segment.to_string(),
+            }
+        })
+        .collect::<Vec<String>>();
+    (segments.join("/"), params)
+}
+
+fn unique_name(name: String, taken: &[(String, Value)]) -> String {
+    let mut unique = name.clone();
+    let mut n = 2;
+    while taken.iter().any(|(t, _)| *t == unique) {
+        unique = format!("{}{}", name, n);
+        n += 1;
+    }
+    unique
+}
+
+/// `users` -> `userId`.
+fn id_name(collection: &str) -> String {
+    let singular = collection.strip_suffix('s').unwrap_or(collection);
+    let camel = singular
+        .split(['-', '_'])
+        .enumerate()
+        .map(|(idx, part)| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) if idx > 0 => first.to_uppercase().chain(chars).collect(),
+                _ => part.to_string(),
+            }
+        })
+        .collect::<String>();
+    if camel.is_empty() || camel.starts_with('{') {
+        "id".to_string()
+    } else {
+        format!("{}Id", camel)
+    }
+}
+
+fn is_uuid(s: &str) -> bool {
+    let groups = s.split('-').map(|g| g.len()).collect::<Vec<usize>>();
+    groups == [8, 4, 4, 4, 12] && s.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+}
+
+fn operation_id(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .enumerate()
+        .map(|(idx, word)| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if idx > 0 => first.to_uppercase().chain(chars).collect(),
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn operation(
+    req: &Request,
+    tag: Option<String>,
+    path_params: &[(String, Value)],
+    resolve: &dyn Fn(String) -> String,
+    schemes: &mut Map<String, Value>,
+) -> Value {
+    let mut parameters = path_params
+        .iter()
+        .map(|(name, kind)| {
+            json!({"name": name, "in": "path", "required": true, "schema": {"type": kind}})
+        })
+        .collect::<Vec<Value>>();
+    for (k, v, active) in req.params().unwrap_or_default() {
+        if k.is_empty() {
+            continue;
+        }
+        let example = scalar(&resolve(v));
+        parameters.push(json!({
+            "name": k,
+            "in": "query",
+            "required": active,
+            "schema": infer(&example),
+            "example": example,
+        }));
+    }
+    let mut security = Vec::new();
+    for (k, v, active) in req.headers().unwrap_or_default() {
+        if k.is_empty() || !active {
+            continue;
+        }
+        let lower = k.to_lowercase();
+        if lower == "authorization" {
+            let scheme = if v.starts_with("Basic ") {
+                ("basicAuth", json!({"type": "http", "scheme": "basic"}))
+            } else if v.starts_with("Bearer ") {
+                ("bearerAuth", json!({"type": "http", "scheme": "bearer"}))
+            } else {
+                (
+                    "apiKeyAuth",
+                    json!({"type": "apiKey", "in": "header", "name": k}),
+                )
+            };
+            schemes.insert(scheme.0.to_string(), scheme.1);
+            security.push(json!({scheme.0: []}));
+            continue;
+        }
+        if SKIPPED_HEADERS.contains(&lower.as_str()) {
+            continue;
+        }
+        parameters.push(json!({
+            "name": k,
+            "in": "header",
+            "required": true,
+            "schema": {"type": "string"},
+        }));
+    }
+    let mut operation = json!({
+        "summary": req.name(),
+        "operationId": operation_id(&req.name()),
+        "parameters": parameters,
+        "responses": {},
+    });
+    if let Some(tag) = tag {
+        operation["tags"] = json!([tag]);
+    }
+    if !security.is_empty() {
+        operation["security"] = json!(security);
+    }
+    if let Some(body) = request_body(req, resolve) {
+        operation["requestBody"] = body;
+    }
+    let (status, response) = response(req).unwrap_or_else(|| {
+        (
+            "default".to_string(),
+            json!({"description": "no response recorded"}),
+        )
+    });
+    operation["responses"][status] = response;
+    operation
+}
+
+fn request_body(req: &Request, resolve: &dyn Fn(String) -> String) -> Option<Value> {
+    if !req.verb().sends_body() {
+        return None;
+    }
+    let body = req.body();
+    let payload = resolve(body.payload.clone().filter(|p| !p.trim().is_empty())?);
+    let form = |file_fields: bool| {
+        let mut properties = Map::new();
+        for (k, v) in body.pairs() {
+            let schema = match v.strip_prefix('@') {
+                Some(_) if file_fields => json!({"type": "string", "format": "binary"}),
+                _ => infer(&scalar(&resolve(v))),
+            };
+            properties.insert(k, schema);
+        }
+        json!({"type": "object", "properties": properties})
+    };
+    let (media_type, schema, example) = match body.kind {
+        BodyKind::JSON => match serde_json::from_str::<Value>(&payload) {
+            Ok(value) => ("application/json", infer(&value), Some(value)),
+            Err(_) => ("application/json", json!({}), None),
+        },
+        BodyKind::FORM => ("application/x-www-form-urlencoded", form(false), None),
+        BodyKind::MULTIPART => ("multipart/form-data", form(true), None),
+        BodyKind::XML => ("application/xml", json!({"type": "string"}), None),
+        BodyKind::GRAPHQL => ("application/graphql", json!({"type": "string"}), None),
+        BodyKind::TEXT => ("text/plain", json!({"type": "string"}), None),
+    };
+    let mut media = json!({"schema": schema});
+    if let Some(example) = example {
+        media["example"] = example;
+    }
+    Some(json!({"required": true, "content": {media_type: media}}))
+}
+
+/// The last recorded response as an entry of `responses`.
+fn response(req: &Request) -> Option<(String, Value)> {
+    let response = req.response().filter(|r| r.status_code > 0)?;
+    let mut described = json!({"description": format!("recorded response of {}", req.name())});
+    let content_type = response
+        .headers
+        .as_ref()
+        .and_then(|h| {
+            h.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                .map(|(_, v)| v.split(';').next().unwrap_or_default().trim().to_string())
+        })
+        .unwrap_or_else(|| "application/json".to_string());
+    if let Some(body) = response.body.filter(|b| !b.trim().is_empty()) {
+        let schema = match serde_json::from_str::<Value>(&body) {
+            Ok(value) if content_type.contains("json") => infer(&value),
+            _ => json!({"type": "string"}),
+        };
+        described["content"] = json!({content_type: {"schema": schema}});
+    }
+    Some((response.status_code.to_string(), described))
+}
+
+/// Query and form values are strings; numbers and booleans among them are
+/// told apart so the inferred schema is more useful.
+fn scalar(value: &str) -> Value {
+    if let Ok(i) = value.parse::<i64>() {
+        return json!(i);
+    }
+    if let Ok(f) = value.parse::<f64>() {
+        if f.is_finite() {
+            return json!(f);
+        }
+    }
+    match value {
+        "true" => json!(true),
+        "false" => json!(false),
+        _ => json!(value),
+    }
+}
+
+/// Infers a JSON schema from an example value. Array items are merged so
+/// optional properties of any element are described.
+fn infer(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({"nullable": true}),
+        Value::Bool(_) => json!({"type": "boolean"}),
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({"type": "integer"}),
+        Value::Number(_) => json!({"type": "number"}),
+        Value::String(s) => match string_format(s) {
+            Some(format) => json!({"type": "string", "format": format}),
+            None => json!({"type": "string"}),
+        },
+        Value::Array(items) => {
+            let merged = items
+                .iter()
+                .map(infer)
+                .reduce(merge)
+                .unwrap_or_else(|| json!({}));
+            json!({"type": "array", "items": merged})
+        }
+        Value::Object(fields) => {
+            let properties = fields
+                .iter()
+                .map(|(k, v)| (k.clone(), infer(v)))
+                .collect::<Map<String, Value>>();
+            let required = fields.keys().cloned().collect::<Vec<String>>();
+            json!({"type": "object", "properties": properties, "required": required})
+        }
+    }
+}
+
+/// Merges two inferred schemas: properties are united and only those present
+/// in both stay required.
+fn merge(a: Value, b: Value) -> Value {
+    match (&a["type"], &b["type"]) {
+        (Value::String(ta), Value::String(tb)) if ta == "object" && tb == "object" => {
+            let mut properties = a["properties"].as_object().cloned().unwrap_or_default();
+            for (k, v) in b["properties"].as_object().cloned().unwrap_or_default() {
+                let merged = match properties.remove(&k) {
+                    Some(existing) => merge(existing, v),
+                    None => v,
+                };
+                properties.insert(k, merged);
+            }
+            let required = a["required"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|r| b["required"].as_array().is_some_and(|req| req.contains(r)))
+                .collect::<Vec<Value>>();
+            json!({"type": "object", "properties": properties, "required": required})
+        }
+        (Value::String(ta), Value::String(tb)) if ta == "array" && tb == "array" => {
+            json!({"type": "array", "items": merge(a["items"].clone(), b["items"].clone())})
+        }
+        (Value::String(ta), Value::String(tb)) if ta == "integer" && tb == "number" => b,
+        (Value::String(_), _) => a,
+        _ => b,
+    }
+}
+
+fn string_format(s: &str) -> Option<&'static str> {
+    if is_uuid(s) {
+        Some("uuid")
+    } else if chrono::DateTime::parse_from_rfc3339(s).is_ok() {
+        Some("date-time")
+    } else if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        Some("date")
+    } else if s.contains('@') && !s.contains(' ') && s.rsplit('@').next()?.contains('.') {
+        Some("email")
+    } else if s.starts_with("http://") || s.starts_with("https://") {
+        Some("uri")
+    } else {
+        None
+    }
+}
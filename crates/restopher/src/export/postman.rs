@@ -0,0 +1,126 @@
+use serde_json::{json, Value};
+
+use super::Folder;
+use crate::request::{BodyKind, Request};
+
+const SCHEMA_V21: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+/// Renders a collection folder as a Postman v2.1 collection. `variables`,
+/// usually the active environment, become collection variables; `{{var}}`
+/// references need no conversion as Postman uses the same syntax.
+pub fn to_postman(folder: &Folder, variables: &[(String, String)]) -> Value {
+    json!({
+        "info": {
+            "name": folder.name,
+            "schema": SCHEMA_V21,
+        },
+        "item": items(folder),
+        "variable": variables
+            .iter()
+            .map(|(k, v)| json!({"key": k, "value": v}))
+            .collect::<Vec<Value>>(),
+    })
+}
+
+/// Renders an environment as a Postman environment file, imported in
+/// Postman next to the collection.
+pub fn to_postman_environment(name: &str, variables: &[(String, String)]) -> Value {
+    json!({
+        "name": name,
+        "values": variables
+            .iter()
+            .map(|(k, v)| json!({"key": k, "value": v, "type": "default", "enabled": true}))
+            .collect::<Vec<Value>>(),
+        "_postman_variable_scope": "environment",
+    })
+}
+
+fn items(folder: &Folder) -> Vec<Value> {
+    let mut items = folder
+        .folders
+        .iter()
+        .map(|sub| json!({"name": sub.name, "item": items(sub)}))
+        .collect::<Vec<Value>>();
+    items.extend(folder.requests.iter().map(item));
+    items
+}
+
+fn key_values(pairs: Option<Vec<(String, String, bool)>>) -> Vec<Value> {
+    pairs
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(k, _, _)| !k.is_empty())
+        .map(|(k, v, active)| {
+            let mut kv = json!({"key": k, "value": v});
+            if !active {
+                kv["disabled"] = json!(true);
+            }
+            kv
+        })
+        .collect()
+}
+
+fn item(req: &Request) -> Value {
+    let query = key_values(req.params());
+    let active = req
+        .params()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(k, _, active)| *active && !k.is_empty())
+        .map(|(k, v, _)| format!("{}={}", k, v))
+        .collect::<Vec<String>>();
+    let mut raw = req.address();
+    if !active.is_empty() {
+        raw.push(if raw.contains('?') { '&' } else { '?' });
+        raw.push_str(&active.join("&"));
+    }
+    let mut request = json!({
+        "method": req.verb().to_string(),
+        "header": key_values(req.headers()),
+        "url": {"raw": raw, "query": query},
+    });
+    if let Some(body) = body(req) {
+        request["body"] = body;
+    }
+    json!({"name": req.name(), "request": request})
+}
+
+fn body(req: &Request) -> Option<Value> {
+    let body = req.body();
+    let payload = body.payload.clone().filter(|p| !p.trim().is_empty())?;
+    let raw = |language: &str| {
+        json!({
+            "mode": "raw",
+            "raw": payload,
+            "options": {"raw": {"language": language}},
+        })
+    };
+    Some(match body.kind {
+        BodyKind::JSON => raw("json"),
+        BodyKind::XML => raw("xml"),
+        BodyKind::TEXT => raw("text"),
+        BodyKind::GRAPHQL => json!({
+            "mode": "graphql",
+            "graphql": {"query": payload, "variables": ""},
+        }),
+        BodyKind::FORM => json!({
+            "mode": "urlencoded",
+            "urlencoded": body
+                .pairs()
+                .into_iter()
+                .map(|(k, v)| json!({"key": k, "value": v, "type": "text"}))
+                .collect::<Vec<Value>>(),
+        }),
+        BodyKind::MULTIPART => json!({
+            "mode": "formdata",
+            "formdata": body
+                .pairs()
+                .into_iter()
+                .map(|(k, v)| match v.strip_prefix('@') {
+                    Some(path) => json!({"key": k, "type": "file", "src": path}),
+                    None => json!({"key": k, "value": v, "type": "text"}),
+                })
+                .collect::<Vec<Value>>(),
+        }),
+    })
+}
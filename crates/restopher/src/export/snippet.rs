@@ -0,0 +1,499 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::{curl, RequestParts};
+
+/// Client code a request can be rendered as.
+#[derive(Clone, Copy, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Language {
+    Curl,
+    Reqwest,
+    Python,
+    Fetch,
+    Go,
+    HTTPie,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::Curl,
+        Language::Reqwest,
+        Language::Python,
+        Language::Fetch,
+        Language::Go,
+        Language::HTTPie,
+    ];
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Curl => "cURL",
+            Language::Reqwest => "Rust reqwest",
+            Language::Python => "Python requests",
+            Language::Fetch => "JavaScript fetch",
+            Language::Go => "Go net/http",
+            Language::HTTPie => "HTTPie",
+        }
+    }
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Language::Curl | Language::HTTPie => "sh",
+            Language::Reqwest => "rs",
+            Language::Python => "py",
+            Language::Fetch => "mjs",
+            Language::Go => "go",
+        }
+    }
+}
+
+/// Renders the request as a snippet in `language`.
+pub fn to_snippet(language: Language, parts: &RequestParts) -> String {
+    match language {
+        Language::Curl => curl::to_curl(parts),
+        Language::Reqwest => to_reqwest(parts),
+        Language::Python => to_python(parts),
+        Language::Fetch => to_fetch(parts),
+        Language::Go => to_go(parts),
+        Language::HTTPie => to_httpie(parts),
+    }
+}
+
+/// A multipart value starting with `@` names a file to upload, as in curl.
+fn file_field(value: &str) -> Option<&str> {
+    value.strip_prefix('@')
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+/// A JSON string literal, which is also valid in Python, JavaScript and Go.
+fn quoted(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+fn rust_str(s: &str) -> String {
+    if !s.contains(['"', '\\', '\n']) {
+        return format!("\"{}\"", s);
+    }
+    let mut hashes = "#".to_string();
+    while s.contains(&format!("\"{}", hashes)) {
+        hashes.push('#');
+    }
+    format!("r{}\"{}\"{}", hashes, s, hashes)
+}
+
+fn to_reqwest(parts: &RequestParts) -> String {
+    let mut lines = vec![
+        "let client = reqwest::Client::new();".to_string(),
+        "let response = client".to_string(),
+    ];
+    let builder = match parts.method.as_str() {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" => {
+            format!(".{}({})", parts.method.to_lowercase(), rust_str(&parts.url))
+        }
+        method => format!(
+            ".request(reqwest::Method::{}, {})",
+            method,
+            rust_str(&parts.url)
+        ),
+    };
+    lines.push(format!("    {}", builder));
+    for (key, value) in &parts.headers {
+        lines.push(format!(
+            "    .header({}, {})",
+            rust_str(key),
+            rust_str(value)
+        ));
+    }
+    if let Some((user, password)) = &parts.basic_auth {
+        lines.push(format!(
+            "    .basic_auth({}, Some({}))",
+            rust_str(user),
+            rust_str(password)
+        ));
+    }
+    if let Some(body) = &parts.body {
+        lines.push(format!("    .body({})", rust_str(body)));
+    }
+    if !parts.form.is_empty() {
+        let mut form = vec!["let form = reqwest::multipart::Form::new()".to_string()];
+        for (key, value) in &parts.form {
+            match file_field(value) {
+                Some(path) => form.push(format!(
+                    "    .part({}, reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}))",
+                    rust_str(key),
+                    rust_str(path),
+                    rust_str(file_name(path))
+                )),
+                None => form.push(format!(
+                    "    .text({}, {})",
+                    rust_str(key),
+                    rust_str(value)
+                )),
+            }
+        }
+        if let Some(last) = form.last_mut() {
+            last.push(';');
+        }
+        lines.splice(1..1, form);
+        lines.push("    .multipart(form)".to_string());
+    }
+    lines.push("    .send()".to_string());
+    lines.push("    .await?;".to_string());
+    lines.push("println!(\"{}\", response.status());".to_string());
+    lines.push("println!(\"{}\", response.text().await?);".to_string());
+    lines.join("\n")
+}
+
+fn to_python(parts: &RequestParts) -> String {
+    let mut lines = vec![
+        "import requests".to_string(),
+        String::new(),
+        format!("url = {}", quoted(&parts.url)),
+    ];
+    let mut args = vec!["url".to_string()];
+    if !parts.headers.is_empty() {
+        lines.push("headers = {".to_string());
+        for (key, value) in &parts.headers {
+            lines.push(format!("    {}: {},", quoted(key), quoted(value)));
+        }
+        lines.push("}".to_string());
+        args.push("headers=headers".to_string());
+    }
+    if let Some(body) = &parts.body {
+        let literal = if body.contains('\n') && !body.contains(['\\', '\'']) {
+            format!("'''{}'''", body)
+        } else {
+            quoted(body)
+        };
+        lines.push(format!("data = {}", literal));
+        args.push("data=data".to_string());
+    }
+    if !parts.form.is_empty() {
+        let (files, fields): (Vec<_>, Vec<_>) = parts
+            .form
+            .iter()
+            .partition(|(_, value)| file_field(value).is_some());
+        if !fields.is_empty() {
+            lines.push("data = {".to_string());
+            for (key, value) in fields {
+                lines.push(format!("    {}: {},", quoted(key), quoted(value)));
+            }
+            lines.push("}".to_string());
+            args.push("data=data".to_string());
+        }
+        if !files.is_empty() {
+            lines.push("files = {".to_string());
+            for (key, value) in files {
+                lines.push(format!(
+                    "    {}: open({}, \"rb\"),",
+                    quoted(key),
+                    quoted(file_field(value).unwrap_or_default())
+                ));
+            }
+            lines.push("}".to_string());
+            args.push("files=files".to_string());
+        }
+    }
+    if let Some((user, password)) = &parts.basic_auth {
+        args.push(format!("auth=({}, {})", quoted(user), quoted(password)));
+    }
+    lines.push(format!(
+        "response = requests.{}({})",
+        parts.method.to_lowercase(),
+        args.join(", ")
+    ));
+    lines.push("print(response.status_code)".to_string());
+    lines.push("print(response.text)".to_string());
+    lines.join("\n")
+}
+
+fn to_fetch(parts: &RequestParts) -> String {
+    let mut lines = Vec::new();
+    if !parts.form.is_empty() {
+        if parts
+            .form
+            .iter()
+            .any(|(_, value)| file_field(value).is_some())
+        {
+            lines.push("import { openAsBlob } from \"node:fs\";".to_string());
+            lines.push(String::new());
+        }
+        lines.push("const form = new FormData();".to_string());
+        for (key, value) in &parts.form {
+            match file_field(value) {
+                Some(path) => lines.push(format!(
+                    "form.append({}, await openAsBlob({}), {});",
+                    quoted(key),
+                    quoted(path),
+                    quoted(file_name(path))
+                )),
+                None => lines.push(format!("form.append({}, {});", quoted(key), quoted(value))),
+            }
+        }
+    }
+    lines.push(format!(
+        "const response = await fetch({}, {{",
+        quoted(&parts.url)
+    ));
+    lines.push(format!("  method: {},", quoted(&parts.method)));
+    let mut headers = parts.headers.clone();
+    if let Some((user, password)) = &parts.basic_auth {
+        headers.push((
+            "Authorization".to_string(),
+            format!(
+                "Basic {}",
+                STANDARD.encode(format!("{}:{}", user, password))
+            ),
+        ));
+    }
+    if !headers.is_empty() {
+        lines.push("  headers: {".to_string());
+        for (key, value) in &headers {
+            lines.push(format!("    {}: {},", quoted(key), quoted(value)));
+        }
+        lines.push("  },".to_string());
+    }
+    if let Some(body) = &parts.body {
+        let literal = if body.contains('\n') && !body.contains(['\\', '`', '$']) {
+            format!("`{}`", body)
+        } else {
+            quoted(body)
+        };
+        lines.push(format!("  body: {},", literal));
+    } else if !parts.form.is_empty() {
+        lines.push("  body: form,".to_string());
+    }
+    lines.push("});".to_string());
+    lines.push("console.log(response.status);".to_string());
+    lines.push("console.log(await response.text());".to_string());
+    lines.join("\n")
+}
+
+fn to_go(parts: &RequestParts) -> String {
+    let mut imports = vec!["fmt", "io", "net/http"];
+    let mut body_lines = Vec::new();
+    let mut content_type = None;
+    let body = if let Some(body) = &parts.body {
+        imports.push("strings");
+        let literal = if body.contains('\n') && !body.contains('`') {
+            format!("`{}`", body)
+        } else {
+            quoted(body)
+        };
+        body_lines.push(format!("\tbody := strings.NewReader({})", literal));
+        "body"
+    } else if !parts.form.is_empty() {
+        imports.extend(["bytes", "mime/multipart"]);
+        body_lines.push("\tbody := &bytes.Buffer{}".to_string());
+        body_lines.push("\twriter := multipart.NewWriter(body)".to_string());
+        for (key, value) in &parts.form {
+            match file_field(value) {
+                Some(path) => {
+                    if !imports.contains(&"os") {
+                        imports.push("os");
+                    }
+                    body_lines.extend([
+                        "\t{".to_string(),
+                        format!("\t\tfile, err := os.Open({})", quoted(path)),
+                        "\t\tif err != nil {\n\t\t\tpanic(err)\n\t\t}".to_string(),
+                        "\t\tdefer file.Close()".to_string(),
+                        format!(
+                            "\t\tpart, err := writer.CreateFormFile({}, {})",
+                            quoted(key),
+                            quoted(file_name(path))
+                        ),
+                        "\t\tif err != nil {\n\t\t\tpanic(err)\n\t\t}".to_string(),
+                        "\t\tio.Copy(part, file)".to_string(),
+                        "\t}".to_string(),
+                    ]);
+                }
+                None => body_lines.push(format!(
+                    "\twriter.WriteField({}, {})",
+                    quoted(key),
+                    quoted(value)
+                )),
+            }
+        }
+        body_lines.push("\twriter.Close()".to_string());
+        content_type = Some("writer.FormDataContentType()");
+        "body"
+    } else {
+        "nil"
+    };
+    imports.sort_unstable();
+
+    let mut lines = vec![
+        "package main".to_string(),
+        String::new(),
+        "import (".to_string(),
+    ];
+    lines.extend(imports.iter().map(|i| format!("\t\"{}\"", i)));
+    lines.extend([")".to_string(), String::new(), "func main() {".to_string()]);
+    lines.extend(body_lines);
+    lines.push(format!(
+        "\treq, err := http.NewRequest({}, {}, {})",
+        quoted(&parts.method),
+        quoted(&parts.url),
+        body
+    ));
+    lines.push("\tif err != nil {\n\t\tpanic(err)\n\t}".to_string());
+    for (key, value) in &parts.headers {
+        lines.push(format!(
+            "\treq.Header.Add({}, {})",
+            quoted(key),
+            quoted(value)
+        ));
+    }
+    if let Some(content_type) = content_type {
+        lines.push(format!(
+            "\treq.Header.Set(\"Content-Type\", {})",
+            content_type
+        ));
+    }
+    if let Some((user, password)) = &parts.basic_auth {
+        lines.push(format!(
+            "\treq.SetBasicAuth({}, {})",
+            quoted(user),
+            quoted(password)
+        ));
+    }
+    lines.extend([
+        "\tresp, err := http.DefaultClient.Do(req)".to_string(),
+        "\tif err != nil {\n\t\tpanic(err)\n\t}".to_string(),
+        "\tdefer resp.Body.Close()".to_string(),
+        "\trespBody, err := io.ReadAll(resp.Body)".to_string(),
+        "\tif err != nil {\n\t\tpanic(err)\n\t}".to_string(),
+        "\tfmt.Println(resp.Status)".to_string(),
+        "\tfmt.Println(string(respBody))".to_string(),
+        "}".to_string(),
+    ]);
+    lines.join("\n")
+}
+
+fn to_httpie(parts: &RequestParts) -> String {
+    let mut args = Vec::new();
+    if !parts.form.is_empty() {
+        args.push("--multipart".to_string());
+    }
+    if let Some((user, password)) = &parts.basic_auth {
+        args.push(format!(
+            "-a {}",
+            curl::quote(&format!("{}:{}", user, password))
+        ));
+    }
+    if let Some(body) = &parts.body {
+        args.push(format!("--raw {}", curl::quote(body)));
+    }
+    args.push(parts.method.clone());
+    args.push(curl::quote(&parts.url));
+    for (key, value) in &parts.headers {
+        args.push(curl::quote(&format!("{}:{}", key, value)));
+    }
+    for (key, value) in &parts.form {
+        let item = match file_field(value) {
+            Some(path) => format!("{}@{}", key, path),
+            None => format!("{}={}", key, value),
+        };
+        args.push(curl::quote(&item));
+    }
+    format!("http {}", args.join(" \\\n  "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_post() -> RequestParts {
+        RequestParts {
+            method: "POST".to_string(),
+            url: "https://example.com/users".to_string(),
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: Some("{\"name\": \"ada\"}".to_string()),
+            form: Vec::new(),
+            basic_auth: Some(("user".to_string(), "pass".to_string())),
+        }
+    }
+
+    fn upload() -> RequestParts {
+        RequestParts {
+            method: "PUT".to_string(),
+            url: "https://example.com/files".to_string(),
+            headers: Vec::new(),
+            body: None,
+            form: vec![
+                ("title".to_string(), "report".to_string()),
+                ("file".to_string(), "@/tmp/report.pdf".to_string()),
+            ],
+            basic_auth: None,
+        }
+    }
+
+    #[test]
+    fn rust_strings_use_raw_literals_when_needed() {
+        assert_eq!(rust_str("plain"), "\"plain\"");
+        assert_eq!(rust_str("{\"a\": 1}"), "r#\"{\"a\": 1}\"#");
+        assert_eq!(rust_str("\"#"), "r##\"\"#\"##");
+    }
+
+    #[test]
+    fn renders_reqwest() {
+        let snippet = to_snippet(Language::Reqwest, &json_post());
+        assert!(snippet.contains("    .post(\"https://example.com/users\")\n"));
+        assert!(snippet.contains("    .header(\"Content-Type\", \"application/json\")\n"));
+        assert!(snippet.contains("    .basic_auth(\"user\", Some(\"pass\"))\n"));
+        assert!(snippet.contains("    .body(r#\"{\"name\": \"ada\"}\"#)\n"));
+
+        let snippet = to_snippet(Language::Reqwest, &upload());
+        assert!(snippet.starts_with(
+            "let client = reqwest::Client::new();\nlet form = reqwest::multipart::Form::new()\n"
+        ));
+        assert!(snippet.contains("    .text(\"title\", \"report\")\n"));
+        assert!(snippet.contains(".file_name(\"report.pdf\"));\n"));
+        assert!(snippet.contains("    .multipart(form)\n"));
+    }
+
+    #[test]
+    fn renders_python() {
+        let snippet = to_snippet(Language::Python, &json_post());
+        assert!(snippet.contains("data = \"{\\\"name\\\": \\\"ada\\\"}\"\n"));
+        assert!(snippet.contains(
+            "response = requests.post(url, headers=headers, data=data, auth=(\"user\", \"pass\"))"
+        ));
+        let snippet = to_snippet(Language::Python, &upload());
+        assert!(snippet.contains("    \"file\": open(\"/tmp/report.pdf\", \"rb\"),\n"));
+        assert!(snippet.contains("requests.put(url, data=data, files=files)"));
+    }
+
+    #[test]
+    fn renders_fetch() {
+        let snippet = to_snippet(Language::Fetch, &json_post());
+        assert!(snippet.contains("  method: \"POST\",\n"));
+        assert!(snippet.contains("    \"Authorization\": \"Basic dXNlcjpwYXNz\",\n"));
+        let snippet = to_snippet(Language::Fetch, &upload());
+        assert!(snippet.starts_with("import { openAsBlob } from \"node:fs\";"));
+        assert!(snippet.contains("  body: form,\n"));
+    }
+
+    #[test]
+    fn renders_go() {
+        let snippet = to_snippet(Language::Go, &json_post());
+        assert!(snippet.contains("\t\"strings\"\n"));
+        assert!(snippet.contains("\treq.SetBasicAuth(\"user\", \"pass\")\n"));
+        let snippet = to_snippet(Language::Go, &upload());
+        assert!(snippet.contains(
+            "import (\n\t\"bytes\"\n\t\"fmt\"\n\t\"io\"\n\t\"mime/multipart\"\n\t\"net/http\"\n\t\"os\"\n)"
+        ));
+        assert!(
+            snippet.contains("\treq.Header.Set(\"Content-Type\", writer.FormDataContentType())\n")
+        );
+    }
+
+    #[test]
+    fn renders_httpie() {
+        assert_eq!(
+            to_snippet(Language::HTTPie, &upload()),
+            "http --multipart \\\n  PUT \\\n  https://example.com/files \\\n  title=report \\\n  file@/tmp/report.pdf"
+        );
+        assert!(to_snippet(Language::HTTPie, &json_post()).starts_with("http -a user:pass \\\n"));
+    }
+}
@@ -0,0 +1,159 @@
+use std::cmp::Ordering;
+
+use serde_json::Value;
+
+use super::{Comparison, Condition, Filter};
+
+pub fn eval(filter: &Filter, input: &Value) -> Result<Vec<Value>, String> {
+    match filter {
+        Filter::Identity => Ok(vec![input.clone()]),
+        Filter::Field { name, optional } => match input {
+            Value::Object(map) => match map.get(name) {
+                Some(v) => Ok(vec![v.clone()]),
+                None if *optional => Ok(Vec::new()),
+                None => Ok(vec![Value::Null]),
+            },
+            Value::Null if !optional => Ok(vec![Value::Null]),
+            _ if *optional => Ok(Vec::new()),
+            _ => Err(format!("cannot index {} with \"{}\"", kind(input), name)),
+        },
+        Filter::Index(idx) => match input {
+            Value::Array(items) => Ok(vec![position(*idx, items.len())
+                .and_then(|i| items.get(i).cloned())
+                .unwrap_or(Value::Null)]),
+            Value::Null => Ok(vec![Value::Null]),
+            _ => Err(format!("cannot index {} with number", kind(input))),
+        },
+        Filter::Slice(from, to) => match input {
+            Value::Array(items) => {
+                let (from, to) = slice_bounds(*from, *to, items.len());
+                Ok(vec![Value::Array(items[from..to].to_vec())])
+            }
+            Value::String(s) => {
+                let chars = s.chars().collect::<Vec<char>>();
+                let (from, to) = slice_bounds(*from, *to, chars.len());
+                Ok(vec![Value::String(chars[from..to].iter().collect())])
+            }
+            Value::Null => Ok(vec![Value::Null]),
+            _ => Err(format!("cannot slice {}", kind(input))),
+        },
+        Filter::Iterate => match input {
+            Value::Array(items) => Ok(items.clone()),
+            Value::Object(map) => Ok(map.values().cloned().collect()),
+            _ => Err(format!("cannot iterate over {}", kind(input))),
+        },
+        Filter::Recurse => {
+            let mut result = Vec::new();
+            recurse(input, &mut result);
+            Ok(result)
+        }
+        Filter::Pipe(first, next) => {
+            let mut result = Vec::new();
+            for value in eval(first, input)? {
+                result.append(&mut eval(next, &value)?);
+            }
+            Ok(result)
+        }
+        Filter::Keys => match input {
+            Value::Object(map) => Ok(vec![Value::Array(
+                map.keys().map(|k| Value::String(k.clone())).collect(),
+            )]),
+            Value::Array(items) => Ok(vec![Value::Array(
+                (0..items.len()).map(Value::from).collect(),
+            )]),
+            _ => Err(format!("{} has no keys", kind(input))),
+        },
+        Filter::Length => match input {
+            Value::Array(items) => Ok(vec![Value::from(items.len())]),
+            Value::Object(map) => Ok(vec![Value::from(map.len())]),
+            Value::String(s) => Ok(vec![Value::from(s.chars().count())]),
+            Value::Null => Ok(vec![Value::from(0)]),
+            Value::Number(n) => Ok(vec![Value::from(n.as_f64().unwrap_or(0.0).abs())]),
+            Value::Bool(_) => Err("boolean has no length".to_string()),
+        },
+        Filter::Map(inner) => match input {
+            Value::Array(items) => {
+                let mut result = Vec::new();
+                for item in items {
+                    result.append(&mut eval(inner, item)?);
+                }
+                Ok(vec![Value::Array(result)])
+            }
+            _ => Err(format!("cannot map over {}", kind(input))),
+        },
+        Filter::Select(condition) => {
+            if matches(condition, input)? {
+                Ok(vec![input.clone()])
+            } else {
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+fn matches(condition: &Condition, input: &Value) -> Result<bool, String> {
+    let values = eval(&condition.lhs, input)?;
+    Ok(match &condition.comparison {
+        Some((comparison, literal)) => values.iter().any(|v| compare(v, comparison, literal)),
+        None => values
+            .iter()
+            .any(|v| !matches!(v, Value::Null | Value::Bool(false))),
+    })
+}
+
+fn compare(value: &Value, comparison: &Comparison, literal: &Value) -> bool {
+    let ordering = match (value, literal) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match comparison {
+        Comparison::Eq => match ordering {
+            Some(o) => o == Ordering::Equal,
+            None => value == literal,
+        },
+        Comparison::Ne => match ordering {
+            Some(o) => o != Ordering::Equal,
+            None => value != literal,
+        },
+        Comparison::Lt => ordering == Some(Ordering::Less),
+        Comparison::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        Comparison::Gt => ordering == Some(Ordering::Greater),
+        Comparison::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+fn recurse(value: &Value, result: &mut Vec<Value>) {
+    result.push(value.clone());
+    match value {
+        Value::Array(items) => items.iter().for_each(|v| recurse(v, result)),
+        Value::Object(map) => map.values().for_each(|v| recurse(v, result)),
+        _ => (),
+    }
+}
+
+fn position(idx: i64, len: usize) -> Option<usize> {
+    if idx < 0 {
+        len.checked_sub(idx.unsigned_abs() as usize)
+    } else {
+        Some(idx as usize)
+    }
+}
+
+fn slice_bounds(from: Option<i64>, to: Option<i64>, len: usize) -> (usize, usize) {
+    let clamp = |idx: i64| position(idx, len).unwrap_or(0).min(len);
+    let from = from.map_or(0, clamp);
+    let to = to.map_or(len, clamp);
+    (from, to.max(from))
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,134 @@
+use super::{Condition, Cursor, Filter};
+
+/// Parses the supported jq subset: paths (`.a.b`, `."a b"`, `.[0]`, `.[-1]`,
+/// `.[1:3]`, `.[]`, `..`), optional access with `?`, pipes, and the `keys`,
+/// `length`, `first`, `last`, `map(f)` and `select(f <op> literal)` builtins.
+pub fn parse(expression: &str) -> Result<Filter, String> {
+    let mut cursor = Cursor::new(expression);
+    let filter = pipeline(&mut cursor)?;
+    cursor.skip_whitespace();
+    if !cursor.is_done() {
+        return Err(cursor.error("unexpected character"));
+    }
+    Ok(filter)
+}
+
+fn pipeline(cursor: &mut Cursor) -> Result<Filter, String> {
+    let mut filter = term(cursor)?;
+    loop {
+        cursor.skip_whitespace();
+        if !cursor.eat('|') {
+            return Ok(filter);
+        }
+        filter = Filter::Pipe(Box::new(filter), Box::new(term(cursor)?));
+    }
+}
+
+fn term(cursor: &mut Cursor) -> Result<Filter, String> {
+    cursor.skip_whitespace();
+    if cursor.eat_str("..") {
+        return path(cursor, Filter::Recurse);
+    }
+    if cursor.eat('.') {
+        let first = match cursor.peek() {
+            Some('[') => Filter::Identity,
+            Some('"') => {
+                cursor.eat('"');
+                field(cursor.string('"')?, cursor)
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = cursor.identifier().unwrap_or_default();
+                field(name, cursor)
+            }
+            _ => Filter::Identity,
+        };
+        return path(cursor, first);
+    }
+    let Some(name) = cursor.identifier() else {
+        return Err(cursor.error("expected a filter"));
+    };
+    match name.as_str() {
+        "keys" => Ok(Filter::Keys),
+        "length" => Ok(Filter::Length),
+        "first" => Ok(Filter::Index(0)),
+        "last" => Ok(Filter::Index(-1)),
+        "map" => {
+            cursor.expect('(')?;
+            let inner = pipeline(cursor)?;
+            cursor.expect(')')?;
+            Ok(Filter::Map(Box::new(inner)))
+        }
+        "select" => {
+            cursor.expect('(')?;
+            let lhs = pipeline(cursor)?;
+            let comparison = match cursor.comparison() {
+                Some(comparison) => Some((comparison, cursor.literal()?)),
+                None => None,
+            };
+            cursor.expect(')')?;
+            Ok(Filter::Select(Box::new(Condition { lhs, comparison })))
+        }
+        _ => Err(format!("unknown function '{}'", name)),
+    }
+}
+
+fn field(name: String, cursor: &mut Cursor) -> Filter {
+    Filter::Field {
+        name,
+        optional: cursor.eat('?'),
+    }
+}
+
+/// Parses the suffixes following a path start: `.name`, `."name"` and brackets.
+fn path(cursor: &mut Cursor, mut filter: Filter) -> Result<Filter, String> {
+    loop {
+        match cursor.peek() {
+            Some('[') => {
+                cursor.eat('[');
+                filter = filter.pipe(bracket(cursor)?);
+            }
+            Some('.') => {
+                cursor.eat('.');
+                if cursor.eat('"') {
+                    let name = cursor.string('"')?;
+                    filter = filter.pipe(field(name, cursor));
+                } else if cursor.peek() == Some('[') {
+                    continue;
+                } else {
+                    let Some(name) = cursor.identifier() else {
+                        return Err(cursor.error("expected a field name"));
+                    };
+                    filter = filter.pipe(field(name, cursor));
+                }
+            }
+            _ => return Ok(filter),
+        }
+    }
+}
+
+fn bracket(cursor: &mut Cursor) -> Result<Filter, String> {
+    cursor.skip_whitespace();
+    if cursor.eat(']') {
+        return Ok(Filter::Iterate);
+    }
+    if cursor.eat('"') {
+        let name = cursor.string('"')?;
+        cursor.expect(']')?;
+        return Ok(field(name, cursor));
+    }
+    let from = cursor.integer();
+    cursor.skip_whitespace();
+    if cursor.eat(':') {
+        cursor.skip_whitespace();
+        let to = cursor.integer();
+        cursor.expect(']')?;
+        return Ok(Filter::Slice(from, to));
+    }
+    match from {
+        Some(idx) => {
+            cursor.expect(']')?;
+            Ok(Filter::Index(idx))
+        }
+        None => Err(cursor.error("expected an index")),
+    }
+}
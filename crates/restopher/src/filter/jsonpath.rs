@@ -0,0 +1,99 @@
+use super::{Condition, Cursor, Filter};
+
+/// Parses the supported JSONPath subset: `$`, `.name`, `['name']`, `[0]`,
+/// `[1:3]`, `[*]`, `.*`, `..name` and `[?(@.path <op> literal)]` filters.
+/// Missing members yield no result instead of null.
+pub fn parse(expression: &str) -> Result<Filter, String> {
+    let mut cursor = Cursor::new(expression);
+    if !cursor.eat('$') {
+        return Err(cursor.error("expected '$'"));
+    }
+    let filter = segments(&mut cursor)?;
+    cursor.skip_whitespace();
+    if !cursor.is_done() {
+        return Err(cursor.error("unexpected character"));
+    }
+    Ok(filter)
+}
+
+fn segments(cursor: &mut Cursor) -> Result<Filter, String> {
+    let mut filter = Filter::Identity;
+    loop {
+        if cursor.eat_str("..") {
+            filter = filter.pipe(Filter::Recurse);
+            filter = filter.pipe(match cursor.peek() {
+                Some('[') => {
+                    cursor.eat('[');
+                    bracket(cursor)?
+                }
+                _ => member(cursor)?,
+            });
+        } else if cursor.eat('.') {
+            filter = filter.pipe(member(cursor)?);
+        } else if cursor.eat('[') {
+            filter = filter.pipe(bracket(cursor)?);
+        } else {
+            return Ok(filter);
+        }
+    }
+}
+
+fn member(cursor: &mut Cursor) -> Result<Filter, String> {
+    if cursor.eat('*') {
+        return Ok(Filter::Iterate);
+    }
+    match cursor.identifier() {
+        Some(name) => Ok(Filter::Field {
+            name,
+            optional: true,
+        }),
+        None => Err(cursor.error("expected a member name")),
+    }
+}
+
+fn bracket(cursor: &mut Cursor) -> Result<Filter, String> {
+    cursor.skip_whitespace();
+    if cursor.eat('*') {
+        cursor.expect(']')?;
+        return Ok(Filter::Iterate);
+    }
+    if cursor.eat_str("?(") {
+        cursor.skip_whitespace();
+        if !cursor.eat('@') {
+            return Err(cursor.error("expected '@'"));
+        }
+        let lhs = segments(cursor)?;
+        let comparison = match cursor.comparison() {
+            Some(comparison) => Some((comparison, cursor.literal()?)),
+            None => None,
+        };
+        cursor.expect(')')?;
+        cursor.expect(']')?;
+        return Ok(Filter::Iterate.pipe(Filter::Select(Box::new(Condition { lhs, comparison }))));
+    }
+    for quote in ['\'', '"'] {
+        if cursor.eat(quote) {
+            let name = cursor.string(quote)?;
+            cursor.expect(']')?;
+            return Ok(Filter::Field {
+                name,
+                optional: true,
+            });
+        }
+    }
+    let from = cursor.integer();
+    cursor.skip_whitespace();
+    if cursor.eat(':') {
+        cursor.skip_whitespace();
+        let to = cursor.integer();
+        cursor.expect(']')?;
+        return Ok(Filter::Slice(from, to).pipe(Filter::Iterate));
+    }
+    match from {
+        Some(idx) => {
+            cursor.expect(']')?;
+            Ok(Filter::Index(idx))
+        }
+        None => Err(cursor.error("expected an index")),
+    }
+}
@@ -0,0 +1,333 @@
+mod eval;
+mod jq;
+mod jsonpath;
+
+use serde_json::Value;
+
+/// A parsed response filter. Both the jq-like syntax (`.items[] | .name`) and
+/// JSONPath (`$.items[*].name`) are compiled into this form.
+#[derive(Debug, Clone)]
+pub enum Filter {
+    Identity,
+    /// Object member access. Optional members yield nothing instead of an
+    /// error when the input is not an object or the key is missing.
+    Field {
+        name: String,
+        optional: bool,
+    },
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+    Iterate,
+    Recurse,
+    Pipe(Box<Filter>, Box<Filter>),
+    Keys,
+    Length,
+    Map(Box<Filter>),
+    Select(Box<Condition>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Condition {
+    pub lhs: Filter,
+    pub comparison: Option<(Comparison, Value)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Filter {
+    fn pipe(self, next: Filter) -> Filter {
+        match self {
+            Filter::Identity => next,
+            _ => Filter::Pipe(Box::new(self), Box::new(next)),
+        }
+    }
+}
+
+/// Parses `expression`, picking JSONPath when it starts with `$`.
+pub fn parse(expression: &str) -> Result<Filter, String> {
+    let expression = expression.trim();
+    if expression.starts_with('$') {
+        jsonpath::parse(expression)
+    } else {
+        jq::parse(expression)
+    }
+}
+
+pub fn apply(expression: &str, value: &Value) -> Result<Vec<Value>, String> {
+    eval::eval(&parse(expression)?, value)
+}
+
+/// Renders filter results the way jq does, one pretty-printed value after another.
+pub fn render(results: &[Value]) -> String {
+    results
+        .iter()
+        .map(|v| serde_json::to_string_pretty(v).unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Character cursor shared by the jq and JSONPath parsers.
+pub(crate) struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Cursor {
+    pub(crate) fn new(input: &str) -> Self {
+        Self {
+            chars: input.chars().collect(),
+            pos: 0,
+        }
+    }
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+    pub(crate) fn is_done(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+    pub(crate) fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+    pub(crate) fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+    pub(crate) fn eat_str(&mut self, s: &str) -> bool {
+        let len = s.chars().count();
+        if self.chars[self.pos..]
+            .iter()
+            .take(len)
+            .copied()
+            .eq(s.chars())
+        {
+            self.pos += len;
+            return true;
+        }
+        false
+    }
+    pub(crate) fn expect(&mut self, c: char) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", c)))
+        }
+    }
+    pub(crate) fn error(&self, msg: &str) -> String {
+        match self.peek() {
+            Some(c) => format!("{} at {}, found '{}'", msg, self.pos + 1, c),
+            None => format!("{} at end of expression", msg),
+        }
+    }
+    pub(crate) fn identifier(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        Some(self.chars[start..self.pos].iter().collect())
+    }
+    pub(crate) fn integer(&mut self) -> Option<i64> {
+        let start = self.pos;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let text = self.chars[start..self.pos].iter().collect::<String>();
+        match text.parse() {
+            Ok(n) => Some(n),
+            Err(_) => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+    /// Reads a string delimited by `quote`, the opening quote already consumed.
+    pub(crate) fn string(&mut self, quote: char) -> Result<String, String> {
+        let mut result = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some('\\') => {
+                    self.pos += 1;
+                    if let Some(c) = self.peek() {
+                        result.push(c);
+                        self.pos += 1;
+                    }
+                }
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(result);
+                }
+                Some(c) => {
+                    result.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+    /// Reads a JSON literal, or a single-quoted string as JSONPath allows.
+    pub(crate) fn literal(&mut self) -> Result<serde_json::Value, String> {
+        self.skip_whitespace();
+        if self.eat('\'') {
+            return Ok(serde_json::Value::String(self.string('\'')?));
+        }
+        if self.peek().is_some_and(|c| c == '-' || c.is_ascii_digit()) {
+            // the JSON stream parser rejects numbers directly followed by `)` or `]`
+            let start = self.pos;
+            while self
+                .peek()
+                .is_some_and(|c| c.is_ascii_digit() || "-+.eE".contains(c))
+            {
+                self.pos += 1;
+            }
+            let number = self.chars[start..self.pos].iter().collect::<String>();
+            return serde_json::from_str(&number).map_err(|_| {
+                self.pos = start;
+                self.error("expected a number")
+            });
+        }
+        let rest = self.chars[self.pos..].iter().collect::<String>();
+        let mut stream = serde_json::Deserializer::from_str(&rest).into_iter::<serde_json::Value>();
+        match stream.next() {
+            Some(Ok(value)) => {
+                self.pos += rest[..stream.byte_offset()].chars().count();
+                Ok(value)
+            }
+            _ => Err(self.error("expected a literal")),
+        }
+    }
+    fn comparison(&mut self) -> Option<Comparison> {
+        self.skip_whitespace();
+        for (token, comparison) in [
+            ("==", Comparison::Eq),
+            ("!=", Comparison::Ne),
+            ("<=", Comparison::Le),
+            (">=", Comparison::Ge),
+            ("<", Comparison::Lt),
+            (">", Comparison::Gt),
+        ] {
+            if self.eat_str(token) {
+                return Some(comparison);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn doc() -> Value {
+        json!({
+            "store": {
+                "name": "corner shop",
+                "books": [
+                    {"title": "Dune", "price": 9, "tags": ["sf"]},
+                    {"title": "Emma", "price": 12},
+                    {"title": "Ubik", "price": 7, "tags": ["sf", "classic"]}
+                ]
+            },
+            "a b": 1
+        })
+    }
+
+    fn run(expression: &str) -> Vec<Value> {
+        apply(expression, &doc()).unwrap()
+    }
+
+    #[test]
+    fn jq_paths() {
+        assert_eq!(run("."), vec![doc()]);
+        assert_eq!(run(".store.name"), vec![json!("corner shop")]);
+        assert_eq!(run(".\"a b\""), vec![json!(1)]);
+        assert_eq!(run(".store.books[-1].title"), vec![json!("Ubik")]);
+        // a slice is an array, not its items, as in jq
+        assert!(apply(".store.books[1:].title", &doc()).is_err());
+        assert_eq!(
+            run(".store.books[1:] | .[].title"),
+            vec![json!("Emma"), json!("Ubik")]
+        );
+        assert_eq!(
+            run(".store.books[] | .title"),
+            vec![json!("Dune"), json!("Emma"), json!("Ubik")]
+        );
+        assert_eq!(run(".missing"), vec![Value::Null]);
+    }
+
+    #[test]
+    fn jq_builtins() {
+        assert_eq!(run(".store.books | length"), vec![json!(3)]);
+        assert_eq!(run(".store | keys"), vec![json!(["books", "name"])]);
+        assert_eq!(run(".store.books | map(.price)"), vec![json!([9, 12, 7])]);
+        assert_eq!(
+            run(".store.books[] | select(.price < 10) | .title"),
+            vec![json!("Dune"), json!("Ubik")]
+        );
+        assert_eq!(
+            run(".store.books[] | select(.tags) | .title"),
+            vec![json!("Dune"), json!("Ubik")]
+        );
+        assert_eq!(run(".store.books | first | .title"), vec![json!("Dune")]);
+        assert_eq!(run(".. | .title?"), run("$..title"));
+    }
+
+    #[test]
+    fn jsonpath_selectors() {
+        assert_eq!(run("$.store.name"), vec![json!("corner shop")]);
+        assert_eq!(run("$['a b']"), vec![json!(1)]);
+        assert_eq!(
+            run("$.store.books[*].title"),
+            vec![json!("Dune"), json!("Emma"), json!("Ubik")]
+        );
+        assert_eq!(
+            run("$..title"),
+            vec![json!("Dune"), json!("Emma"), json!("Ubik")]
+        );
+        assert_eq!(
+            run("$.store.books[?(@.title == 'Emma')].price"),
+            vec![json!(12)]
+        );
+        assert_eq!(
+            run("$.store.books[0:2].title"),
+            vec![json!("Dune"), json!("Emma")]
+        );
+        assert_eq!(run("$.missing"), Vec::<Value>::new());
+    }
+
+    #[test]
+    fn reports_invalid_expressions() {
+        for expression in [".store.", ".[", "select(.a", "$.store[", "$x", ". | | ."] {
+            assert!(parse(expression).is_err(), "{}", expression);
+        }
+        assert!(apply(".store.name[0]", &doc()).is_err());
+    }
+
+    #[test]
+    fn renders_like_jq() {
+        assert_eq!(
+            render(&[json!("a"), json!({"b": 1})]),
+            "\"a\"\n{\n  \"b\": 1\n}"
+        );
+    }
+}
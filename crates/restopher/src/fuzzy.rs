@@ -0,0 +1,90 @@
+/// Scores `text` against `query` when every character of the query appears
+/// in order, ignoring case. Runs of consecutive characters and matches at
+/// the start of a word score higher; `None` means no match.
+pub fn score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(|c| c.to_lowercase()).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for q in query.chars().flat_map(|c| c.to_lowercase()) {
+        if q.is_whitespace() {
+            continue;
+        }
+        let found = next + text[next..].iter().position(|c| *c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+    // shorter texts win between equal matches
+    Some(score * 100 - text.len() as i64)
+}
+
+/// Indices of the `texts` matching `query`, best match first. An empty query
+/// keeps every text in its order.
+pub fn rank<'a>(query: &str, texts: impl Iterator<Item = &'a str>) -> Vec<usize> {
+    let mut ranked: Vec<(usize, i64)> = texts
+        .enumerate()
+        .filter_map(|(idx, text)| score(query, text).map(|s| (idx, s)))
+        .collect();
+    if !query.trim().is_empty() {
+        ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    }
+    ranked.into_iter().map(|(idx, _)| idx).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_characters_in_order() {
+        assert!(score("sr", "Send request").is_some());
+        assert!(score("SR", "send request").is_some());
+        assert!(score("rs", "Send request").is_some());
+        assert!(score("xs", "Send request").is_none());
+        assert!(score("send  req", "Send request").is_some());
+        assert_eq!(score("", "abc"), Some(-3));
+    }
+
+    #[test]
+    fn consecutive_runs_score_higher() {
+        let run = score("sen", "send").unwrap();
+        let scattered = score("sen", "s_e_n").unwrap();
+        assert!(run > scattered, "{} <= {}", run, scattered);
+    }
+
+    #[test]
+    fn word_starts_score_higher() {
+        let start = score("r", "send request").unwrap();
+        let middle = score("r", "sender quest").unwrap();
+        assert!(start > middle, "{} <= {}", start, middle);
+    }
+
+    #[test]
+    fn shorter_texts_win_ties() {
+        assert!(score("save", "save").unwrap() > score("save", "save all").unwrap());
+    }
+
+    #[test]
+    fn ranks_best_match_first() {
+        let texts = ["Show history", "Save request", "Send request", "Quit"];
+        // equal scores keep their order
+        assert_eq!(rank("sr", texts.iter().copied()), vec![1, 2, 0]);
+        assert_eq!(rank("send", texts.iter().copied()), vec![2]);
+        assert_eq!(rank("hist", texts.iter().copied()), vec![0]);
+        assert!(rank("zzz", texts.iter().copied()).is_empty());
+    }
+
+    #[test]
+    fn empty_query_keeps_the_order() {
+        let texts = ["b", "a", "c"];
+        assert_eq!(rank("", texts.iter().copied()), vec![0, 1, 2]);
+        assert_eq!(rank("  ", texts.iter().copied()), vec![0, 1, 2]);
+    }
+}
@@ -2,6 +2,7 @@ use super::models::Focus;
 use super::{schema::Schema, State};
 use crate::graphql::GraphQL;
 use crate::request::Request;
+use crate::theme;
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 use scrollable_widget::ScrollableList;
@@ -32,25 +33,20 @@ impl GqlUI {
                     .split(rect);
 
                 // Objects list
-                let items: Vec<ListItem> = objects
-                    .iter()
-                    .map(|name| ListItem::new(name.clone()))
-                    .collect();
-
-                let objects_list = ScrollableList::new(items)
+                let objects_list = ScrollableList::new(objects.clone())
                     .block(
                         Block::default()
                             .title(" Objects ")
                             .borders(Borders::ALL)
                             .border_style(Style::default().fg(
                                 if matches!(state.focus, Focus::Objects) {
-                                    Color::Yellow
+                                    theme::get().selected
                                 } else {
-                                    Color::Gray
+                                    theme::get().border
                                 },
                             )),
                     )
-                    .highlight_style(Style::default().fg(Color::Yellow));
+                    .highlight_style(Style::default().fg(theme::get().selected));
 
                 let mut list_state = ListState::default();
                 if !objects.is_empty() {
@@ -106,13 +102,13 @@ impl GqlUI {
                             .borders(Borders::ALL)
                             .border_style(Style::default().fg(
                                 if matches!(state.focus, Focus::ObjectsFields) {
-                                    Color::Yellow
+                                    theme::get().selected
                                 } else {
-                                    Color::Gray
+                                    theme::get().border
                                 },
                             )),
                     )
-                    .highlight_style(Style::default().fg(Color::Yellow));
+                    .highlight_style(Style::default().fg(theme::get().selected));
 
                     let mut tstate = TableState::default();
                     if !fields.is_empty() {
@@ -136,9 +132,9 @@ impl GqlUI {
                         Block::default()
                             .title(" Queries ")
                             .borders(Borders::ALL)
-                            .border_style(Style::default().fg(Color::Yellow)),
+                            .border_style(Style::default().fg(theme::get().selected)),
                     )
-                    .highlight_style(Style::default().fg(Color::Yellow));
+                    .highlight_style(Style::default().fg(theme::get().selected));
 
                 let mut list_state = ListState::default();
                 let queries_len = schema.get_queries().len();
@@ -161,9 +157,9 @@ impl GqlUI {
                         Block::default()
                             .title(" Mutations ")
                             .borders(Borders::ALL)
-                            .border_style(Style::default().fg(Color::Yellow)),
+                            .border_style(Style::default().fg(theme::get().selected)),
                     )
-                    .highlight_style(Style::default().fg(Color::Yellow));
+                    .highlight_style(Style::default().fg(theme::get().selected));
 
                 let mut list_state = ListState::default();
                 let mutations_len = schema.get_mutations().len();
@@ -0,0 +1,57 @@
+use super::{next_non_space, number_end, string_end, word_end, Token, TokenKind};
+
+const KEYWORDS: &[&str] = &[
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+    "on",
+    "type",
+    "input",
+    "enum",
+    "schema",
+    "interface",
+    "union",
+    "scalar",
+    "extend",
+    "directive",
+    "implements",
+];
+
+pub fn tokens(chars: &[char]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        let (end, kind) = match c {
+            '#' => (chars.len(), TokenKind::Comment),
+            '"' => (string_end(chars, idx, '"'), TokenKind::String),
+            '-' | '0'..='9' => (number_end(chars, idx), TokenKind::Number),
+            '$' | '@' => (word_end(chars, idx + 1), TokenKind::Variable),
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '!' | '=' | ',' | '|' | '.' => {
+                (idx + 1, TokenKind::Punctuation)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let end = word_end(chars, idx);
+                let word = chars[idx..end].iter().collect::<String>();
+                if matches!(word.as_str(), "true" | "false" | "null") {
+                    (end, TokenKind::Boolean)
+                } else if KEYWORDS.contains(&word.as_str()) {
+                    (end, TokenKind::Keyword)
+                } else if next_non_space(chars, end) == Some(':') {
+                    (end, TokenKind::Key)
+                } else {
+                    idx = end;
+                    continue;
+                }
+            }
+            _ => {
+                idx += 1;
+                continue;
+            }
+        };
+        tokens.push((idx, end, kind));
+        idx = end;
+    }
+    tokens
+}
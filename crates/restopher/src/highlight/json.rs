@@ -0,0 +1,38 @@
+use super::{next_non_space, number_end, string_end, word_end, Token, TokenKind};
+
+pub fn tokens(chars: &[char]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        let (end, kind) = match c {
+            '"' => {
+                let end = string_end(chars, idx, '"');
+                if next_non_space(chars, end) == Some(':') {
+                    (end, TokenKind::Key)
+                } else {
+                    (end, TokenKind::String)
+                }
+            }
+            '-' | '0'..='9' => (number_end(chars, idx), TokenKind::Number),
+            '{' | '}' | '[' | ']' | ',' | ':' => (idx + 1, TokenKind::Punctuation),
+            c if c.is_alphabetic() => {
+                let end = word_end(chars, idx);
+                let word = chars[idx..end].iter().collect::<String>();
+                if matches!(word.as_str(), "true" | "false" | "null") {
+                    (end, TokenKind::Boolean)
+                } else {
+                    idx = end;
+                    continue;
+                }
+            }
+            _ => {
+                idx += 1;
+                continue;
+            }
+        };
+        tokens.push((idx, end, kind));
+        idx = end;
+    }
+    tokens
+}
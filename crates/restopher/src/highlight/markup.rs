@@ -0,0 +1,89 @@
+use super::{string_end, Token, TokenKind};
+
+/// Highlights XML and HTML. Text outside tags is left alone except entities.
+pub fn tokens(chars: &[char]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        if starts_with(chars, idx, "<!--") {
+            let end = find(chars, idx, "-->").unwrap_or(chars.len());
+            tokens.push((idx, end, TokenKind::Comment));
+            idx = end;
+        } else if starts_with(chars, idx, "<![CDATA[") {
+            let end = find(chars, idx, "]]>").unwrap_or(chars.len());
+            tokens.push((idx, end, TokenKind::String));
+            idx = end;
+        } else if chars[idx] == '<' {
+            idx = tag(chars, idx, &mut tokens);
+        } else if chars[idx] == '&' {
+            let end = chars[idx..]
+                .iter()
+                .position(|c| *c == ';' || c.is_whitespace())
+                .map_or(chars.len(), |p| idx + p + 1);
+            tokens.push((idx, end, TokenKind::Keyword));
+            idx = end;
+        } else {
+            idx += 1;
+        }
+    }
+    tokens
+}
+
+/// Highlights a tag starting at `start` and returns the index after it.
+fn tag(chars: &[char], start: usize, tokens: &mut Vec<Token>) -> usize {
+    let mut idx = start + 1;
+    while idx < chars.len() && "/?!".contains(chars[idx]) {
+        idx += 1;
+    }
+    tokens.push((start, idx, TokenKind::Punctuation));
+    let tag_end = name_end(chars, idx);
+    tokens.push((idx, tag_end, TokenKind::Tag));
+    idx = tag_end;
+    while idx < chars.len() {
+        match chars[idx] {
+            '>' => {
+                tokens.push((idx, idx + 1, TokenKind::Punctuation));
+                return idx + 1;
+            }
+            '/' | '?' | '=' => {
+                tokens.push((idx, idx + 1, TokenKind::Punctuation));
+                idx += 1;
+            }
+            q @ ('"' | '\'') => {
+                let end = string_end(chars, idx, q);
+                tokens.push((idx, end, TokenKind::String));
+                idx = end;
+            }
+            c if c.is_whitespace() => idx += 1,
+            _ => {
+                let end = name_end(chars, idx).max(idx + 1);
+                tokens.push((idx, end, TokenKind::Attribute));
+                idx = end;
+            }
+        }
+    }
+    idx
+}
+
+fn name_end(chars: &[char], start: usize) -> usize {
+    let mut idx = start;
+    while idx < chars.len() && !chars[idx].is_whitespace() && !"=/>?\"'".contains(chars[idx]) {
+        idx += 1;
+    }
+    idx
+}
+
+fn starts_with(chars: &[char], idx: usize, pattern: &str) -> bool {
+    chars[idx..]
+        .iter()
+        .copied()
+        .take(pattern.len())
+        .eq(pattern.chars())
+}
+
+/// Index right after the first `pattern` found from `from`.
+fn find(chars: &[char], from: usize, pattern: &str) -> Option<usize> {
+    (from..chars.len())
+        .find(|idx| starts_with(chars, *idx, pattern))
+        .map(|idx| idx + pattern.len())
+}
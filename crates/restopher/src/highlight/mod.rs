@@ -0,0 +1,117 @@
+mod graphql;
+mod json;
+mod markup;
+
+use ratatui::style::Style;
+
+use crate::request::BodyKind;
+use crate::styles::syntax_style;
+use crate::xml;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    Json,
+    Xml,
+    GraphQL,
+    #[default]
+    Text,
+}
+
+impl Language {
+    pub fn from_body_kind(kind: &BodyKind) -> Self {
+        match kind {
+            BodyKind::JSON => Language::Json,
+            BodyKind::XML => Language::Xml,
+            BodyKind::GRAPHQL => Language::GraphQL,
+            BodyKind::TEXT | BodyKind::FORM | BodyKind::MULTIPART => Language::Text,
+        }
+    }
+    pub fn from_content_type(content_type: &str) -> Self {
+        if content_type.contains("json") {
+            Language::Json
+        } else if xml::is_xml_content_type(content_type) || xml::is_html_content_type(content_type)
+        {
+            Language::Xml
+        } else if content_type.contains("graphql") {
+            Language::GraphQL
+        } else {
+            Language::Text
+        }
+    }
+}
+
+/// Kinds of tokens the highlighters tell apart. Colors live in `styles`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Key,
+    String,
+    Number,
+    Boolean,
+    Punctuation,
+    Tag,
+    Attribute,
+    Comment,
+    Keyword,
+    Variable,
+}
+
+/// Returns one style per char of `line`. Lines are highlighted on their own so
+/// only the visible part of a body is ever tokenized; constructs spanning
+/// several lines, such as an XML comment, are colored from where they start.
+pub fn highlight_line(language: Language, line: &str) -> Vec<Style> {
+    let chars = line.chars().collect::<Vec<char>>();
+    let mut styles = vec![Style::default(); chars.len()];
+    let tokens = match language {
+        Language::Json => json::tokens(&chars),
+        Language::Xml => markup::tokens(&chars),
+        Language::GraphQL => graphql::tokens(&chars),
+        Language::Text => return styles,
+    };
+    for (start, end, kind) in tokens {
+        let style = syntax_style(kind);
+        for s in styles.iter_mut().take(end).skip(start) {
+            *s = style;
+        }
+    }
+    styles
+}
+
+/// A highlighted range of chars, as (start, end, kind).
+type Token = (usize, usize, TokenKind);
+
+/// End of the string starting at `start` with `quote`, honoring backslash
+/// escapes. Unterminated strings run to the end of the line.
+fn string_end(chars: &[char], start: usize, quote: char) -> usize {
+    let mut idx = start + 1;
+    while idx < chars.len() {
+        match chars[idx] {
+            '\\' => idx += 2,
+            c if c == quote => return idx + 1,
+            _ => idx += 1,
+        }
+    }
+    chars.len()
+}
+
+fn number_end(chars: &[char], start: usize) -> usize {
+    let mut idx = start + 1;
+    while idx < chars.len() && (chars[idx].is_ascii_digit() || "+-.eE".contains(chars[idx])) {
+        idx += 1;
+    }
+    idx
+}
+
+fn word_end(chars: &[char], start: usize) -> usize {
+    let mut idx = start;
+    while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '_') {
+        idx += 1;
+    }
+    idx
+}
+
+fn next_non_space(chars: &[char], from: usize) -> Option<char> {
+    chars[from.min(chars.len())..]
+        .iter()
+        .find(|c| !c.is_whitespace())
+        .copied()
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use similar::{capture_diff_slices, Algorithm, DiffTag};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Equal,
+    Removed,
+    Added,
+    Modified,
+}
+
+/// One row of a side-by-side diff. A side is `None` when the line only exists
+/// on the other side.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub left: Option<String>,
+    pub right: Option<String>,
+    pub change: Change,
+}
+
+/// Normalizes a body before diffing. JSON bodies are re-serialized, which sorts
+/// object keys, so two responses that only differ in key order compare equal.
+pub fn body_lines(body: &Option<String>) -> Vec<String> {
+    let body = body.clone().unwrap_or_default();
+    let normalized = match serde_json::from_str::<serde_json::Value>(&body) {
+        Ok(value) => serde_json::to_string_pretty(&value).unwrap_or(body),
+        Err(_) => body,
+    };
+    normalized.lines().map(|l| l.to_string()).collect()
+}
+
+pub fn header_lines(headers: &Option<HashMap<String, String>>) -> Vec<String> {
+    let mut lines = headers
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(k, v)| format!("{}: {}", k, v))
+        .collect::<Vec<String>>();
+    lines.sort();
+    lines
+}
+
+pub fn side_by_side(old: &[String], new: &[String]) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for op in capture_diff_slices(Algorithm::Myers, old, new) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        match tag {
+            DiffTag::Equal => {
+                for (o, n) in old_range.zip(new_range) {
+                    rows.push(Row {
+                        left: Some(old[o].clone()),
+                        right: Some(new[n].clone()),
+                        change: Change::Equal,
+                    });
+                }
+            }
+            DiffTag::Delete => {
+                for o in old_range {
+                    rows.push(Row {
+                        left: Some(old[o].clone()),
+                        right: None,
+                        change: Change::Removed,
+                    });
+                }
+            }
+            DiffTag::Insert => {
+                for n in new_range {
+                    rows.push(Row {
+                        left: None,
+                        right: Some(new[n].clone()),
+                        change: Change::Added,
+                    });
+                }
+            }
+            DiffTag::Replace => {
+                let len = old_range.len().max(new_range.len());
+                for i in 0..len {
+                    rows.push(Row {
+                        left: old_range.clone().nth(i).map(|o| old[o].clone()),
+                        right: new_range.clone().nth(i).map(|n| new[n].clone()),
+                        change: Change::Modified,
+                    });
+                }
+            }
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn pairs_lines_side_by_side() {
+        let rows = side_by_side(&lines("a\nb\nc\nd"), &lines("a\nB\nc\ne\nf"));
+        let changes = rows
+            .iter()
+            .map(|r| r.change.clone())
+            .collect::<Vec<Change>>();
+        assert_eq!(
+            changes,
+            vec![
+                Change::Equal,
+                Change::Modified,
+                Change::Equal,
+                Change::Modified,
+                Change::Modified,
+            ]
+        );
+        assert_eq!(rows[1].left.as_deref(), Some("b"));
+        assert_eq!(rows[1].right.as_deref(), Some("B"));
+        assert_eq!(rows[4].left, None);
+        assert_eq!(rows[4].right.as_deref(), Some("f"));
+    }
+
+    #[test]
+    fn marks_added_and_removed_lines() {
+        let rows = side_by_side(&lines("a\nb"), &lines("a"));
+        assert_eq!(rows[1].change, Change::Removed);
+        assert_eq!(rows[1].right, None);
+        let rows = side_by_side(&[], &lines("x"));
+        assert_eq!(rows[0].change, Change::Added);
+    }
+
+    #[test]
+    fn json_bodies_ignore_key_order() {
+        let old = body_lines(&Some("{\"b\": 1, \"a\": [1, 2]}".to_string()));
+        let new = body_lines(&Some("{\"a\":[1,2],\"b\":1}".to_string()));
+        assert_eq!(old, new);
+        assert!(side_by_side(&old, &new)
+            .iter()
+            .all(|r| r.change == Change::Equal));
+        assert_eq!(body_lines(&Some("not json\nat all".to_string())).len(), 2);
+        assert!(body_lines(&None).is_empty());
+    }
+
+    #[test]
+    fn headers_are_sorted() {
+        let headers = HashMap::from([
+            ("server".to_string(), "x".to_string()),
+            ("content-type".to_string(), "text/plain".to_string()),
+        ]);
+        assert_eq!(
+            header_lines(&Some(headers)),
+            vec!["content-type: text/plain", "server: x"]
+        );
+    }
+}
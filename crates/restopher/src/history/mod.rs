@@ -0,0 +1,174 @@
+mod diff;
+mod view;
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::app::Error;
+use crate::config::{data_dir, preferences};
+use crate::request::Response;
+use crate::{COLLECTION_PATH, HISTORY_PATH};
+
+pub use view::HistoryComponent;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Local>,
+    pub environment: String,
+    pub response: Response,
+}
+
+impl HistoryEntry {
+    pub fn status_code(&self) -> i32 {
+        self.response.status_code
+    }
+}
+
+/// Past responses of a single request, newest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History {
+    entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.insert(0, entry);
+        // older entries are dropped first
+        self.entries.truncate(preferences().history_limit);
+    }
+    /// Loads the history stored for the request saved at `collection_path`.
+    /// A request that was never called has no history file, which is not an error.
+    pub fn load(collection_path: &str) -> Result<Self, Error> {
+        let mut path = store_path(collection_path);
+        if !Path::new(&path).exists() && Path::new(&legacy_store_path(collection_path)).exists() {
+            path = legacy_store_path(collection_path);
+        }
+        match fs::File::open(path) {
+            Ok(f) => Ok(serde_json::from_reader(f)?),
+            Err(e) => match e.kind() {
+                std::io::ErrorKind::NotFound => Ok(History::default()),
+                _ => Err(Error::FileOperationsErr(e)),
+            },
+        }
+    }
+    pub fn save(&self, collection_path: &str) -> Result<(), Error> {
+        let path = store_path(collection_path);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut f = fs::File::create(path)?;
+        f.write_all(serde_json::to_vec(self)?.as_slice())?;
+        Ok(())
+    }
+    /// Moves the history of the request saved at `from` along with the
+    /// request, when it is renamed or moved to `to`.
+    pub fn relocate(from: &str, to: &str) -> Result<(), Error> {
+        let to = store_path(to);
+        if let Some(parent) = Path::new(&to).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let from = match store_path(from) {
+            path if Path::new(&path).exists() => path,
+            _ => legacy_store_path(from),
+        };
+        match fs::rename(from, to) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::FileOperationsErr(e)),
+            _ => Ok(()),
+        }
+    }
+    /// Drops the history of a deleted request.
+    pub fn remove(collection_path: &str) -> Result<(), Error> {
+        let _ = fs::remove_file(legacy_store_path(collection_path));
+        match fs::remove_file(store_path(collection_path)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::FileOperationsErr(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// History files live in the data directory, one per saved request, in the
+/// same folders as the request inside the collections folder: the history of
+/// `collections/api/users.rph` is `history/api/users.rph.json`.
+fn store_path(collection_path: &str) -> String {
+    let root = format!("{}/{}/", data_dir(), COLLECTION_PATH);
+    let key = collection_path
+        .strip_prefix(&root)
+        .unwrap_or(collection_path)
+        .trim_start_matches('/');
+    format!("{}/{}/{}.json", data_dir(), HISTORY_PATH, key)
+}
+
+/// Where histories were kept before they mirrored the collections, the path
+/// flattened into one name. Still read so no history is lost.
+fn legacy_store_path(collection_path: &str) -> String {
+    let root = format!("{}/{}/", data_dir(), COLLECTION_PATH);
+    let key: String = collection_path
+        .trim_start_matches(&root)
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}/{}/{}.json", data_dir(), HISTORY_PATH, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(status_code: i32) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Local::now(),
+            environment: String::new(),
+            response: Response {
+                headers: None,
+                body: None,
+                status_code,
+            },
+        }
+    }
+
+    #[test]
+    fn keeps_the_newest_entries_first() {
+        let limit = crate::config::init_for_tests().preferences.history_limit;
+        let mut history = History::default();
+        for status_code in 0..limit as i32 + 5 {
+            history.push(entry(status_code));
+        }
+        assert_eq!(history.entries().len(), limit);
+        assert_eq!(history.entries()[0].status_code(), limit as i32 + 4);
+        assert_eq!(history.entries().last().unwrap().status_code(), 5);
+    }
+
+    #[test]
+    fn history_files_mirror_the_collections() {
+        let data_dir = &crate::config::init_for_tests().data_dir;
+        let request = format!("{}/{}/history-test/users.rph", data_dir, COLLECTION_PATH);
+        assert_eq!(
+            store_path(&request),
+            format!("{}/{}/history-test/users.rph.json", data_dir, HISTORY_PATH)
+        );
+        let _ = fs::remove_dir_all(format!("{}/{}/history-test", data_dir, HISTORY_PATH));
+
+        // histories saved under the flattened names are still found
+        let mut history = History::default();
+        history.push(entry(200));
+        let legacy = legacy_store_path(&request);
+        fs::create_dir_all(Path::new(&legacy).parent().unwrap()).unwrap();
+        fs::write(&legacy, serde_json::to_vec(&history).unwrap()).unwrap();
+        assert_eq!(History::load(&request).unwrap().entries().len(), 1);
+
+        let moved = request.replace("users.rph", "people.rph");
+        History::relocate(&request, &moved).unwrap();
+        assert!(!Path::new(&legacy).exists());
+        assert_eq!(History::load(&moved).unwrap().entries().len(), 1);
+        History::remove(&moved).unwrap();
+        assert!(History::load(&moved).unwrap().entries().is_empty());
+        fs::remove_dir_all(format!("{}/{}/history-test", data_dir, HISTORY_PATH)).unwrap();
+    }
+}
@@ -0,0 +1,276 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+
+use super::diff::{body_lines, header_lines, side_by_side, Change, Row as DiffRow};
+use super::{History, HistoryEntry};
+use crate::components::{default_block, tabs};
+use crate::keys::keys::{Event, Key};
+use crate::layout::centered_rect;
+use crate::styles::status_color;
+use crate::theme;
+
+#[derive(Debug, Clone)]
+enum Section {
+    Body,
+    Headers,
+}
+
+#[derive(Debug)]
+enum Mode {
+    List,
+    Diff {
+        old: usize,
+        new: usize,
+        section: Section,
+        /// The diff of `section`, computed when it is opened.
+        rows: Vec<DiffRow>,
+        scroll: usize,
+    },
+}
+
+/// Popup listing the past responses of the current request. Two entries can be
+/// marked with space and compared side by side with enter.
+pub struct HistoryComponent {
+    selected: usize,
+    marked: Vec<usize>,
+    mode: Mode,
+    /// Rows of the diff that fit the popup, as of the last draw.
+    diff_height: std::cell::Cell<usize>,
+}
+
+impl HistoryComponent {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            marked: Vec::new(),
+            mode: Mode::List,
+            diff_height: std::cell::Cell::new(0),
+        }
+    }
+    /// Returns false once the popup should be closed.
+    pub fn update(&mut self, history: &History, event: &Event) -> bool {
+        let len = history.entries().len();
+        match &mut self.mode {
+            Mode::List => match event.key {
+                Key::Esc => return false,
+                Key::Down if len > 0 => {
+                    self.selected = (self.selected + 1) % len;
+                }
+                Key::Up if len > 0 => {
+                    self.selected = (self.selected + len - 1) % len;
+                }
+                Key::Space if len > 0 => {
+                    if let Some(pos) = self.marked.iter().position(|m| *m == self.selected) {
+                        self.marked.remove(pos);
+                    } else {
+                        self.marked.push(self.selected);
+                        if self.marked.len() > 2 {
+                            self.marked.remove(0);
+                        }
+                    }
+                }
+                Key::Enter => {
+                    // entries are newest first, so the larger index is the older response
+                    let (old, new) = match self.marked.as_slice() {
+                        [a, b] => (*a.max(b), *a.min(b)),
+                        _ => (self.selected + 1, self.selected),
+                    };
+                    if old < len && old != new {
+                        let entries = history.entries();
+                        self.mode = Mode::Diff {
+                            old,
+                            new,
+                            rows: diff_rows(&entries[old], &entries[new], &Section::Body),
+                            section: Section::Body,
+                            scroll: 0,
+                        };
+                    }
+                }
+                _ => (),
+            },
+            Mode::Diff {
+                old,
+                new,
+                section,
+                rows,
+                scroll,
+            } => match event.key {
+                Key::Esc => self.mode = Mode::List,
+                Key::Tab => {
+                    *section = match section {
+                        Section::Body => Section::Headers,
+                        Section::Headers => Section::Body,
+                    };
+                    let entries = history.entries();
+                    *rows = diff_rows(&entries[*old], &entries[*new], section);
+                    *scroll = 0;
+                }
+                Key::Down => {
+                    let last = rows.len().saturating_sub(self.diff_height.get());
+                    *scroll = (*scroll + 1).min(last);
+                }
+                Key::Up => *scroll = scroll.saturating_sub(1),
+                _ => (),
+            },
+        }
+        true
+    }
+    pub fn draw(&self, f: &mut Frame, history: &History, area: Rect) {
+        let rect = centered_rect(80, 80, area);
+        f.render_widget(Clear, rect);
+        match &self.mode {
+            Mode::List => self.draw_list(f, history, rect),
+            Mode::Diff {
+                old,
+                new,
+                section,
+                rows,
+                scroll,
+            } => {
+                let entries = history.entries();
+                let height = draw_diff(
+                    f,
+                    (&entries[*old], &entries[*new]),
+                    section,
+                    rows,
+                    *scroll,
+                    rect,
+                );
+                self.diff_height.set(height);
+            }
+        }
+    }
+    fn draw_list(&self, f: &mut Frame, history: &History, rect: Rect) {
+        let rows = history
+            .entries()
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                Row::new(vec![
+                    Cell::from(if self.marked.contains(&idx) { "*" } else { " " }),
+                    Cell::from(entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    Cell::from(entry.environment.clone()),
+                    Cell::from(Span::styled(
+                        entry.status_code().to_string(),
+                        Style::default().fg(status_color(entry.status_code())),
+                    )),
+                    Cell::from(format!(
+                        "{} B",
+                        entry.response.body.as_ref().map_or(0, |b| b.len())
+                    )),
+                ])
+            })
+            .collect::<Vec<Row>>();
+        let table = Table::new(
+            rows,
+            vec![
+                Constraint::Length(1),
+                Constraint::Length(20),
+                Constraint::Percentage(30),
+                Constraint::Length(8),
+                Constraint::Length(12),
+            ],
+        )
+        .header(Row::new(vec!["", "Time", "Environment", "Status", "Size"]))
+        .block(default_block(
+            Some("History (space: mark, enter: diff, esc: close)"),
+            true,
+        ))
+        .row_highlight_style(
+            Style::default()
+                .fg(theme::get().row_fg)
+                .bg(theme::get().row_bg),
+        );
+        f.render_stateful_widget(
+            table,
+            rect,
+            &mut TableState::new().with_selected(self.selected),
+        );
+    }
+}
+
+fn diff_rows(old: &HistoryEntry, new: &HistoryEntry, section: &Section) -> Vec<DiffRow> {
+    match section {
+        Section::Body => side_by_side(
+            &body_lines(&old.response.body),
+            &body_lines(&new.response.body),
+        ),
+        Section::Headers => side_by_side(
+            &header_lines(&old.response.headers),
+            &header_lines(&new.response.headers),
+        ),
+    }
+}
+
+/// Draws the two responses side by side and returns how many rows fit.
+fn draw_diff(
+    f: &mut Frame,
+    (old, new): (&HistoryEntry, &HistoryEntry),
+    section: &Section,
+    rows: &[DiffRow],
+    scroll: usize,
+    rect: Rect,
+) -> usize {
+    let chunks = Layout::vertical([Constraint::Max(3), Constraint::Fill(1)]).split(rect);
+    f.render_widget(
+        tabs(
+            vec![Span::from("Body"), Span::from("Headers")],
+            Some("Diff (tab: switch, esc: back)"),
+            match section {
+                Section::Body => 0,
+                Section::Headers => 1,
+            },
+            true,
+        ),
+        chunks[0],
+    );
+    let sides = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let height = sides[0].height.saturating_sub(2) as usize;
+    let scroll = scroll.min(rows.len().saturating_sub(height));
+    let visible = rows
+        .iter()
+        .skip(scroll)
+        .take(height)
+        .collect::<Vec<&DiffRow>>();
+    for (side, entry, is_left) in [(sides[0], old, true), (sides[1], new, false)] {
+        let lines = visible
+            .iter()
+            .map(|row| {
+                let (content, changed) = if is_left {
+                    (&row.left, matches!(row.change, Change::Removed))
+                } else {
+                    (&row.right, matches!(row.change, Change::Added))
+                };
+                let diff = &theme::get().diff;
+                let color = match row.change {
+                    Change::Equal => Color::Reset,
+                    Change::Modified => diff.modified,
+                    _ if changed && is_left => diff.removed,
+                    _ if changed => diff.added,
+                    _ => diff.missing,
+                };
+                Line::from(Span::styled(
+                    content.clone().unwrap_or_default(),
+                    Style::default().fg(color),
+                ))
+            })
+            .collect::<Vec<Line>>();
+        let title = format!(
+            "{} [{}] {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.environment,
+            entry.status_code()
+        );
+        f.render_widget(
+            Paragraph::new(lines).block(default_block(Some(&title), false)),
+            side,
+        );
+    }
+    height
+}
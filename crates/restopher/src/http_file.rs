@@ -0,0 +1,743 @@
+use std::fs;
+
+use crate::app::Error;
+use crate::export::{content_type, encode};
+use crate::import::query_pairs;
+use crate::request::{Body, BodyKind, HttpVerb, Mode, Request};
+
+pub const EXTENSIONS: &[&str] = &["http", "rest"];
+
+const SEPARATOR: &str = "###";
+const MULTIPART_BOUNDARY: &str = "----RestopherFormBoundary";
+
+pub fn is_http_file(path: &str) -> bool {
+    EXTENSIONS
+        .iter()
+        .any(|ext| path.ends_with(&format!(".{}", ext)))
+}
+
+/// Requests inside a `.http` file are addressed as `path#index`, the index
+/// counting blocks from the top of the file.
+pub fn location(path: &str, idx: usize) -> String {
+    format!("{}#{}", path, idx)
+}
+
+/// Splits a `path#index` location. Plain paths have no index.
+pub fn split_location(location: &str) -> (&str, Option<usize>) {
+    match location.rsplit_once('#') {
+        Some((path, idx)) if is_http_file(path) => match idx.parse() {
+            Ok(idx) => (path, Some(idx)),
+            Err(_) => (location, None),
+        },
+        _ => (location, None),
+    }
+}
+
+struct Block {
+    /// The `###` line opening the block, missing for a first block that
+    /// starts right at the top of the file.
+    separator: Option<String>,
+    /// Comments, annotations, variables and blank lines before the request.
+    lead: Vec<String>,
+    /// The request line and everything after it, as written.
+    text: Vec<String>,
+    request: Option<Request>,
+}
+
+/// A `.http` / `.rest` file of the JetBrains HTTP client or the VS Code REST
+/// Client. Requests are separated by `###` lines; before a request line there
+/// may be comments, a `# @name` annotation and `@var = value` file variables.
+/// Saving a request rewrites only its block, the rest of the file is kept as
+/// it was.
+pub struct HttpFile {
+    blocks: Vec<Block>,
+    variables: Vec<(String, String)>,
+}
+
+impl HttpFile {
+    pub fn read(path: &str) -> Result<Self, Error> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+    pub fn write(&self, path: &str) -> Result<(), Error> {
+        fs::write(path, self.to_text())?;
+        Ok(())
+    }
+    pub fn parse(content: &str) -> Self {
+        let mut blocks = Vec::new();
+        let mut current = Vec::new();
+        let mut separator = None;
+        for line in content.lines() {
+            if line.trim_start().starts_with(SEPARATOR) {
+                if separator.is_some() || !current.is_empty() {
+                    blocks.push((separator, std::mem::take(&mut current)));
+                }
+                separator = Some(line.to_string());
+            } else {
+                current.push(line.to_string());
+            }
+        }
+        if separator.is_some() || !current.is_empty() {
+            blocks.push((separator, current));
+        }
+
+        let mut variables = Vec::new();
+        let blocks = blocks
+            .into_iter()
+            .map(|(separator, lines)| {
+                let start = lines
+                    .iter()
+                    .position(|l| !is_lead(l))
+                    .unwrap_or(lines.len());
+                let (lead, text) = lines.split_at(start);
+                for line in lead {
+                    if let Some(variable) = file_variable(line) {
+                        variables.push(variable);
+                    }
+                }
+                let request = (!text.is_empty()).then(|| {
+                    let mut req = parse_request(text);
+                    let name = annotated_name(lead)
+                        .or_else(|| separator.as_deref().and_then(separator_name))
+                        .unwrap_or_else(|| default_name(&req));
+                    req.set_name(name);
+                    req
+                });
+                Block {
+                    separator,
+                    lead: lead.to_vec(),
+                    text: text.to_vec(),
+                    request,
+                }
+            })
+            .collect();
+        HttpFile { blocks, variables }
+    }
+    pub fn to_text(&self) -> String {
+        let mut lines = Vec::new();
+        for block in &self.blocks {
+            lines.extend(block.separator.iter().cloned());
+            lines.extend(block.lead.iter().cloned());
+            lines.extend(block.text.iter().cloned());
+        }
+        let mut text = lines.join("\n");
+        text.push('\n');
+        text
+    }
+    /// The requests of the file by block index, each carrying the file
+    /// variables.
+    pub fn requests(&self) -> Vec<(usize, Request)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, block)| {
+                let mut req = clone_request(block.request.as_ref()?);
+                req.set_variables(self.variables.clone());
+                Some((idx, req))
+            })
+            .collect()
+    }
+    pub fn request(&self, idx: usize) -> Option<Request> {
+        self.requests()
+            .into_iter()
+            .find(|(i, _)| *i == idx)
+            .map(|(_, req)| req)
+    }
+    /// Rewrites the block at `idx`, keeping its comments. The variables of
+    /// the request become the file variables.
+    pub fn set_request(&mut self, idx: usize, req: &Request) -> Result<(), Error> {
+        let block = self
+            .blocks
+            .get_mut(idx)
+            .filter(|b| b.request.is_some())
+            .ok_or(Error::NoRequestErr(idx))?;
+        let name = req.name();
+        let named_by_annotation = annotated_name(&block.lead).is_some();
+        if named_by_annotation {
+            for line in block.lead.iter_mut() {
+                if let Some(prefix) = annotation_prefix(line) {
+                    *line = format!("{}@name {}", prefix, name);
+                }
+            }
+        } else if name != default_name(req) {
+            match &mut block.separator {
+                Some(separator) => *separator = format!("{} {}", SEPARATOR, name),
+                None => block.lead.push(format!("# @name {}", name)),
+            }
+        }
+        block.text = render_request(req);
+        block.request = Some(clone_request(req));
+        self.set_variables(idx, req.variables(), true);
+        Ok(())
+    }
+    /// Appends a request as a new block and returns its index.
+    pub fn push_request(&mut self, req: &Request) -> usize {
+        if let Some(last) = self.blocks.last_mut() {
+            if last.text.last().is_some_and(|l| !l.trim().is_empty()) {
+                last.text.push(String::new());
+            }
+        }
+        let name = req.name();
+        self.blocks.push(Block {
+            separator: Some(format!("{} {}", SEPARATOR, name)),
+            lead: Vec::new(),
+            text: render_request(req),
+            request: Some(clone_request(req)),
+        });
+        let idx = self.blocks.len() - 1;
+        self.set_variables(idx, req.variables(), false);
+        idx
+    }
+    /// Rewrites the `@var = value` lines to hold `variables`. A changed
+    /// variable is rewritten where it is defined and a new one is added
+    /// before the request at `idx`; the others are dropped when `exact`.
+    fn set_variables(&mut self, idx: usize, variables: &[(String, String)], exact: bool) {
+        let mut defined = Vec::new();
+        for block in self.blocks.iter_mut() {
+            block.lead.retain_mut(|line| {
+                let Some((name, value)) = file_variable(line) else {
+                    return true;
+                };
+                match variables.iter().find(|(n, _)| *n == name) {
+                    Some((_, wanted)) => {
+                        if *wanted != value {
+                            *line = format!("@{} = {}", name, wanted);
+                        }
+                        defined.push(name);
+                        true
+                    }
+                    None => !exact,
+                }
+            });
+        }
+        for (name, value) in variables {
+            if !defined.contains(name) {
+                self.blocks[idx].lead.push(format!("@{} = {}", name, value));
+            }
+        }
+        self.variables = self
+            .blocks
+            .iter()
+            .flat_map(|b| b.lead.iter().filter_map(|l| file_variable(l)))
+            .collect();
+    }
+    /// Removes the request at `idx`. File variables defined in its block are
+    /// moved to the top of the file, where every request still sees them.
+    pub fn remove_request(&mut self, idx: usize) -> Result<(), Error> {
+        if self.blocks.get(idx).is_none_or(|b| b.request.is_none()) {
+            return Err(Error::NoRequestErr(idx));
+        }
+        let block = self.blocks.remove(idx);
+        let mut kept = block
+            .lead
+            .into_iter()
+            .filter(|l| file_variable(l).is_some())
+            .collect::<Vec<String>>();
+        if !kept.is_empty() {
+            match self.blocks.first_mut() {
+                Some(first) => {
+                    kept.append(&mut first.lead);
+                    first.lead = kept;
+                }
+                None => self.blocks.push(Block {
+                    separator: None,
+                    lead: kept,
+                    text: Vec::new(),
+                    request: None,
+                }),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Request` is not `Clone` because of its history; a block only needs what
+/// is written in the file.
+fn clone_request(req: &Request) -> Request {
+    let mut clone = Request::new(Mode::REST);
+    clone.set_name(req.name());
+    clone.set_verb(req.verb());
+    clone.set_address(req.address());
+    for (k, v, active) in req.headers().unwrap_or_default() {
+        clone.add_to_header(k, v, active);
+    }
+    for (k, v, active) in req.params().unwrap_or_default() {
+        clone.add_to_param(k, v, active);
+    }
+    clone.set_body(req.body());
+    clone
+}
+
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') || line.starts_with("//")
+}
+
+fn is_lead(line: &str) -> bool {
+    line.trim().is_empty() || is_comment(line) || file_variable(line).is_some()
+}
+
+fn file_variable(line: &str) -> Option<(String, String)> {
+    let (name, value) = line.trim().strip_prefix('@')?.split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((name.to_string(), value.trim().to_string()))
+}
+
+/// The comment marker and spacing before `@name`, as in `# @name`.
+fn annotation_prefix(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let marker = if trimmed.starts_with('#') { "#" } else { "//" };
+    let rest = trimmed.strip_prefix(marker)?;
+    let spaces = rest.len() - rest.trim_start().len();
+    rest.trim_start()
+        .starts_with("@name ")
+        .then(|| format!("{}{}", marker, &rest[..spaces]))
+}
+
+fn annotated_name(lead: &[String]) -> Option<String> {
+    lead.iter().find_map(|line| {
+        let prefix = annotation_prefix(line)?;
+        let name = line.trim_start()[prefix.len()..]
+            .trim_start_matches("@name")
+            .trim();
+        (!name.is_empty()).then(|| name.to_string())
+    })
+}
+
+fn separator_name(separator: &str) -> Option<String> {
+    let name = separator.trim_start().trim_start_matches('#').trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// `GET /users`, used when the file gives a request no name.
+fn default_name(req: &Request) -> String {
+    let address = req.address();
+    // the host is often a variable, as in `{{host}}/users`
+    let rest = match address.split_once("://") {
+        Some((_, rest)) => rest,
+        None => address
+            .split_once("}}")
+            .map_or(address.as_str(), |(_, r)| r),
+    };
+    let path = if rest.starts_with('/') {
+        rest
+    } else {
+        rest.find('/').map_or("/", |idx| &rest[idx..])
+    };
+    format!("{} {}", req.verb().to_string(), path)
+}
+
+fn parse_request(text: &[String]) -> Request {
+    let mut req = Request::new(Mode::REST);
+    let mut lines = text.iter().peekable();
+    let mut url = request_line(lines.next().map_or("", |l| l.as_str()), &mut req);
+    while let Some(line) = lines.next_if(|l| {
+        let l = l.trim_start();
+        l.starts_with('?') || l.starts_with('&')
+    }) {
+        url.push_str(line.trim());
+    }
+    let (address, query) = url.split_once('?').unwrap_or((&url, ""));
+    req.set_address(address.to_string());
+    for (k, v) in query_pairs(query) {
+        req.add_to_param(k, v, true);
+    }
+
+    let mut headers = Vec::new();
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        // a commented out header is kept as a disabled one
+        let (line, active) = match line.trim_start().strip_prefix('#') {
+            Some(rest) => (rest, false),
+            None => match line.trim_start().strip_prefix("//") {
+                Some(rest) => (rest, false),
+                None => (line.as_str(), true),
+            },
+        };
+        match line.split_once(':') {
+            Some((k, v)) if !k.trim().is_empty() && !k.trim().contains(' ') => {
+                headers.push((k.trim().to_string(), v.trim().to_string(), active))
+            }
+            _ => (),
+        }
+    }
+    let mut body = lines.map(|l| l.as_str()).collect::<Vec<&str>>();
+    while body.last().is_some_and(|l| l.trim().is_empty()) {
+        body.pop();
+    }
+    let body = body.join("\n");
+
+    let content_type_header = headers
+        .iter()
+        .find(|(k, _, active)| *active && k.eq_ignore_ascii_case("content-type"))
+        .map(|(_, v, _)| v.clone())
+        .unwrap_or_default();
+    let content_type = content_type_header.to_lowercase();
+    let multipart_boundary = content_type
+        .contains("multipart/form-data")
+        .then(|| {
+            content_type_header
+                .split(';')
+                .find_map(|p| p.trim().strip_prefix("boundary="))
+                .map(|b| b.trim_matches('"').to_string())
+        })
+        .flatten();
+    for (k, v, active) in headers {
+        // reqwest writes its own boundary, so the recorded header must go
+        if multipart_boundary.is_some() && k.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+        req.add_to_header(k, v, active);
+    }
+    if body.is_empty() {
+        return req;
+    }
+    let (kind, payload) = if let Some(boundary) = multipart_boundary {
+        (BodyKind::MULTIPART, parse_multipart(&body, &boundary))
+    } else if content_type.contains("json")
+        || (content_type.is_empty() && serde_json::from_str::<serde_json::Value>(&body).is_ok())
+    {
+        (BodyKind::JSON, body)
+    } else if content_type.contains("x-www-form-urlencoded") {
+        let joined = body.lines().map(|l| l.trim()).collect::<String>();
+        let pairs = query_pairs(&joined)
+            .into_iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<String>>();
+        (BodyKind::FORM, pairs.join("\n"))
+    } else if content_type.contains("xml") || content_type.contains("html") {
+        (BodyKind::XML, body)
+    } else if content_type.contains("graphql") {
+        (BodyKind::GRAPHQL, body)
+    } else {
+        (BodyKind::TEXT, body)
+    };
+    req.set_body(Body {
+        kind,
+        payload: Some(payload),
+    });
+    req
+}
+
+/// Reads the method off the request line and returns the URL. A line that is
+/// only a URL is a GET.
+fn request_line(line: &str, req: &mut Request) -> String {
+    let mut words = line.split_whitespace().collect::<Vec<&str>>();
+    if words.len() > 1 && words.last().is_some_and(|w| w.starts_with("HTTP/")) {
+        words.pop();
+    }
+    match words.as_slice() {
+        [method, url @ ..] if !url.is_empty() => {
+            if let Some(verb) = HttpVerb::from_name(method) {
+                req.set_verb(verb);
+            }
+            url.join(" ")
+        }
+        _ => words.join(" "),
+    }
+}
+
+/// Turns a multipart body back into the `key=value` lines of restopher, file
+/// parts given as `< path` becoming `key=@path`.
+fn parse_multipart(body: &str, boundary: &str) -> String {
+    let delimiter = format!("--{}", boundary);
+    body.split(&delimiter)
+        .filter_map(|part| {
+            let part = part.trim_start_matches(['\r', '\n']);
+            if part.starts_with("--") || part.trim().is_empty() {
+                return None;
+            }
+            let (head, value) = part
+                .split_once("\n\n")
+                .or_else(|| part.split_once("\r\n\r\n"))
+                .unwrap_or((part, ""));
+            let name = head
+                .lines()
+                .find(|l| l.to_lowercase().starts_with("content-disposition"))?
+                .split(';')
+                .find_map(|p| p.trim().strip_prefix("name="))?
+                .trim_matches('"')
+                .to_string();
+            let value = value.trim_end_matches(['\r', '\n']);
+            Some(match value.strip_prefix("< ") {
+                Some(path) => format!("{}=@{}", name, path.trim()),
+                None => format!("{}={}", name, value),
+            })
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Writes a request as the lines of a block, ending with a blank line so the
+/// next `###` stands apart. Disabled params are left out; disabled headers are
+/// written commented out.
+fn render_request(req: &Request) -> Vec<String> {
+    let params = req
+        .params()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(k, _, active)| *active && !k.is_empty())
+        .map(|(k, v, _)| format!("{}={}", encode(&k), encode(&v)))
+        .collect::<Vec<String>>();
+    let mut url = req.address();
+    if !params.is_empty() {
+        url.push(if url.contains('?') { '&' } else { '?' });
+        url.push_str(&params.join("&"));
+    }
+    let mut lines = vec![format!("{} {}", req.verb().to_string(), url)];
+
+    let headers = req.headers().unwrap_or_default();
+    for (k, v, active) in &headers {
+        if k.is_empty() {
+            continue;
+        }
+        let marker = if *active { "" } else { "# " };
+        lines.push(format!("{}{}: {}", marker, k, v));
+    }
+
+    let body = req.body();
+    let payload = body.payload.clone().unwrap_or_default();
+    if !payload.trim().is_empty() {
+        let has_content_type = headers
+            .iter()
+            .any(|(k, _, active)| *active && k.eq_ignore_ascii_case("content-type"));
+        let content = match body.kind {
+            BodyKind::MULTIPART => {
+                lines.push(format!(
+                    "Content-Type: multipart/form-data; boundary={}",
+                    MULTIPART_BOUNDARY
+                ));
+                render_multipart(&body)
+            }
+            BodyKind::FORM => body
+                .pairs()
+                .into_iter()
+                .map(|(k, v)| format!("{}={}", encode(&k), encode(&v)))
+                .collect::<Vec<String>>()
+                .join("\n&"),
+            _ => payload,
+        };
+        if !has_content_type && !matches!(body.kind, BodyKind::MULTIPART) {
+            if let Some(content_type) = content_type(&body.kind) {
+                lines.push(format!("Content-Type: {}", content_type));
+            }
+        }
+        lines.push(String::new());
+        lines.extend(content.lines().map(|l| l.to_string()));
+    }
+    lines.push(String::new());
+    lines
+}
+
+fn render_multipart(body: &Body) -> String {
+    let mut lines = Vec::new();
+    for (k, v) in body.pairs() {
+        lines.push(format!("--{}", MULTIPART_BOUNDARY));
+        match v.strip_prefix('@') {
+            Some(path) => {
+                let file_name = path.rsplit('/').next().unwrap_or(path);
+                lines.push(format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"",
+                    k, file_name
+                ));
+                lines.push(String::new());
+                lines.push(format!("< {}", path));
+            }
+            None => {
+                lines.push(format!("Content-Disposition: form-data; name=\"{}\"", k));
+                lines.push(String::new());
+                lines.push(v);
+            }
+        }
+    }
+    lines.push(format!("--{}--", MULTIPART_BOUNDARY));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+@host = https://example.com
+// shared by every request
+
+### List users
+GET {{host}}/users?page=1
+Accept: application/json
+# X-Debug: 1
+
+###
+# @name create-user
+@token = abc
+POST {{host}}/users
+Content-Type: application/json
+
+{\"name\": \"ada\"}
+
+### Delete
+DELETE {{host}}/users/1
+";
+
+    fn names(file: &HttpFile) -> Vec<(usize, String)> {
+        file.requests()
+            .into_iter()
+            .map(|(idx, req)| (idx, req.name()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_and_to_text_round_trip() {
+        let file = HttpFile::parse(FILE);
+        assert_eq!(file.to_text(), FILE);
+        assert_eq!(
+            names(&file),
+            vec![
+                (1, "List users".to_string()),
+                (2, "create-user".to_string()),
+                (3, "Delete".to_string()),
+            ]
+        );
+        assert_eq!(
+            file.variables,
+            vec![
+                ("host".to_string(), "https://example.com".to_string()),
+                ("token".to_string(), "abc".to_string()),
+            ]
+        );
+        let list = file.request(1).unwrap();
+        assert_eq!(list.verb().to_string(), "GET");
+        assert_eq!(list.address(), "{{host}}/users");
+        assert_eq!(
+            list.params(),
+            Some(vec![("page".to_string(), "1".to_string(), true)])
+        );
+        assert_eq!(
+            list.headers(),
+            Some(vec![
+                ("Accept".to_string(), "application/json".to_string(), true),
+                ("X-Debug".to_string(), "1".to_string(), false),
+            ])
+        );
+        let create = file.request(2).unwrap();
+        assert!(matches!(create.body().kind, BodyKind::JSON));
+        assert_eq!(
+            create.body().payload.as_deref(),
+            Some("{\"name\": \"ada\"}")
+        );
+        assert_eq!(create.variables().len(), 2);
+    }
+
+    #[test]
+    fn unnamed_request_gets_a_default_name() {
+        let file = HttpFile::parse("https://example.com/health\n");
+        assert_eq!(names(&file), vec![(0, "GET /health".to_string())]);
+    }
+
+    #[test]
+    fn set_request_keeps_other_blocks() {
+        let mut file = HttpFile::parse(FILE);
+        let mut req = file.request(3).unwrap();
+        req.set_verb(HttpVerb::PUT);
+        file.set_request(3, &req).unwrap();
+        let text = file.to_text();
+        let (before, after) = text.split_once("### Delete\n").unwrap();
+        let (original, _) = FILE.split_once("### Delete\n").unwrap();
+        assert_eq!(before, original);
+        assert!(after.starts_with("PUT {{host}}/users/1\n"));
+        assert_eq!(file.request(3).unwrap().verb().to_string(), "PUT");
+    }
+
+    #[test]
+    fn set_request_renames_with_the_annotation() {
+        let mut file = HttpFile::parse(FILE);
+        let mut req = file.request(2).unwrap();
+        req.set_name("new-user".to_string());
+        file.set_request(2, &req).unwrap();
+        assert!(file.to_text().contains("# @name new-user\n"));
+        assert_eq!(
+            HttpFile::parse(&file.to_text()).request(2).unwrap().name(),
+            "new-user"
+        );
+    }
+
+    #[test]
+    fn set_request_writes_changed_variables_where_defined() {
+        let mut file = HttpFile::parse(FILE);
+        let mut req = file.request(3).unwrap();
+        req.set_variables(vec![
+            ("host".to_string(), "http://localhost".to_string()),
+            ("token".to_string(), "abc".to_string()),
+        ]);
+        file.set_request(3, &req).unwrap();
+        let text = file.to_text();
+        assert!(text.starts_with("@host = http://localhost\n"));
+        assert!(text.contains("@token = abc\n"));
+    }
+
+    #[test]
+    fn remove_request_moves_variables_to_the_top() {
+        let mut file = HttpFile::parse(FILE);
+        file.remove_request(2).unwrap();
+        let text = file.to_text();
+        assert!(!text.contains("create-user"));
+        assert!(text.starts_with("@token = abc\n@host = https://example.com\n"));
+        let parsed = HttpFile::parse(&text);
+        assert_eq!(
+            names(&parsed),
+            vec![(1, "List users".to_string()), (2, "Delete".to_string())]
+        );
+        assert_eq!(parsed.request(2).unwrap().variables().len(), 2);
+        assert!(file.remove_request(0).is_err());
+    }
+
+    #[test]
+    fn multipart_body_round_trip() {
+        let mut req = Request::new(Mode::REST);
+        req.set_name("upload".to_string());
+        req.set_verb(HttpVerb::POST);
+        req.set_address("https://example.com/files".to_string());
+        req.set_body(Body {
+            kind: BodyKind::MULTIPART,
+            payload: Some("title=report\nfile=@/tmp/report.pdf".to_string()),
+        });
+        let mut file = HttpFile::parse("");
+        let idx = file.push_request(&req);
+        let text = file.to_text();
+        assert!(text.contains(&format!(
+            "Content-Type: multipart/form-data; boundary={}",
+            MULTIPART_BOUNDARY
+        )));
+        assert!(text.contains("filename=\"report.pdf\"\n\n< /tmp/report.pdf\n"));
+        let parsed = HttpFile::parse(&text).request(idx).unwrap();
+        assert!(matches!(parsed.body().kind, BodyKind::MULTIPART));
+        assert_eq!(
+            parsed.body().payload.as_deref(),
+            Some("title=report\nfile=@/tmp/report.pdf")
+        );
+        // reqwest sets its own boundary
+        assert_eq!(parsed.headers(), None);
+    }
+
+    #[test]
+    fn form_body_round_trip() {
+        let file = HttpFile::parse(
+            "POST https://example.com/login\n\
+             Content-Type: application/x-www-form-urlencoded\n\
+             \n\
+             user=ada\n\
+             &note=a%20b\n",
+        );
+        let req = file.request(0).unwrap();
+        assert!(matches!(req.body().kind, BodyKind::FORM));
+        assert_eq!(req.body().payload.as_deref(), Some("user=ada\nnote=a b"));
+        let mut rewritten = HttpFile::parse("");
+        rewritten.push_request(&req);
+        let again = HttpFile::parse(&rewritten.to_text()).request(0).unwrap();
+        assert_eq!(again.body().payload, req.body().payload);
+    }
+}
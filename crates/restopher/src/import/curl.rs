@@ -0,0 +1,437 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::{decode, query_pairs};
+use crate::request::{Body, BodyKind, HttpVerb, Mode, Request};
+
+/// Options whose value restopher has no use for but which must not be taken
+/// for the URL.
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-x",
+    "--proxy",
+    "-w",
+    "--write-out",
+    "-c",
+    "--cookie-jar",
+    "--cacert",
+    "--cert",
+    "--key",
+    "--retry",
+    "--resolve",
+    "-T",
+    "--upload-file",
+];
+
+/// Short options that take a value, which may be attached as in `-XPOST`.
+const SHORT_WITH_VALUE: &[char] = &[
+    'X', 'H', 'd', 'F', 'u', 'A', 'b', 'e', 'o', 'm', 'x', 'w', 'c', 'T',
+];
+
+#[derive(Default)]
+struct Command {
+    method: Option<String>,
+    url: Option<String>,
+    headers: Vec<(String, String)>,
+    data: Vec<String>,
+    form: Vec<(String, String)>,
+    get: bool,
+    head: bool,
+}
+
+/// Parses a `curl` command line, as copied from browser devtools or API
+/// documentation, into a new request.
+pub fn from_curl(input: &str) -> Result<Request, String> {
+    let words = split_words(input)?;
+    let mut words = words.into_iter().peekable();
+    if words.peek().is_some_and(|w| w == "curl") {
+        words.next();
+    }
+    let mut command = Command::default();
+    while let Some(word) = words.next() {
+        let (option, attached) = split_option(&word);
+        let Some(option) = option else {
+            command.url = Some(word);
+            continue;
+        };
+        let mut value = || -> Result<String, String> {
+            match attached.clone() {
+                Some(v) => Ok(v),
+                None => words
+                    .next()
+                    .ok_or_else(|| format!("missing value for {}", option)),
+            }
+        };
+        match option.as_str() {
+            "-X" | "--request" => command.method = Some(value()?),
+            "-H" | "--header" => {
+                let header = value()?;
+                // `-H 'X-Empty;'` is how curl sends a header without a value
+                let (k, v) = header
+                    .split_once(':')
+                    .or_else(|| header.strip_suffix(';').map(|k| (k, "")))
+                    .unwrap_or((&header, ""));
+                command
+                    .headers
+                    .push((k.trim().to_string(), v.trim().to_string()));
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii" => {
+                command.data.push(value()?)
+            }
+            "--data-urlencode" => {
+                let data = value()?;
+                command.data.push(match data.split_once('=') {
+                    Some((k, v)) => format!("{}={}", k, crate::export::encode(v)),
+                    None => crate::export::encode(&data),
+                });
+            }
+            "-F" | "--form" | "--form-string" => {
+                let field = value()?;
+                let (k, v) = field.split_once('=').unwrap_or((&field, ""));
+                // drop `;type=...` and similar attributes of file fields
+                let v = if v.starts_with('@') {
+                    v.split(';').next().unwrap_or_default()
+                } else {
+                    v
+                };
+                command.form.push((k.to_string(), v.to_string()));
+            }
+            "-u" | "--user" => {
+                let credentials = value()?;
+                command.headers.push((
+                    "Authorization".to_string(),
+                    format!("Basic {}", STANDARD.encode(credentials)),
+                ));
+            }
+            "--url" => command.url = Some(value()?),
+            "-A" | "--user-agent" => command.headers.push(("User-Agent".to_string(), value()?)),
+            "-b" | "--cookie" => command.headers.push(("Cookie".to_string(), value()?)),
+            "-e" | "--referer" => command.headers.push(("Referer".to_string(), value()?)),
+            "-G" | "--get" => command.get = true,
+            "-I" | "--head" => command.head = true,
+            o if IGNORED_WITH_VALUE.contains(&o) => {
+                value()?;
+            }
+            _ => (),
+        }
+    }
+    command.into_request()
+}
+
+/// Splits `-XPOST` and `--request=POST` into option and attached value.
+fn split_option(word: &str) -> (Option<String>, Option<String>) {
+    if let Some(long) = word.strip_prefix("--") {
+        if long.is_empty() {
+            return (None, None);
+        }
+        return match long.split_once('=') {
+            Some((name, value)) => (Some(format!("--{}", name)), Some(value.to_string())),
+            None => (Some(word.to_string()), None),
+        };
+    }
+    let mut chars = word.chars();
+    match (chars.next(), chars.next()) {
+        (Some('-'), Some(c)) => {
+            let rest = chars.as_str();
+            if !rest.is_empty() && SHORT_WITH_VALUE.contains(&c) {
+                (Some(format!("-{}", c)), Some(rest.to_string()))
+            } else {
+                (Some(word.to_string()), None)
+            }
+        }
+        _ => (None, None),
+    }
+}
+
+impl Command {
+    fn into_request(self) -> Result<Request, String> {
+        let url = self.url.ok_or("no URL found in the cURL command")?;
+        let url = url.split('#').next().unwrap_or_default();
+        let (address, query) = url.split_once('?').unwrap_or((url, ""));
+        let mut req = Request::new(Mode::REST);
+        req.set_address(address.to_string());
+        for (k, v) in query_pairs(query) {
+            req.add_to_param(k, v, true);
+        }
+        let has_body = !self.data.is_empty() || !self.form.is_empty();
+        let verb = match &self.method {
+            Some(m) => HttpVerb::from_name(m).ok_or(format!("unsupported method {}", m))?,
+            None if self.head => HttpVerb::HEAD,
+            None if has_body && !self.get => HttpVerb::POST,
+            None => HttpVerb::GET,
+        };
+        req.set_verb(verb);
+        let content_type = self
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+            .map(|(_, v)| v.to_lowercase());
+        for (k, v) in self.headers {
+            req.add_to_header(k, v, true);
+        }
+        let data = self.data.join("&");
+        if self.get {
+            for (k, v) in query_pairs(&data) {
+                req.add_to_param(k, v, true);
+            }
+        } else if !self.form.is_empty() {
+            req.set_body(Body {
+                kind: BodyKind::MULTIPART,
+                payload: Some(
+                    self.form
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v))
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                ),
+            });
+        } else if !data.is_empty() {
+            req.set_body(body_from_data(data, content_type.as_deref()));
+        }
+        Ok(req)
+    }
+}
+
+fn body_from_data(data: String, content_type: Option<&str>) -> Body {
+    let is_json = serde_json::from_str::<serde_json::Value>(&data).is_ok();
+    let kind = match content_type {
+        Some(ct) if ct.contains("json") => BodyKind::JSON,
+        Some(ct) if ct.contains("xml") => BodyKind::XML,
+        Some(ct) if ct.contains("graphql") => BodyKind::GRAPHQL,
+        Some(ct) if ct.contains("x-www-form-urlencoded") => BodyKind::FORM,
+        Some(_) => BodyKind::TEXT,
+        None if is_json => BodyKind::JSON,
+        None if data.contains('=') && !data.contains(char::is_whitespace) => BodyKind::FORM,
+        None => BodyKind::TEXT,
+    };
+    let payload = match kind {
+        BodyKind::FORM => query_pairs(&data)
+            .into_iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<String>>()
+            .join("\n"),
+        _ => data,
+    };
+    Body {
+        kind,
+        payload: Some(payload),
+    }
+}
+
+/// Splits a shell command line into words the way bash would for the quoting
+/// forms found in copied commands: single and double quotes, `$'...'`,
+/// backslash escapes and `\` line continuations.
+fn split_words(input: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => (),
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    word.push(escaped);
+                    in_word = true;
+                }
+                None => (),
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(e @ ('"' | '\\' | '$' | '`')) => word.push(e),
+                            Some('\n') => (),
+                            Some(e) => {
+                                word.push('\\');
+                                word.push(e);
+                            }
+                            None => return Err("unterminated double quote".to_string()),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated double quote".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                ansi_c_string(&mut chars, &mut word)?;
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Reads the rest of a `$'...'` string, which browsers use for bodies holding
+/// quotes or non-printable characters.
+fn ansi_c_string(
+    chars: &mut std::iter::Peekable<std::str::Chars>,
+    word: &mut String,
+) -> Result<(), String> {
+    loop {
+        match chars.next() {
+            Some('\'') => return Ok(()),
+            Some('\\') => match chars.next() {
+                Some('n') => word.push('\n'),
+                Some('t') => word.push('\t'),
+                Some('r') => word.push('\r'),
+                Some(c @ ('x' | 'u')) => {
+                    let len = if c == 'x' { 2 } else { 4 };
+                    let hex = chars.by_ref().take(len).collect::<String>();
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(decoded) => word.push(decoded),
+                        None => word.push_str(&decode(&format!("%{}", hex))),
+                    }
+                }
+                Some(c) => word.push(c),
+                None => return Err("unterminated $'' string".to_string()),
+            },
+            Some(c) => word.push(c),
+            None => return Err("unterminated $'' string".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(req: &Request, name: &str) -> Option<String> {
+        req.headers()?
+            .into_iter()
+            .find(|(k, _, _)| k == name)
+            .map(|(_, v, _)| v)
+    }
+
+    #[test]
+    fn splits_quoted_words_and_continuation_lines() {
+        let words = split_words(
+            "curl 'https://example.com/a b' \\\n  -H \"X-Quote: \\\"q\\\" \\$HOME\" \\\r\n  --data $'{\\'a\\': \"\\x41\\n\"}'",
+        )
+        .unwrap();
+        assert_eq!(
+            words,
+            vec![
+                "curl",
+                "https://example.com/a b",
+                "-H",
+                "X-Quote: \"q\" $HOME",
+                "--data",
+                "{'a': \"A\n\"}",
+            ]
+        );
+        assert!(split_words("curl 'https://example.com").is_err());
+        assert!(split_words("curl \"https://example.com").is_err());
+    }
+
+    #[test]
+    fn imports_a_devtools_command() {
+        let req = from_curl(
+            "curl 'https://example.com/api/users?page=2' \\\n  -H 'Content-Type: application/json' \\\n  --data-raw '{\"name\":\"ada\"}' \\\n  --compressed",
+        )
+        .unwrap();
+        assert_eq!(req.verb().to_string(), "POST");
+        assert_eq!(req.address(), "https://example.com/api/users");
+        assert_eq!(
+            req.params(),
+            Some(vec![("page".to_string(), "2".to_string(), true)])
+        );
+        assert_eq!(
+            header(&req, "Content-Type").as_deref(),
+            Some("application/json")
+        );
+        assert!(matches!(req.body().kind, BodyKind::JSON));
+        assert_eq!(req.body().payload.as_deref(), Some("{\"name\":\"ada\"}"));
+    }
+
+    #[test]
+    fn reads_attached_and_long_option_values() {
+        let req = from_curl(
+            "curl -XPUT --url=https://example.com -uuser:pass -HAccept:text/plain -d a=1 -d b=2",
+        )
+        .unwrap();
+        assert_eq!(req.verb().to_string(), "PUT");
+        assert_eq!(req.address(), "https://example.com");
+        assert_eq!(
+            header(&req, "Authorization").as_deref(),
+            Some("Basic dXNlcjpwYXNz")
+        );
+        assert_eq!(header(&req, "Accept").as_deref(), Some("text/plain"));
+        assert!(matches!(req.body().kind, BodyKind::FORM));
+        assert_eq!(req.body().payload.as_deref(), Some("a=1\nb=2"));
+    }
+
+    #[test]
+    fn get_moves_data_to_params_and_form_becomes_multipart() {
+        let req = from_curl("curl -G https://example.com --data-urlencode 'q=a b'").unwrap();
+        assert_eq!(req.verb().to_string(), "GET");
+        assert_eq!(
+            req.params(),
+            Some(vec![("q".to_string(), "a b".to_string(), true)])
+        );
+        let req =
+            from_curl("curl https://example.com -F name=ada -F 'file=@photo.png;type=image/png'")
+                .unwrap();
+        assert_eq!(req.verb().to_string(), "POST");
+        assert!(matches!(req.body().kind, BodyKind::MULTIPART));
+        assert_eq!(
+            req.body().payload.as_deref(),
+            Some("name=ada\nfile=@photo.png")
+        );
+    }
+
+    #[test]
+    fn reports_broken_commands() {
+        assert!(from_curl("curl -H 'Accept: */*'").is_err());
+        assert!(from_curl("curl https://example.com -X").is_err());
+        assert!(from_curl("curl -X BREW https://example.com").is_err());
+    }
+
+    #[test]
+    fn keeps_empty_headers_and_params() {
+        let req =
+            from_curl("curl 'https://example.com/api?page=2&flag' -H 'X-Empty;' -H 'X-Unset:'")
+                .unwrap();
+        assert_eq!(
+            req.params(),
+            Some(vec![
+                ("page".to_string(), "2".to_string(), true),
+                ("flag".to_string(), String::new(), true),
+            ])
+        );
+        assert_eq!(header(&req, "X-Empty").as_deref(), Some(""));
+        assert_eq!(header(&req, "X-Unset").as_deref(), Some(""));
+    }
+}
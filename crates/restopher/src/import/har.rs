@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+
+use crate::request::{Body, BodyKind, HttpVerb, Mode, Request, Response};
+
+/// Headers the browser records but reqwest sets on its own, or which are
+/// HTTP/2 pseudo headers.
+const SKIPPED_HEADERS: &[&str] = &["content-length", "host", "connection"];
+
+#[derive(Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    #[serde(default)]
+    time: f64,
+    request: HarRequest,
+    response: HarResponse,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    query_string: Vec<NameValue>,
+    post_data: Option<PostData>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarResponse {
+    #[serde(default)]
+    status: i32,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    content: Option<Content>,
+    #[serde(default)]
+    body_size: i64,
+}
+
+#[derive(Deserialize)]
+struct NameValue {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    mime_type: String,
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<Param>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Param {
+    name: String,
+    #[serde(default)]
+    value: String,
+    /// Set on the file fields of a multipart body.
+    file_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    #[serde(default)]
+    size: i64,
+    text: Option<String>,
+    encoding: Option<String>,
+}
+
+/// One recorded exchange, already converted to a request holding the
+/// recorded response.
+pub struct HarEntry {
+    pub method: String,
+    pub url: String,
+    pub status: i32,
+    pub size: i64,
+    pub time: f64,
+    pub request: Request,
+    pub warnings: Vec<String>,
+}
+
+/// Reads the entries of a HAR export as saved by Chrome or Firefox devtools.
+pub fn parse_har(content: &str) -> Result<Vec<HarEntry>, String> {
+    let har: Har = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    Ok(har.log.entries.into_iter().map(convert_entry).collect())
+}
+
+fn convert_entry(entry: Entry) -> HarEntry {
+    let mut warnings = Vec::new();
+    let location = format!("{} {}", entry.request.method, entry.request.url);
+    let url = entry.request.url.split('#').next().unwrap_or_default();
+    let (address, query) = url.split_once('?').unwrap_or((url, ""));
+
+    let mut req = Request::new(Mode::REST);
+    let path = address
+        .split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|idx| &rest[idx..]))
+        .unwrap_or("/");
+    req.set_name(format!("{} {}", entry.request.method, path));
+    req.set_address(address.to_string());
+    match HttpVerb::from_name(&entry.request.method) {
+        Some(verb) => req.set_verb(verb),
+        None => warnings.push(format!(
+            "{}: method is not supported, imported as GET",
+            location
+        )),
+    }
+    if entry.request.query_string.is_empty() {
+        for (k, v) in super::query_pairs(query) {
+            req.add_to_param(k, v, true);
+        }
+    } else {
+        // browsers record the query string already decoded
+        for param in &entry.request.query_string {
+            req.add_to_param(param.name.clone(), param.value.clone(), true);
+        }
+    }
+    let multipart = entry
+        .request
+        .post_data
+        .as_ref()
+        .is_some_and(|p| p.mime_type.contains("multipart/form-data"));
+    for header in &entry.request.headers {
+        let name = header.name.to_lowercase();
+        if name.starts_with(':') || SKIPPED_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        // the recorded boundary does not match the one reqwest generates
+        if multipart && name == "content-type" {
+            continue;
+        }
+        req.add_to_header(header.name.clone(), header.value.clone(), true);
+    }
+    if let Some(post_data) = &entry.request.post_data {
+        req.set_body(body(post_data, &location, &mut warnings));
+    }
+
+    let content = entry.response.content.as_ref();
+    let body = content.and_then(|c| match (&c.text, c.encoding.as_deref()) {
+        (Some(text), Some("base64")) => match STANDARD
+            .decode(text)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        {
+            Some(decoded) => Some(decoded),
+            None => {
+                warnings.push(format!("{}: binary response body was left out", location));
+                None
+            }
+        },
+        (text, _) => text.clone(),
+    });
+    if content.is_none_or(|c| c.text.is_none()) && entry.response.body_size > 0 {
+        warnings.push(format!(
+            "{}: response body was not saved in the HAR file",
+            location
+        ));
+    }
+    req.set_response(Some(Response {
+        headers: Some(
+            entry
+                .response
+                .headers
+                .iter()
+                .map(|h| (h.name.to_lowercase(), h.value.clone()))
+                .collect::<HashMap<String, String>>(),
+        ),
+        body,
+        status_code: entry.response.status,
+    }));
+
+    HarEntry {
+        method: entry.request.method,
+        url: entry.request.url,
+        status: entry.response.status,
+        size: content
+            .map(|c| c.size)
+            .filter(|s| *s >= 0)
+            .unwrap_or(entry.response.body_size.max(0)),
+        time: entry.time,
+        request: req,
+        warnings,
+    }
+}
+
+fn body(post_data: &PostData, location: &str, warnings: &mut Vec<String>) -> Body {
+    let mime = post_data.mime_type.to_lowercase();
+    let text = post_data.text.clone().unwrap_or_default();
+    let (kind, payload) = if mime.contains("json") {
+        (BodyKind::JSON, text)
+    } else if mime.contains("x-www-form-urlencoded") {
+        let payload = if post_data.params.is_empty() {
+            super::query_pairs(&text)
+                .into_iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<String>>()
+                .join("\n")
+        } else {
+            // the params of a form are recorded encoded, as sent
+            post_data
+                .params
+                .iter()
+                .map(|p| format!("{}={}", super::decode(&p.name), super::decode(&p.value)))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        (BodyKind::FORM, payload)
+    } else if mime.contains("multipart/form-data") {
+        let mut fields = Vec::new();
+        for param in &post_data.params {
+            match &param.file_name {
+                Some(file_name) => {
+                    warnings.push(format!(
+                        "{}: file field {} points to {}, set its path before sending",
+                        location, param.name, file_name
+                    ));
+                    fields.push(format!("{}=@{}", param.name, file_name));
+                }
+                None => fields.push(format!("{}={}", param.name, param.value)),
+            }
+        }
+        (BodyKind::MULTIPART, fields.join("\n"))
+    } else if mime.contains("xml") || mime.contains("html") {
+        (BodyKind::XML, text)
+    } else if mime.contains("graphql") {
+        (BodyKind::GRAPHQL, text)
+    } else {
+        (BodyKind::TEXT, text)
+    };
+    Body {
+        kind,
+        payload: Some(payload),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAR: &str = r#"{"log": {"entries": [
+        {
+            "time": 12.5,
+            "request": {
+                "method": "POST",
+                "url": "https://shop.example.com/api/login?next=%2Fhome&q=100%25#top",
+                "headers": [
+                    {"name": ":authority", "value": "shop.example.com"},
+                    {"name": "Host", "value": "shop.example.com"},
+                    {"name": "Accept", "value": "application/json"}
+                ],
+                "queryString": [
+                    {"name": "next", "value": "/home"},
+                    {"name": "q", "value": "100%"}
+                ],
+                "postData": {
+                    "mimeType": "application/x-www-form-urlencoded",
+                    "text": "user=ada&pass=a%20b",
+                    "params": []
+                }
+            },
+            "response": {
+                "status": 200,
+                "headers": [{"name": "Content-Type", "value": "application/json"}],
+                "content": {"size": 11, "text": "eyJvayI6MX0=", "encoding": "base64"},
+                "bodySize": 11
+            }
+        },
+        {
+            "request": {"method": "PURGE", "url": "https://shop.example.com/cache"},
+            "response": {"status": 204, "bodySize": 10}
+        },
+        {
+            "request": {
+                "method": "POST",
+                "url": "https://shop.example.com/api/search",
+                "postData": {
+                    "mimeType": "application/x-www-form-urlencoded; charset=UTF-8",
+                    "params": [{"name": "q", "value": "a%2Bb+c"}]
+                }
+            },
+            "response": {"status": 200}
+        },
+        {
+            "request": {
+                "method": "POST",
+                "url": "https://shop.example.com/api/upload",
+                "headers": [
+                    {"name": "Content-Type", "value": "multipart/form-data; boundary=xyz"}
+                ],
+                "postData": {
+                    "mimeType": "multipart/form-data; boundary=xyz",
+                    "params": [
+                        {"name": "title", "value": "50% off"},
+                        {"name": "avatar", "fileName": "me.png", "contentType": "image/png"}
+                    ]
+                }
+            },
+            "response": {"status": 201}
+        }
+    ]}}"#;
+
+    #[test]
+    fn converts_entries() {
+        let entries = parse_har(HAR).unwrap();
+        assert_eq!(entries.len(), 4);
+
+        let login = &entries[0];
+        assert_eq!(login.status, 200);
+        assert_eq!(login.size, 11);
+        assert_eq!(login.request.name(), "POST /api/login");
+        assert_eq!(
+            login.request.address(),
+            "https://shop.example.com/api/login"
+        );
+        assert_eq!(
+            login.request.params(),
+            Some(vec![
+                ("next".to_string(), "/home".to_string(), true),
+                ("q".to_string(), "100%".to_string(), true),
+            ])
+        );
+        assert_eq!(
+            login.request.headers(),
+            Some(vec![(
+                "Accept".to_string(),
+                "application/json".to_string(),
+                true
+            )])
+        );
+        assert!(matches!(login.request.body().kind, BodyKind::FORM));
+        assert_eq!(
+            login.request.body().payload.as_deref(),
+            Some("user=ada\npass=a b")
+        );
+        let response = login.request.response().unwrap();
+        assert_eq!(response.body.as_deref(), Some("{\"ok\":1}"));
+        assert!(login.warnings.is_empty());
+
+        let purge = &entries[1];
+        assert_eq!(purge.request.verb().to_string(), "GET");
+        assert_eq!(purge.warnings.len(), 2);
+    }
+
+    #[test]
+    fn only_urlencoded_params_are_decoded() {
+        let entries = parse_har(HAR).unwrap();
+        let search = &entries[2];
+        assert!(matches!(search.request.body().kind, BodyKind::FORM));
+        assert_eq!(search.request.body().payload.as_deref(), Some("q=a+b c"));
+
+        let upload = &entries[3];
+        assert!(matches!(upload.request.body().kind, BodyKind::MULTIPART));
+        assert_eq!(
+            upload.request.body().payload.as_deref(),
+            Some("title=50% off\navatar=@me.png")
+        );
+        assert_eq!(upload.request.headers(), None);
+        assert_eq!(upload.warnings.len(), 1);
+        assert!(upload.warnings[0].contains("me.png"));
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(parse_har("{}").is_err());
+        assert!(parse_har("{\"log\": {}}").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+mod curl;
+mod har;
+mod openapi;
+mod postman;
+
+use std::path::{Path, PathBuf};
+
+pub use curl::from_curl;
+pub use har::{parse_har, HarEntry};
+pub use openapi::import_openapi;
+pub use postman::{
+    import_collection as import_postman_collection,
+    import_environment as import_postman_environment,
+};
+
+/// Summary of a file import: how much was brought over and what could not be
+/// mapped onto restopher's model.
+#[derive(Debug, Default)]
+pub struct Report {
+    pub title: String,
+    pub imported: usize,
+    notes: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    pub fn new(title: String) -> Self {
+        Report {
+            title,
+            ..Default::default()
+        }
+    }
+    pub fn note(&mut self, note: String) {
+        self.notes.push(note);
+    }
+    pub fn warn(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("{} items imported", self.imported)];
+        lines.extend(self.notes.iter().cloned());
+        lines.push(String::new());
+        if self.warnings.is_empty() {
+            lines.push("Everything was mapped.".to_string());
+        } else {
+            lines.push(format!(
+                "{} things could not be mapped:",
+                self.warnings.len()
+            ));
+            lines.extend(self.warnings.iter().map(|w| format!("  - {}", w)));
+        }
+        lines
+    }
+}
+
+/// Makes a name safe to use as a file name.
+pub fn file_name(name: &str) -> String {
+    let name = name
+        .trim()
+        .replace(|c: char| c == '/' || c == '\\' || c.is_control(), "_");
+    if name.is_empty() {
+        "unnamed".to_string()
+    } else {
+        name
+    }
+}
+
+/// `dir/name + extension`, numbered when the path is already taken.
+pub fn unique_path(dir: &Path, name: &str, extension: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}{}", name, extension));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}){}", name, n, extension));
+        n += 1;
+    }
+    path
+}
+
+/// Decodes a percent-encoded query component, `+` standing for a space.
+pub fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'+' => result.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(idx + 1..idx + 3)
+                    .and_then(|h| std::str::from_utf8(h).ok())
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                match hex {
+                    Some(b) => {
+                        result.push(b);
+                        idx += 2;
+                    }
+                    None => result.push(b'%'),
+                }
+            }
+            b => result.push(b),
+        }
+        idx += 1;
+    }
+    String::from_utf8_lossy(&result).to_string()
+}
+
+/// Splits a query string into decoded key value pairs.
+pub fn query_pairs(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| match p.split_once('=') {
+            Some((k, v)) => (decode(k), decode(v)),
+            None => (decode(p), String::new()),
+        })
+        .collect()
+}
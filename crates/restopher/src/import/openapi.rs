@@ -0,0 +1,792 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use super::{file_name, unique_path, Report};
+use crate::environments::Environment;
+use crate::request::{Body, BodyKind, HttpVerb, Mode, Request};
+
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Recursive schemas are expanded this deep before falling back to `null`.
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+/// Converts an OpenAPI 3 or Swagger 2 document, JSON or YAML, into a folder
+/// holding a sub folder per tag and one `.rph` per operation. Servers become
+/// the `baseUrl` variable of an environment named after the API.
+pub fn import_openapi(content: &str, root: &Path) -> Result<(Report, Option<Environment>), String> {
+    let spec = parse(content)?;
+    let version = match (spec["openapi"].as_str(), spec["swagger"].as_str()) {
+        (Some(v), _) if v.starts_with('3') => Version::OpenApi3,
+        (_, Some(v)) if v.starts_with('2') => Version::Swagger2,
+        _ => return Err("not an OpenAPI 3 or Swagger 2 document".to_string()),
+    };
+    let title = spec["info"]["title"].as_str().unwrap_or("API").to_string();
+    let mut report = Report::new(format!("OpenAPI \"{}\"", title));
+    let dir = unique_path(root, &file_name(&title), "");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let paths = spec["paths"].as_object().cloned().unwrap_or_default();
+    for (path, item) in &paths {
+        let item = resolve(&spec, item);
+        for method in METHODS {
+            let Some(operation) = item.get(*method) else {
+                continue;
+            };
+            let location = format!("{} {}", method.to_uppercase(), path);
+            let Some(verb) = HttpVerb::from_name(method) else {
+                report.warn(format!("{}: method is not supported, skipped", location));
+                continue;
+            };
+            let operation = Operation {
+                spec: &spec,
+                version,
+                path,
+                verb,
+                item,
+                operation,
+                location: &location,
+            };
+            let req = operation.to_request(&mut report);
+            let folder = match operation.operation["tags"].as_array() {
+                Some(tags) if !tags.is_empty() => {
+                    if tags.len() > 1 {
+                        report.note(format!(
+                            "{}: filed under its first tag of {}",
+                            location,
+                            tags.len()
+                        ));
+                    }
+                    dir.join(file_name(tags[0].as_str().unwrap_or_default()))
+                }
+                _ => dir.clone(),
+            };
+            fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+            let path = unique_path(&folder, &file_name(&req.name()), ".rph");
+            let content = req.to_rph().map_err(|e| e.to_string())?;
+            fs::write(path, content).map_err(|e| e.to_string())?;
+            report.imported += 1;
+        }
+    }
+
+    let servers = match version {
+        Version::OpenApi3 => servers(&spec),
+        Version::Swagger2 => swagger_host(&spec).into_iter().collect(),
+    };
+    let environment = match servers.split_first() {
+        Some((base_url, others)) => {
+            let mut env = Environment::new(file_name(&title));
+            env.envs.insert("baseUrl".to_string(), base_url.clone());
+            for url in others {
+                report.note(format!("other server {} was not used", url));
+            }
+            report.note(format!("servers saved as environment \"{}\"", env.name));
+            Some(env)
+        }
+        None => {
+            report.warn("no server given, set {{baseUrl}} yourself".to_string());
+            None
+        }
+    };
+    report.note(format!("saved to {}", dir.to_string_lossy()));
+    Ok((report, environment))
+}
+
+#[derive(Clone, Copy)]
+enum Version {
+    OpenApi3,
+    Swagger2,
+}
+
+/// JSON is a subset of YAML, but trying it first gives better errors for
+/// the common case.
+fn parse(content: &str) -> Result<Value, String> {
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        return Ok(value);
+    }
+    let yaml = serde_yaml::from_str::<serde_yaml::Value>(content).map_err(|e| e.to_string())?;
+    Ok(yaml_to_json(yaml))
+}
+
+/// YAML allows non-string keys, such as the status codes of `responses`,
+/// which JSON objects do not.
+fn yaml_to_json(yaml: serde_yaml::Value) -> Value {
+    match yaml {
+        serde_yaml::Value::Null => Value::Null,
+        serde_yaml::Value::Bool(b) => Value::Bool(b),
+        serde_yaml::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                json!(i)
+            } else if let Some(u) = n.as_u64() {
+                json!(u)
+            } else {
+                json!(n.as_f64())
+            }
+        }
+        serde_yaml::Value::String(s) => Value::String(s),
+        serde_yaml::Value::Sequence(seq) => {
+            Value::Array(seq.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(k, v)| {
+                    let key = match yaml_to_json(k) {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(v))
+                })
+                .collect::<Map<String, Value>>(),
+        ),
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+/// Follows local `$ref`s such as `#/components/schemas/User`. References to
+/// other documents are left as they are.
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+            Some(pointer) => match spec.pointer(pointer) {
+                Some(target) => value = target,
+                None => return value,
+            },
+            None => return value,
+        }
+    }
+    value
+}
+
+/// OpenAPI 3 server URLs with their variables replaced by the defaults.
+fn servers(spec: &Value) -> Vec<String> {
+    let Some(servers) = spec["servers"].as_array() else {
+        return Vec::new();
+    };
+    servers
+        .iter()
+        .filter_map(|server| {
+            let mut url = server["url"].as_str()?.to_string();
+            if let Some(variables) = server["variables"].as_object() {
+                for (name, variable) in variables {
+                    let default = variable["default"].as_str().unwrap_or_default();
+                    url = url.replace(&format!("{{{}}}", name), default);
+                }
+            }
+            Some(url.trim_end_matches('/').to_string())
+        })
+        .collect()
+}
+
+fn swagger_host(spec: &Value) -> Option<String> {
+    let host = spec["host"].as_str()?;
+    let scheme = spec["schemes"]
+        .as_array()
+        .and_then(|s| s.first())
+        .and_then(|s| s.as_str())
+        .unwrap_or("https");
+    let base_path = spec["basePath"].as_str().unwrap_or_default();
+    Some(format!(
+        "{}://{}{}",
+        scheme,
+        host,
+        base_path.trim_end_matches('/')
+    ))
+}
+
+struct Operation<'a> {
+    spec: &'a Value,
+    version: Version,
+    path: &'a str,
+    verb: HttpVerb,
+    item: &'a Value,
+    operation: &'a Value,
+    location: &'a str,
+}
+
+impl<'a> Operation<'a> {
+    fn to_request(&self, report: &mut Report) -> Request {
+        let mut req = Request::new(Mode::REST);
+        let name = self.operation["summary"]
+            .as_str()
+            .or(self.operation["operationId"].as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| self.location.to_string());
+        req.set_name(name);
+        req.set_verb(self.verb.clone());
+
+        let mut address = format!("{{{{baseUrl}}}}{}", self.path);
+        let mut form = Vec::new();
+        for param in self.parameters() {
+            let name = param["name"].as_str().unwrap_or_default().to_string();
+            let required = param["required"].as_bool().unwrap_or(false);
+            match param["in"].as_str().unwrap_or_default() {
+                "path" => {
+                    address =
+                        address.replace(&format!("{{{}}}", name), &self.placeholder(param, &name));
+                }
+                "query" => {
+                    let value = self.placeholder(param, &name);
+                    req.add_to_param(name, value, required);
+                }
+                "header" => {
+                    let value = self.placeholder(param, &name);
+                    req.add_to_header(name, value, required);
+                }
+                "body" => {
+                    let example = example(self.spec, &param["schema"]);
+                    req.set_body(Body {
+                        kind: BodyKind::JSON,
+                        payload: Some(pretty(&example)),
+                    });
+                }
+                "formData" => {
+                    let value = match param["type"].as_str() {
+                        Some("file") => "@".to_string(),
+                        _ => self.placeholder(param, &name),
+                    };
+                    form.push((name, value, param["type"] == "file"));
+                }
+                "cookie" => report.warn(format!(
+                    "{}: cookie parameter {} was not imported",
+                    self.location, name
+                )),
+                other => report.warn(format!(
+                    "{}: {} parameter {} was not imported",
+                    self.location, other, name
+                )),
+            }
+        }
+        req.set_address(address);
+        if !form.is_empty() {
+            let multipart = form.iter().any(|(_, _, file)| *file)
+                || self.consumes().iter().any(|c| c == "multipart/form-data");
+            req.set_body(Body {
+                kind: if multipart {
+                    BodyKind::MULTIPART
+                } else {
+                    BodyKind::FORM
+                },
+                payload: Some(
+                    form.iter()
+                        .map(|(k, v, _)| format!("{}={}", k, v))
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                ),
+            });
+        }
+        if let Version::OpenApi3 = self.version {
+            self.request_body(&mut req, report);
+        }
+        self.security(&mut req, report);
+        if self.operation.get("callbacks").is_some() {
+            report.warn(format!("{}: callbacks were not imported", self.location));
+        }
+        req
+    }
+
+    /// Path item parameters, overridden by the operation's own of the same
+    /// name and location.
+    fn parameters(&self) -> Vec<&'a Value> {
+        let list = |v: &'a Value| -> Vec<&'a Value> {
+            v.as_array()
+                .map(|params| params.iter().map(|p| resolve(self.spec, p)).collect())
+                .unwrap_or_default()
+        };
+        let own = list(&self.operation["parameters"]);
+        let mut params = list(&self.item["parameters"])
+            .into_iter()
+            .filter(|p| {
+                !own.iter()
+                    .any(|o| o["name"] == p["name"] && o["in"] == p["in"])
+            })
+            .collect::<Vec<&'a Value>>();
+        params.extend(own);
+        params
+    }
+
+    /// The example of a parameter when the spec has one, `{{name}}` otherwise.
+    fn placeholder(&self, param: &Value, name: &str) -> String {
+        let schema = resolve(self.spec, &param["schema"]);
+        let given = [
+            &param["example"],
+            &param["x-example"],
+            &param["default"],
+            &schema["example"],
+            &schema["default"],
+        ]
+        .into_iter()
+        .find(|v| !v.is_null())
+        .or_else(|| param["enum"].get(0))
+        .or_else(|| schema["enum"].get(0));
+        match given {
+            Some(Value::String(s)) => s.clone(),
+            Some(v) => v.to_string(),
+            None => format!("{{{{{}}}}}", name),
+        }
+    }
+
+    fn consumes(&self) -> Vec<String> {
+        let consumes = match self.operation["consumes"].as_array() {
+            Some(c) => c,
+            None => match self.spec["consumes"].as_array() {
+                Some(c) => c,
+                None => return Vec::new(),
+            },
+        };
+        consumes
+            .iter()
+            .filter_map(|c| c.as_str())
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    fn request_body(&self, req: &mut Request, report: &mut Report) {
+        let body = resolve(self.spec, &self.operation["requestBody"]);
+        let Some(content) = body["content"].as_object() else {
+            return;
+        };
+        let preferred = [
+            "json",
+            "x-www-form-urlencoded",
+            "multipart/form-data",
+            "xml",
+            "graphql",
+            "text",
+        ];
+        let Some((media_type, media)) = preferred
+            .iter()
+            .find_map(|p| content.iter().find(|(m, _)| m.contains(p)))
+            .or_else(|| content.iter().next())
+        else {
+            return;
+        };
+        let example = match (&media["example"], media["examples"].as_object()) {
+            (example, _) if !example.is_null() => example.clone(),
+            (_, Some(examples)) if !examples.is_empty() => {
+                let first = examples.values().next().unwrap();
+                resolve(self.spec, first)["value"].clone()
+            }
+            _ => example(self.spec, &media["schema"]),
+        };
+        let (kind, payload) = if media_type.contains("json") {
+            (BodyKind::JSON, pretty(&example))
+        } else if media_type.contains("x-www-form-urlencoded") {
+            (BodyKind::FORM, form_lines(&example))
+        } else if media_type.contains("multipart/form-data") {
+            (BodyKind::MULTIPART, form_lines(&example))
+        } else if media_type.contains("xml") {
+            report.warn(format!(
+                "{}: XML body left for you to fill in",
+                self.location
+            ));
+            (BodyKind::XML, String::new())
+        } else if media_type.contains("graphql") {
+            (BodyKind::GRAPHQL, scalar(&example))
+        } else {
+            (BodyKind::TEXT, scalar(&example))
+        };
+        if content.len() > 1 {
+            report.note(format!(
+                "{}: body imported as {} of {} media types",
+                self.location,
+                media_type,
+                content.len()
+            ));
+        }
+        // the defaults restopher sends for these kinds may not be what the
+        // API expects, e.g. `application/soap+xml`
+        if matches!(kind, BodyKind::XML | BodyKind::GRAPHQL | BodyKind::TEXT) {
+            req.add_to_header("Content-Type".to_string(), media_type.clone(), true);
+        }
+        req.set_body(Body {
+            kind,
+            payload: Some(payload),
+        });
+    }
+
+    /// Applies the first security requirement as a header or query param with
+    /// a placeholder for the secret.
+    fn security(&self, req: &mut Request, report: &mut Report) {
+        let requirements = match self.operation.get("security") {
+            Some(s) => s,
+            None => &self.spec["security"],
+        };
+        let Some(requirement) = requirements
+            .as_array()
+            .and_then(|r| r.first())
+            .and_then(|r| r.as_object())
+        else {
+            return;
+        };
+        let schemes = match self.version {
+            Version::OpenApi3 => &self.spec["components"]["securitySchemes"],
+            Version::Swagger2 => &self.spec["securityDefinitions"],
+        };
+        for name in requirement.keys() {
+            let scheme = resolve(self.spec, &schemes[name]);
+            let variable = format!("{{{{{}}}}}", name);
+            match (
+                scheme["type"].as_str().unwrap_or_default(),
+                scheme["scheme"].as_str().map(|s| s.to_lowercase()),
+            ) {
+                ("http", Some(s)) if s == "bearer" => req.add_to_header(
+                    "Authorization".to_string(),
+                    format!("Bearer {}", variable),
+                    true,
+                ),
+                ("http", Some(s)) if s == "basic" => req.add_to_header(
+                    "Authorization".to_string(),
+                    format!("Basic {}", variable),
+                    true,
+                ),
+                ("basic", _) => req.add_to_header(
+                    "Authorization".to_string(),
+                    format!("Basic {}", variable),
+                    true,
+                ),
+                ("apiKey", _) => {
+                    let key = scheme["name"].as_str().unwrap_or(name).to_string();
+                    match scheme["in"].as_str() {
+                        Some("query") => req.add_to_param(key, variable, true),
+                        Some("header") => req.add_to_header(key, variable, true),
+                        _ => report.warn(format!(
+                            "{}: cookie API key {} was not imported",
+                            self.location, name
+                        )),
+                    }
+                }
+                (kind, _) => report.warn(format!(
+                    "{}: {} security scheme {} is not supported",
+                    self.location,
+                    if kind.is_empty() { "unknown" } else { kind },
+                    name
+                )),
+            }
+        }
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        v => v.to_string(),
+    }
+}
+
+/// An object example as the `key=value` lines of a form body.
+fn form_lines(value: &Value) -> String {
+    value
+        .as_object()
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, scalar(v)))
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+/// Synthesizes an example value from a schema, preferring what the schema
+/// itself gives: `example`, `default`, then the first `enum` value.
+fn example(spec: &Value, schema: &Value) -> Value {
+    example_within(spec, schema, &mut Vec::new(), 0)
+}
+
+/// `expanding` holds the `$ref`s being expanded, so a recursive schema such
+/// as a tree node stops at its first repetition.
+fn example_within<'a>(
+    spec: &'a Value,
+    schema: &'a Value,
+    expanding: &mut Vec<&'a str>,
+    depth: usize,
+) -> Value {
+    if depth > MAX_SCHEMA_DEPTH {
+        return Value::Null;
+    }
+    if let Some(reference) = schema["$ref"].as_str() {
+        if expanding.contains(&reference) {
+            return Value::Null;
+        }
+        expanding.push(reference);
+        let value = example_within(spec, resolve(spec, schema), expanding, depth + 1);
+        expanding.pop();
+        return value;
+    }
+    for given in [&schema["example"], &schema["default"]] {
+        if !given.is_null() {
+            return given.clone();
+        }
+    }
+    if let Some(first) = schema["enum"].get(0) {
+        return first.clone();
+    }
+    if let Some(all_of) = schema["allOf"].as_array() {
+        let mut merged = Map::new();
+        for part in all_of {
+            match example_within(spec, part, expanding, depth + 1) {
+                Value::Object(fields) => merged.extend(fields),
+                other => return other,
+            }
+        }
+        return Value::Object(merged);
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema[key].get(0) {
+            return example_within(spec, first, expanding, depth + 1);
+        }
+    }
+    let kind = match &schema["type"] {
+        Value::String(t) => t.as_str(),
+        // OpenAPI 3.1 allows a list such as `["string", "null"]`
+        Value::Array(types) => types
+            .iter()
+            .filter_map(|t| t.as_str())
+            .find(|t| *t != "null")
+            .unwrap_or("null"),
+        _ if schema.get("properties").is_some() => "object",
+        _ if schema.get("items").is_some() => "array",
+        _ => "",
+    };
+    match kind {
+        "object" => {
+            let mut fields = Map::new();
+            if let Some(properties) = schema["properties"].as_object() {
+                for (name, property) in properties {
+                    if resolve(spec, property)["readOnly"] == true {
+                        continue;
+                    }
+                    let value = example_within(spec, property, expanding, depth + 1);
+                    if !value.is_null() {
+                        fields.insert(name.clone(), value);
+                    }
+                }
+            }
+            Value::Object(fields)
+        }
+        "array" => match example_within(spec, &schema["items"], expanding, depth + 1) {
+            Value::Null => json!([]),
+            item => json!([item]),
+        },
+        "integer" => json!(0),
+        "number" => json!(0.0),
+        "boolean" => json!(false),
+        "string" => json!(match schema["format"].as_str() {
+            Some("date-time") => "2024-01-01T00:00:00Z",
+            Some("date") => "2024-01-01",
+            Some("email") => "user@example.com",
+            Some("uuid") => "00000000-0000-0000-0000-000000000000",
+            Some("uri") | Some("url") => "https://example.com",
+            Some("binary") => "@",
+            _ => "string",
+        }),
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r##"
+openapi: 3.0.3
+info:
+  title: Pet Store
+servers:
+  - url: https://{region}.pets.example.com/v1/
+    variables:
+      region:
+        default: eu
+  - url: http://localhost:8080
+security:
+  - apiKey: []
+paths:
+  /pets/{petId}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    put:
+      summary: Update pet
+      tags: [pets]
+      parameters:
+        - name: X-Trace
+          in: header
+          schema:
+            type: string
+      requestBody:
+        $ref: '#/components/requestBodies/Pet'
+      security:
+        - bearer: []
+  /health:
+    get:
+      operationId: health
+      parameters:
+        - name: verbose
+          in: query
+          required: true
+          schema:
+            enum: [full, short]
+components:
+  parameters:
+    PetId:
+      name: petId
+      in: path
+      required: true
+      schema:
+        type: integer
+        example: 42
+  requestBodies:
+    Pet:
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+  schemas:
+    Pet:
+      allOf:
+        - $ref: '#/components/schemas/Named'
+        - type: object
+          properties:
+            id:
+              type: integer
+              readOnly: true
+            born:
+              type: string
+              format: date
+            parent:
+              $ref: '#/components/schemas/Pet'
+            tags:
+              type: array
+              items:
+                type: string
+    Named:
+      type: object
+      properties:
+        name:
+          type: string
+          example: Rex
+  securitySchemes:
+    bearer:
+      type: http
+      scheme: bearer
+    apiKey:
+      type: apiKey
+      in: query
+      name: key
+"##;
+
+    fn temp_root(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("restopher-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn read(path: std::path::PathBuf) -> Request {
+        Request::from_rph(&fs::read_to_string(&path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn resolves_local_refs() {
+        let spec = parse(SPEC).unwrap();
+        let param = json!({"$ref": "#/components/parameters/PetId"});
+        assert_eq!(resolve(&spec, &param)["name"], "petId");
+        let missing = json!({"$ref": "#/components/parameters/Nope"});
+        assert_eq!(resolve(&spec, &missing), &missing);
+        let remote = json!({"$ref": "other.yaml#/Pet"});
+        assert_eq!(resolve(&spec, &remote), &remote);
+    }
+
+    #[test]
+    fn examples_follow_refs_and_stop_on_recursion() {
+        let spec = parse(SPEC).unwrap();
+        let pet = example(&spec, &json!({"$ref": "#/components/schemas/Pet"}));
+        assert_eq!(
+            pet,
+            json!({"name": "Rex", "born": "2024-01-01", "tags": ["string"]})
+        );
+    }
+
+    #[test]
+    fn imports_operations_with_refs_and_security() {
+        let root = temp_root("openapi");
+        let (report, env) = import_openapi(SPEC, &root).unwrap();
+        assert_eq!(report.imported, 2);
+        let dir = root.join("Pet Store");
+
+        let env = env.unwrap();
+        assert_eq!(env.name, "Pet Store");
+        assert_eq!(
+            env.envs.get("baseUrl").map(String::as_str),
+            Some("https://eu.pets.example.com/v1")
+        );
+
+        let update = read(dir.join("pets/Update pet.rph"));
+        assert_eq!(update.verb().to_string(), "PUT");
+        assert_eq!(update.address(), "{{baseUrl}}/pets/42");
+        assert_eq!(
+            update.headers(),
+            Some(vec![
+                ("X-Trace".to_string(), "{{X-Trace}}".to_string(), false),
+                (
+                    "Authorization".to_string(),
+                    "Bearer {{bearer}}".to_string(),
+                    true
+                ),
+            ])
+        );
+        assert!(matches!(update.body().kind, BodyKind::JSON));
+        let body: Value = serde_json::from_str(&update.body().payload.unwrap()).unwrap();
+        assert_eq!(body["name"], "Rex");
+
+        let health = read(dir.join("health.rph"));
+        assert_eq!(
+            health.params(),
+            Some(vec![
+                ("verbose".to_string(), "full".to_string(), true),
+                ("key".to_string(), "{{apiKey}}".to_string(), true),
+            ])
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn imports_swagger_2() {
+        let root = temp_root("swagger");
+        let spec = r#"{
+            "swagger": "2.0",
+            "info": {"title": "Legacy/API"},
+            "host": "legacy.example.com",
+            "basePath": "/api/",
+            "schemes": ["http"],
+            "paths": {"/login": {"post": {
+                "consumes": ["application/x-www-form-urlencoded"],
+                "parameters": [
+                    {"name": "user", "in": "formData", "type": "string", "default": "ada"},
+                    {"name": "session", "in": "cookie", "type": "string"}
+                ]
+            }}}
+        }"#;
+        let (report, env) = import_openapi(spec, &root).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(
+            env.unwrap().envs.get("baseUrl").map(String::as_str),
+            Some("http://legacy.example.com/api")
+        );
+        let login = read(root.join("Legacy_API/POST _login.rph"));
+        assert!(matches!(login.body().kind, BodyKind::FORM));
+        assert_eq!(login.body().payload.as_deref(), Some("user=ada"));
+        fs::remove_dir_all(&root).unwrap();
+        assert!(import_openapi("{\"openapi\": \"1.0\"}", &root).is_err());
+    }
+}
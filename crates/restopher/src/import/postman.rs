@@ -0,0 +1,602 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{file_name, query_pairs, unique_path, Report};
+use crate::environments::Environment;
+use crate::request::{Body, BodyKind, HttpVerb, Mode, Request};
+
+const SCHEMA_V21: &str = "v2.1.0";
+
+#[derive(Deserialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Value>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    name: String,
+    #[serde(default)]
+    schema: String,
+}
+
+/// A folder when `item` is set, a request otherwise.
+#[derive(Deserialize)]
+struct Item {
+    #[serde(default)]
+    name: String,
+    item: Option<Vec<Item>>,
+    request: Option<PostmanRequest>,
+    auth: Option<Auth>,
+    #[serde(default)]
+    event: Vec<Value>,
+    #[serde(default)]
+    response: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PostmanRequest {
+    Url(String),
+    Detailed(Box<RequestDetails>),
+}
+
+#[derive(Deserialize)]
+struct RequestDetails {
+    #[serde(default = "default_method")]
+    method: String,
+    url: Option<Url>,
+    #[serde(default)]
+    header: Vec<KeyValue>,
+    body: Option<PostmanBody>,
+    auth: Option<Auth>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Url {
+    Raw(String),
+    Detailed {
+        #[serde(default)]
+        raw: String,
+        query: Option<Vec<KeyValue>>,
+        #[serde(default)]
+        variable: Vec<KeyValue>,
+    },
+}
+
+#[derive(Deserialize, Default)]
+struct KeyValue {
+    #[serde(default)]
+    key: String,
+    value: Option<Value>,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    src: Option<Value>,
+}
+
+impl KeyValue {
+    fn value(&self) -> String {
+        match &self.value {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(v) => v.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PostmanBody {
+    #[serde(default)]
+    mode: String,
+    raw: Option<String>,
+    #[serde(default)]
+    urlencoded: Vec<KeyValue>,
+    #[serde(default)]
+    formdata: Vec<KeyValue>,
+    graphql: Option<Value>,
+    options: Option<Value>,
+}
+
+#[derive(Deserialize, Clone)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(flatten)]
+    params: HashMap<String, Value>,
+}
+
+impl Auth {
+    /// Postman stores auth parameters as a list of `{key, value}` under the
+    /// auth type's name.
+    fn param(&self, key: &str) -> String {
+        self.params
+            .get(&self.kind)
+            .and_then(|v| v.as_array())
+            .and_then(|params| params.iter().find(|p| p["key"] == key))
+            .map(|p| match &p["value"] {
+                Value::String(s) => s.clone(),
+                v => v.to_string(),
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Deserialize)]
+struct PostmanEnvironment {
+    name: String,
+    #[serde(default)]
+    values: Vec<EnvironmentValue>,
+}
+
+#[derive(Deserialize)]
+struct EnvironmentValue {
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default = "enabled")]
+    enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// Converts a Postman v2.1 collection into a folder of `.rph` files under
+/// `root`. Collection variables become an environment, returned alongside.
+pub fn import_collection(
+    content: &str,
+    root: &Path,
+) -> Result<(Report, Option<Environment>), String> {
+    let collection: Collection = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut report = Report::new(format!("Postman collection \"{}\"", collection.info.name));
+    if !collection.info.schema.is_empty() && !collection.info.schema.contains(SCHEMA_V21) {
+        report.warn(format!(
+            "schema {} is not v2.1, the import may be incomplete",
+            collection.info.schema
+        ));
+    }
+    if !collection.event.is_empty() {
+        report.warn("collection scripts were not imported".to_string());
+    }
+    let dir = unique_path(root, &file_name(&collection.info.name), "");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    import_items(
+        &collection.item,
+        &dir,
+        collection.auth.as_ref(),
+        &collection.info.name,
+        &mut report,
+    )?;
+    let environment = if collection.variable.is_empty() {
+        None
+    } else {
+        let mut env = Environment::new(file_name(&collection.info.name));
+        for var in &collection.variable {
+            env.envs.insert(var.key.clone(), var.value());
+        }
+        report.note(format!(
+            "collection variables saved as environment \"{}\"",
+            env.name
+        ));
+        Some(env)
+    };
+    report.note(format!("saved to {}", dir.to_string_lossy()));
+    Ok((report, environment))
+}
+
+fn import_items(
+    items: &[Item],
+    dir: &Path,
+    inherited_auth: Option<&Auth>,
+    location: &str,
+    report: &mut Report,
+) -> Result<(), String> {
+    for item in items {
+        let location = format!("{} / {}", location, item.name);
+        if !item.event.is_empty() {
+            report.warn(format!("{}: scripts were not imported", location));
+        }
+        let auth = item.auth.as_ref().or(inherited_auth);
+        match (&item.item, &item.request) {
+            (Some(children), _) => {
+                let sub_dir = unique_path(dir, &file_name(&item.name), "");
+                fs::create_dir_all(&sub_dir).map_err(|e| e.to_string())?;
+                import_items(children, &sub_dir, auth, &location, report)?;
+            }
+            (None, Some(request)) => {
+                if !item.response.is_empty() {
+                    report.warn(format!(
+                        "{}: {} saved example responses were not imported",
+                        location,
+                        item.response.len()
+                    ));
+                }
+                let req = convert_request(&item.name, request, auth, &location, report);
+                let path = unique_path(dir, &file_name(&item.name), ".rph");
+                let content = req.to_rph().map_err(|e| e.to_string())?;
+                fs::write(path, content).map_err(|e| e.to_string())?;
+                report.imported += 1;
+            }
+            (None, None) => report.warn(format!("{}: empty item skipped", location)),
+        }
+    }
+    Ok(())
+}
+
+fn convert_request(
+    name: &str,
+    request: &PostmanRequest,
+    inherited_auth: Option<&Auth>,
+    location: &str,
+    report: &mut Report,
+) -> Request {
+    let mut req = Request::new(Mode::REST);
+    req.set_name(name.to_string());
+    let details = match request {
+        PostmanRequest::Url(url) => {
+            set_url(&mut req, &Url::Raw(url.clone()), location, report);
+            if let Some(auth) = inherited_auth {
+                set_auth(&mut req, auth, location, report);
+            }
+            return req;
+        }
+        PostmanRequest::Detailed(details) => details,
+    };
+    match HttpVerb::from_name(&details.method) {
+        Some(verb) => req.set_verb(verb),
+        None => report.warn(format!(
+            "{}: method {} is not supported, imported as GET",
+            location, details.method
+        )),
+    }
+    if let Some(url) = &details.url {
+        set_url(&mut req, url, location, report);
+    }
+    for header in &details.header {
+        req.add_to_header(header.key.clone(), header.value(), !header.disabled);
+    }
+    if let Some(body) = &details.body {
+        set_body(&mut req, body, location, report);
+    }
+    if let Some(auth) = details.auth.as_ref().or(inherited_auth) {
+        set_auth(&mut req, auth, location, report);
+    }
+    req
+}
+
+fn set_url(req: &mut Request, url: &Url, location: &str, report: &mut Report) {
+    let (raw, query, variables) = match url {
+        Url::Raw(raw) => (raw.as_str(), None, &[][..]),
+        Url::Detailed {
+            raw,
+            query,
+            variable,
+        } => (raw.as_str(), query.as_ref(), &variable[..]),
+    };
+    let (address, raw_query) = raw.split_once('?').unwrap_or((raw, ""));
+    req.set_address(address.to_string());
+    match query {
+        Some(query) => {
+            for param in query {
+                req.add_to_param(param.key.clone(), param.value(), !param.disabled);
+            }
+        }
+        None => {
+            for (k, v) in query_pairs(raw_query) {
+                req.add_to_param(k, v, true);
+            }
+        }
+    }
+    if !variables.is_empty() {
+        report.warn(format!(
+            "{}: path variables ({}) are kept as written in the URL",
+            location,
+            variables
+                .iter()
+                .map(|v| format!(":{}", v.key))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+}
+
+fn set_body(req: &mut Request, body: &PostmanBody, location: &str, report: &mut Report) {
+    let (kind, payload) = match body.mode.as_str() {
+        "raw" => {
+            let language = body
+                .options
+                .as_ref()
+                .and_then(|o| o["raw"]["language"].as_str())
+                .unwrap_or("text");
+            let kind = match language {
+                "json" => BodyKind::JSON,
+                "xml" | "html" => BodyKind::XML,
+                _ => BodyKind::TEXT,
+            };
+            (kind, body.raw.clone().unwrap_or_default())
+        }
+        "urlencoded" => (BodyKind::FORM, pairs(&body.urlencoded, location, report)),
+        "formdata" => (BodyKind::MULTIPART, pairs(&body.formdata, location, report)),
+        "graphql" => {
+            let query = body
+                .graphql
+                .as_ref()
+                .and_then(|g| g["query"].as_str())
+                .unwrap_or_default();
+            if body.graphql.as_ref().is_some_and(|g| {
+                g["variables"]
+                    .as_str()
+                    .is_some_and(|v| !v.trim().is_empty())
+            }) {
+                report.warn(format!("{}: GraphQL variables were not imported", location));
+            }
+            (BodyKind::GRAPHQL, query.to_string())
+        }
+        mode => {
+            report.warn(format!("{}: {} body was not imported", location, mode));
+            return;
+        }
+    };
+    req.set_body(Body {
+        kind,
+        payload: Some(payload),
+    });
+}
+
+/// Renders form fields as the `key=value` lines of a restopher body, file
+/// fields as `key=@path`.
+fn pairs(fields: &[KeyValue], location: &str, report: &mut Report) -> String {
+    for field in fields.iter().filter(|f| f.disabled) {
+        report.warn(format!(
+            "{}: disabled body field {} was dropped",
+            location, field.key
+        ));
+    }
+    fields
+        .iter()
+        .filter(|f| !f.disabled)
+        .map(|f| match (f.kind.as_deref(), &f.src) {
+            (Some("file"), Some(Value::String(src))) => format!("{}=@{}", f.key, src),
+            _ => format!("{}={}", f.key, f.value()),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Auth is stored as headers or params, the way restopher sends it.
+fn set_auth(req: &mut Request, auth: &Auth, location: &str, report: &mut Report) {
+    match auth.kind.as_str() {
+        "noauth" => (),
+        "basic" => req.add_to_header(
+            "Authorization".to_string(),
+            format!(
+                "Basic {}",
+                STANDARD.encode(format!(
+                    "{}:{}",
+                    auth.param("username"),
+                    auth.param("password")
+                ))
+            ),
+            true,
+        ),
+        "bearer" => req.add_to_header(
+            "Authorization".to_string(),
+            format!("Bearer {}", auth.param("token")),
+            true,
+        ),
+        "apikey" => {
+            if auth.param("in") == "query" {
+                req.add_to_param(auth.param("key"), auth.param("value"), true);
+            } else {
+                req.add_to_header(auth.param("key"), auth.param("value"), true);
+            }
+        }
+        kind => report.warn(format!("{}: {} auth is not supported", location, kind)),
+    }
+}
+
+/// Converts a Postman environment export. Disabled values are left out.
+pub fn import_environment(content: &str) -> Result<(Report, Environment), String> {
+    let postman: PostmanEnvironment = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut report = Report::new(format!("Postman environment \"{}\"", postman.name));
+    // the name is the file the environment is saved to
+    let mut env = Environment::new(file_name(&postman.name));
+    for value in postman.values {
+        if !value.enabled {
+            report.warn(format!("disabled variable {} was skipped", value.key));
+            continue;
+        }
+        let v = match value.value {
+            Value::String(s) => s,
+            Value::Null => String::new(),
+            v => v.to_string(),
+        };
+        env.envs.insert(value.key, v);
+        report.imported += 1;
+    }
+    Ok((report, env))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+        "info": {
+            "name": "Shop/API",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}"}]},
+        "variable": [{"key": "host", "value": "https://shop.example.com"}],
+        "item": [
+            {
+                "name": "Users",
+                "item": [
+                    {
+                        "name": "Admin",
+                        "auth": {"type": "basic", "basic": [
+                            {"key": "username", "value": "root"},
+                            {"key": "password", "value": "secret"}
+                        ]},
+                        "item": [
+                            {"name": "List users", "request": {
+                                "method": "GET",
+                                "url": {
+                                    "raw": "{{host}}/users?page=1",
+                                    "query": [
+                                        {"key": "page", "value": "1"},
+                                        {"key": "debug", "value": null, "disabled": true}
+                                    ]
+                                },
+                                "header": [{"key": "X-Empty", "value": ""}]
+                            }}
+                        ]
+                    }
+                ]
+            },
+            {"name": "Health", "request": "{{host}}/health"},
+            {"name": "Key", "request": {
+                "method": "POST",
+                "url": "{{host}}/items",
+                "auth": {"type": "apikey", "apikey": [
+                    {"key": "key", "value": "api_key"},
+                    {"key": "value", "value": "k"},
+                    {"key": "in", "value": "query"}
+                ]},
+                "body": {"mode": "urlencoded", "urlencoded": [{"key": "a", "value": "1"}]}
+            }}
+        ]
+    }"#;
+
+    /// A fresh directory under the system temp dir.
+    fn temp_root(name: &str) -> std::path::PathBuf {
+        let root = std::env::temp_dir().join(format!("restopher-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn read(path: std::path::PathBuf) -> Request {
+        Request::from_rph(&fs::read_to_string(&path).unwrap()).unwrap()
+    }
+
+    fn header(req: &Request, name: &str) -> Option<String> {
+        req.headers()?
+            .into_iter()
+            .find(|(k, _, _)| k == name)
+            .map(|(_, v, _)| v)
+    }
+
+    #[test]
+    fn imports_nested_folders_and_inherited_auth() {
+        let root = temp_root("postman");
+        let (report, env) = import_collection(COLLECTION, &root).unwrap();
+        assert_eq!(report.imported, 3);
+        assert!(report.warnings.is_empty(), "{:?}", report.lines());
+        let dir = root.join("Shop_API");
+
+        let env = env.unwrap();
+        assert_eq!(env.name, "Shop_API");
+        assert_eq!(
+            env.envs.get("host").map(String::as_str),
+            Some("https://shop.example.com")
+        );
+
+        let list = read(dir.join("Users/Admin/List users.rph"));
+        assert_eq!(list.address(), "{{host}}/users");
+        assert_eq!(
+            list.params(),
+            Some(vec![
+                ("page".to_string(), "1".to_string(), true),
+                ("debug".to_string(), String::new(), false),
+            ])
+        );
+        assert_eq!(header(&list, "X-Empty").as_deref(), Some(""));
+        // the folder's basic auth wins over the collection's bearer token
+        assert_eq!(
+            header(&list, "Authorization").as_deref(),
+            Some("Basic cm9vdDpzZWNyZXQ=")
+        );
+
+        let health = read(dir.join("Health.rph"));
+        assert_eq!(health.verb().to_string(), "GET");
+        assert_eq!(
+            header(&health, "Authorization").as_deref(),
+            Some("Bearer {{token}}")
+        );
+
+        let key = read(dir.join("Key.rph"));
+        assert_eq!(key.verb().to_string(), "POST");
+        assert_eq!(
+            key.params(),
+            Some(vec![("api_key".to_string(), "k".to_string(), true)])
+        );
+        assert!(header(&key, "Authorization").is_none());
+        assert!(matches!(key.body().kind, BodyKind::FORM));
+        assert_eq!(key.body().payload.as_deref(), Some("a=1"));
+
+        // a second import does not overwrite the first
+        import_collection(COLLECTION, &root).unwrap();
+        assert!(root.join("Shop_API (2)/Health.rph").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reports_what_is_not_mapped() {
+        let root = temp_root("postman-report");
+        let content = r#"{
+            "info": {"name": "Old", "schema": "https://schema.getpostman.com/json/collection/v2.0.0/collection.json"},
+            "event": [{"listen": "prerequest"}],
+            "item": [
+                {"name": "Digest", "request": {"method": "GET", "url": "https://example.com",
+                    "auth": {"type": "digest"}}},
+                {"name": "Nothing"}
+            ]
+        }"#;
+        let (report, env) = import_collection(content, &root).unwrap();
+        assert!(env.is_none());
+        assert_eq!(report.imported, 1);
+        let lines = report.lines().join("\n");
+        assert!(lines.contains("is not v2.1"), "{}", lines);
+        assert!(lines.contains("collection scripts"), "{}", lines);
+        assert!(lines.contains("digest auth is not supported"), "{}", lines);
+        assert!(lines.contains("empty item skipped"), "{}", lines);
+        fs::remove_dir_all(&root).unwrap();
+        assert!(import_collection("{}", &root).is_err());
+    }
+
+    #[test]
+    fn imports_environments() {
+        let (report, env) = import_environment(
+            r#"{"name": "staging/eu", "values": [
+                {"key": "host", "value": "https://eu.example.com"},
+                {"key": "port", "value": 8080},
+                {"key": "old", "value": "x", "enabled": false}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(env.name, "staging_eu");
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(env.envs.get("port").map(String::as_str), Some("8080"));
+        assert!(!env.envs.contains_key("old"));
+    }
+}
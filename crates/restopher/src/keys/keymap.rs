@@ -0,0 +1,674 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use super::keys::{Event, Key, Modifier};
+use crate::main_windows::MainWindows;
+
+const WINDOWS: [MainWindows; 3] = [
+    MainWindows::Main,
+    MainWindows::Environments,
+    MainWindows::Collections,
+];
+
+/// Keys used by the widgets of the main window. Binding one of them to an
+/// action hides it from the widget, which is reported at startup.
+const RESERVED: &[(&str, &str)] = &[
+    ("ctrl-v", "paste"),
+    ("ctrl-n", "new header, param or variable"),
+    ("ctrl-d", "delete header, param, variable or request"),
+    ("ctrl-o", "switch body kind or response view"),
+    ("ctrl-f", "search"),
+    ("alt-n", "next search match"),
+    ("alt-p", "previous search match"),
+    ("alt-c", "search case toggle"),
+    ("alt-r", "rename request"),
+];
+
+static KEYMAP: OnceLock<Keymap> = OnceLock::new();
+
+/// Everything a key can be bound to. Keys typed into widgets, such as
+/// characters or arrows in a text box, are not actions.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Quit,
+    NavUp,
+    NavDown,
+    NavLeft,
+    NavRight,
+    OpenCollections,
+    OpenEnvironments,
+    NextRequestTab,
+    NextResponseTab,
+    SaveRequest,
+    NewRequest,
+    PreviousRequest,
+    NextRequest,
+    SendRequest,
+    ShowHistory,
+    ExportRequest,
+    Import,
+    CommandPalette,
+    QuickOpen,
+    /// Show the variables of the request and the scope of each.
+    ShowVariables,
+    NextEnvironment,
+    /// Switch the body of the request between JSON, XML, text and the
+    /// others.
+    ToggleBodyKind,
+    /// Leave the collections or environments window.
+    Close,
+    Create,
+    Delete,
+    ExportCollection,
+    MigrateCollection,
+    Rename,
+    Duplicate,
+    /// Mark the selected entry to be moved by `Paste`.
+    Cut,
+    Paste,
+    /// Move the selected entry up in its folder.
+    MoveUp,
+    MoveDown,
+}
+
+impl Action {
+    pub const ALL: [Action; 33] = [
+        Action::Quit,
+        Action::NavUp,
+        Action::NavDown,
+        Action::NavLeft,
+        Action::NavRight,
+        Action::OpenCollections,
+        Action::OpenEnvironments,
+        Action::NextRequestTab,
+        Action::NextResponseTab,
+        Action::SaveRequest,
+        Action::NewRequest,
+        Action::PreviousRequest,
+        Action::NextRequest,
+        Action::SendRequest,
+        Action::ShowHistory,
+        Action::ExportRequest,
+        Action::Import,
+        Action::CommandPalette,
+        Action::QuickOpen,
+        Action::ShowVariables,
+        Action::NextEnvironment,
+        Action::ToggleBodyKind,
+        Action::Close,
+        Action::Create,
+        Action::Delete,
+        Action::ExportCollection,
+        Action::MigrateCollection,
+        Action::Rename,
+        Action::Duplicate,
+        Action::Cut,
+        Action::Paste,
+        Action::MoveUp,
+        Action::MoveDown,
+    ];
+    /// The name used in `config.toml`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NavUp => "nav_up",
+            Action::NavDown => "nav_down",
+            Action::NavLeft => "nav_left",
+            Action::NavRight => "nav_right",
+            Action::OpenCollections => "open_collections",
+            Action::OpenEnvironments => "open_environments",
+            Action::NextRequestTab => "next_request_tab",
+            Action::NextResponseTab => "next_response_tab",
+            Action::SaveRequest => "save_request",
+            Action::NewRequest => "new_request",
+            Action::PreviousRequest => "previous_request",
+            Action::NextRequest => "next_request",
+            Action::SendRequest => "send_request",
+            Action::ShowHistory => "show_history",
+            Action::ExportRequest => "export_request",
+            Action::Import => "import",
+            Action::CommandPalette => "command_palette",
+            Action::QuickOpen => "quick_open",
+            Action::ShowVariables => "show_variables",
+            Action::NextEnvironment => "next_environment",
+            Action::ToggleBodyKind => "toggle_body_kind",
+            Action::Close => "close",
+            Action::Create => "create",
+            Action::Delete => "delete",
+            Action::ExportCollection => "export_collection",
+            Action::MigrateCollection => "migrate_collection",
+            Action::Rename => "rename",
+            Action::Duplicate => "duplicate",
+            Action::Cut => "cut",
+            Action::Paste => "paste",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+        }
+    }
+    fn from_name(name: &str) -> Option<Self> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+    /// What the action does, as listed in the command palette.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::NavUp => "Focus the pane above",
+            Action::NavDown => "Focus the pane below",
+            Action::NavLeft => "Focus the pane on the left",
+            Action::NavRight => "Focus the pane on the right",
+            Action::OpenCollections => "Open collections",
+            Action::OpenEnvironments => "Edit environments",
+            Action::NextRequestTab => "Next request tab",
+            Action::NextResponseTab => "Next response tab",
+            Action::SaveRequest => "Save request",
+            Action::NewRequest => "New request",
+            Action::PreviousRequest => "Previous request",
+            Action::NextRequest => "Next request",
+            Action::SendRequest => "Send request",
+            Action::ShowHistory => "Show history",
+            Action::ExportRequest => "Export request",
+            Action::Import => "Import",
+            Action::CommandPalette => "Command palette",
+            Action::QuickOpen => "Open a saved request",
+            Action::ShowVariables => "Show the resolved variables",
+            Action::NextEnvironment => "Switch to the next environment",
+            Action::ToggleBodyKind => "Toggle body kind",
+            Action::Close => "Close",
+            Action::Create => "Create",
+            Action::Delete => "Delete",
+            Action::ExportCollection => "Export collection",
+            Action::MigrateCollection => "Migrate collection",
+            Action::Rename => "Rename",
+            Action::Duplicate => "Duplicate",
+            Action::Cut => "Cut, to move with paste",
+            Action::Paste => "Paste into the selected folder",
+            Action::MoveUp => "Move up in the folder",
+            Action::MoveDown => "Move down in the folder",
+        }
+    }
+    /// Actions without a default key can still be bound in the config.
+    fn default_key(&self) -> Option<&'static str> {
+        match self {
+            Action::Quit => Some("alt-q"),
+            Action::NavUp => Some("ctrl-k"),
+            Action::NavDown => Some("ctrl-j"),
+            Action::NavLeft => Some("ctrl-h"),
+            Action::NavRight => Some("ctrl-l"),
+            Action::OpenCollections => Some("alt-b"),
+            Action::OpenEnvironments => Some("ctrl-e"),
+            Action::NextRequestTab => Some("ctrl-t"),
+            Action::NextResponseTab => Some("ctrl-r"),
+            // ctrl-s stops the output of terminals with flow control
+            Action::SaveRequest => Some("alt-s"),
+            Action::NewRequest => Some("ctrl-w"),
+            Action::PreviousRequest => Some("ctrl-left"),
+            Action::NextRequest => Some("ctrl-right"),
+            Action::SendRequest => Some("ctrl-p"),
+            Action::ShowHistory => Some("alt-h"),
+            Action::ExportRequest => Some("alt-e"),
+            Action::Import => Some("alt-i"),
+            Action::CommandPalette => Some("alt-x"),
+            Action::QuickOpen => Some("alt-o"),
+            Action::ShowVariables => Some("alt-g"),
+            Action::NextEnvironment => None,
+            // ctrl-o already does it in the body tab
+            Action::ToggleBodyKind => None,
+            Action::Close => Some("esc"),
+            Action::Create => Some("ctrl-n"),
+            Action::Delete => Some("ctrl-d"),
+            Action::ExportCollection => Some("ctrl-e"),
+            Action::MigrateCollection => Some("ctrl-u"),
+            Action::Rename => Some("ctrl-r"),
+            Action::Duplicate => Some("ctrl-y"),
+            Action::Cut => Some("ctrl-x"),
+            Action::Paste => Some("ctrl-v"),
+            Action::MoveUp => Some("alt-up"),
+            Action::MoveDown => Some("alt-down"),
+        }
+    }
+    /// Whether the action can be used in `window`.
+    pub fn available_in(&self, window: &MainWindows) -> bool {
+        self.windows().contains(window)
+    }
+    /// The windows the action is available in.
+    fn windows(&self) -> &'static [MainWindows] {
+        match self {
+            Action::Quit => &WINDOWS,
+            Action::Close | Action::Create | Action::Delete => {
+                &[MainWindows::Environments, MainWindows::Collections]
+            }
+            Action::ExportCollection
+            | Action::MigrateCollection
+            | Action::Rename
+            | Action::Duplicate
+            | Action::Cut
+            | Action::Paste
+            | Action::MoveUp
+            | Action::MoveDown => &[MainWindows::Collections],
+            _ => &[MainWindows::Main],
+        }
+    }
+}
+
+fn window_name(window: &MainWindows) -> &'static str {
+    match window {
+        MainWindows::Main => "main",
+        MainWindows::Environments => "environments",
+        MainWindows::Collections => "collections",
+    }
+}
+
+/// One key, or a list of keys; an empty list leaves the action unbound.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Binding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Binding {
+    fn keys(&self) -> Vec<&str> {
+        match self {
+            Binding::One(key) if key == "none" => Vec::new(),
+            Binding::One(key) => vec![key.as_str()],
+            Binding::Many(keys) => keys.iter().map(|k| k.as_str()).collect(),
+        }
+    }
+}
+
+/// The `[keys]` table of `config.toml`, as in `send_request = "ctrl-p"`.
+/// Bindings at the top apply to every window the action is available in;
+/// `[keys.main]`, `[keys.environments]` and `[keys.collections]` override
+/// them for one window.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct KeyConfig {
+    main: BTreeMap<String, Binding>,
+    environments: BTreeMap<String, Binding>,
+    collections: BTreeMap<String, Binding>,
+    #[serde(flatten)]
+    global: BTreeMap<String, Binding>,
+}
+
+/// Parses keys written as `ctrl-s`, `alt-left`, `esc` or `x`.
+pub fn parse_key(key: &str) -> Result<Event, String> {
+    let lower = key.trim().to_lowercase();
+    let (modifier, name) = match lower.split_once(['-', '+']) {
+        Some((modifier, name)) if !name.is_empty() => {
+            let modifier = match modifier {
+                "ctrl" | "control" => Modifier::Control,
+                "alt" | "meta" => Modifier::Alt,
+                "shift" => Modifier::Shift,
+                _ => return Err(format!("unknown modifier in {}", key)),
+            };
+            (Some(modifier), name)
+        }
+        _ => (None, lower.as_str()),
+    };
+    let key_code = match name {
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "backspace" => Key::Backspace,
+        "esc" | "escape" => Key::Esc,
+        "enter" | "return" => Key::Enter,
+        "tab" => Key::Tab,
+        "space" => Key::Space,
+        _ => {
+            let mut chars = key.trim().chars().rev();
+            match (chars.next(), name.chars().count()) {
+                (Some(c), 1) => Key::Char(if modifier.is_some() {
+                    c.to_ascii_lowercase()
+                } else {
+                    c
+                }),
+                _ => return Err(format!("unknown key {}", key)),
+            }
+        }
+    };
+    Ok(Event {
+        modifier,
+        key: key_code,
+    })
+}
+
+/// Writes a key the way `parse_key` reads it.
+pub fn describe(event: &Event) -> String {
+    let key = match &event.key {
+        Key::Char(c) => c.to_string(),
+        Key::Up => "up".to_string(),
+        Key::Down => "down".to_string(),
+        Key::Left => "left".to_string(),
+        Key::Right => "right".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::Esc => "esc".to_string(),
+        Key::Enter => "enter".to_string(),
+        Key::Tab => "tab".to_string(),
+        Key::Space => "space".to_string(),
+    };
+    match &event.modifier {
+        Some(Modifier::Control) => format!("ctrl-{}", key),
+        Some(Modifier::Alt) => format!("alt-{}", key),
+        Some(Modifier::Shift) => format!("shift-{}", key),
+        None => key,
+    }
+}
+
+pub struct Keymap {
+    windows: HashMap<MainWindows, HashMap<Event, Action>>,
+    problems: Vec<String>,
+}
+
+impl Keymap {
+    /// Builds the keymap from the defaults and the user's bindings. Bindings
+    /// that cannot be read, that take a key from another action or that hide
+    /// a key used by the widgets are listed in `problems`.
+    fn load(config: &KeyConfig) -> Self {
+        let mut keymap = Keymap {
+            windows: HashMap::new(),
+            problems: Vec::new(),
+        };
+        for action in Action::ALL {
+            let Some(key) = action.default_key() else {
+                continue;
+            };
+            let event = parse_key(key).expect("default keys are valid");
+            for window in action.windows() {
+                keymap
+                    .windows
+                    .entry(*window)
+                    .or_default()
+                    .insert(event.clone(), action);
+            }
+        }
+        let mut unbound = Vec::new();
+        let layers = [
+            (None, &config.global),
+            (Some(MainWindows::Main), &config.main),
+            (Some(MainWindows::Environments), &config.environments),
+            (Some(MainWindows::Collections), &config.collections),
+        ];
+        for (scope, table) in layers {
+            // a table is applied as a whole, so swapping two keys is not a conflict
+            let mut pending = Vec::new();
+            for (name, binding) in table {
+                let Some(action) = Action::from_name(name) else {
+                    keymap.problems.push(format!("unknown action {}", name));
+                    continue;
+                };
+                let windows = match &scope {
+                    None => action.windows().to_vec(),
+                    Some(window) if action.available_in(window) => vec![*window],
+                    Some(window) => {
+                        keymap.problems.push(format!(
+                            "{} is not available in the {} window",
+                            name,
+                            window_name(window)
+                        ));
+                        continue;
+                    }
+                };
+                let mut events = Vec::new();
+                for key in binding.keys() {
+                    match parse_key(key) {
+                        Ok(event) => events.push(event),
+                        Err(e) => keymap.problems.push(format!("{}: {}", name, e)),
+                    }
+                }
+                for window in windows {
+                    if events.is_empty() {
+                        unbound.push((window, action));
+                    }
+                    keymap
+                        .windows
+                        .entry(window)
+                        .or_default()
+                        .retain(|_, bound| *bound != action);
+                    pending.push((window, action, events.clone()));
+                }
+            }
+            for (window, action, events) in pending {
+                keymap.bind(window, action, &events);
+            }
+        }
+        for window in WINDOWS {
+            for action in Action::ALL
+                .iter()
+                .filter(|a| a.default_key().is_some() && a.available_in(&window))
+                .filter(|a| !unbound.contains(&(window, **a)))
+            {
+                if keymap.keys(&window, *action).is_empty() {
+                    keymap.problems.push(format!(
+                        "{}: {} has no key left",
+                        window_name(&window),
+                        action.name()
+                    ));
+                }
+            }
+        }
+        keymap
+    }
+    /// Binds `events` to `action` in `window`.
+    fn bind(&mut self, window: MainWindows, action: Action, events: &[Event]) {
+        let bindings = self.windows.entry(window).or_default();
+        for event in events {
+            let key = describe(event);
+            if let Some(other) = bindings.insert(event.clone(), action) {
+                if other != action {
+                    self.problems.push(format!(
+                        "{}: {} was bound to {} and now is {}",
+                        window_name(&window),
+                        key,
+                        other.name(),
+                        action.name()
+                    ));
+                }
+            }
+            let reserved = RESERVED
+                .iter()
+                .find(|(k, _)| *k == key && window == MainWindows::Main);
+            if let Some((_, used_for)) = reserved {
+                self.problems.push(format!(
+                    "{}: {} of {} hides {}",
+                    window_name(&window),
+                    key,
+                    action.name(),
+                    used_for
+                ));
+            } else if matches!(event.modifier, None | Some(Modifier::Shift))
+                && !matches!(event.key, Key::Esc)
+            {
+                self.problems.push(format!(
+                    "{}: {} of {} hides a key used for typing or moving around",
+                    window_name(&window),
+                    key,
+                    action.name()
+                ));
+            }
+        }
+    }
+    pub fn action(&self, window: &MainWindows, event: &Event) -> Option<Action> {
+        self.windows.get(window)?.get(event).copied()
+    }
+    pub fn is(&self, window: &MainWindows, event: &Event, action: Action) -> bool {
+        self.action(window, event) == Some(action)
+    }
+    /// The keys bound to `action` in `window`, as written in the config.
+    pub fn keys(&self, window: &MainWindows, action: Action) -> Vec<String> {
+        let mut keys = self
+            .windows
+            .get(window)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter(|(_, a)| **a == action)
+                    .map(|(event, _)| describe(event))
+                    .collect::<Vec<String>>()
+            })
+            .unwrap_or_default();
+        keys.sort();
+        keys
+    }
+    pub fn problems(&self) -> &[String] {
+        &self.problems
+    }
+}
+
+/// Builds the keymap from the `[keys]` table. Called once at startup, after
+/// the config is loaded.
+pub fn init(config: &KeyConfig) -> &'static Keymap {
+    KEYMAP.get_or_init(|| Keymap::load(config))
+}
+
+pub fn get() -> &'static Keymap {
+    KEYMAP.get().expect("the keymap is loaded at startup")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(config: &str) -> Keymap {
+        Keymap::load(&toml::from_str(config).unwrap())
+    }
+
+    fn event(key: &str) -> Event {
+        parse_key(key).unwrap()
+    }
+
+    #[test]
+    fn parses_and_describes_keys() {
+        assert_eq!(
+            parse_key("Ctrl+S").unwrap(),
+            Event {
+                modifier: Some(Modifier::Control),
+                key: Key::Char('s'),
+            }
+        );
+        assert_eq!(
+            parse_key("meta-left").unwrap(),
+            Event {
+                modifier: Some(Modifier::Alt),
+                key: Key::Left,
+            }
+        );
+        assert_eq!(parse_key("X").unwrap().key, Key::Char('X'));
+        assert_eq!(parse_key("-").unwrap().key, Key::Char('-'));
+        assert!(parse_key("hyper-x").is_err());
+        assert!(parse_key("ctrl-enterr").is_err());
+        for key in ["ctrl-s", "alt-left", "esc", "shift-tab", "x"] {
+            assert_eq!(describe(&event(key)), key);
+        }
+    }
+
+    #[test]
+    fn defaults_have_no_problems() {
+        let keymap = load("");
+        assert!(keymap.problems().is_empty(), "{:?}", keymap.problems());
+        assert_eq!(
+            keymap.action(&MainWindows::Main, &event("ctrl-p")),
+            Some(Action::SendRequest)
+        );
+        // the same key means different things per window
+        assert!(keymap.is(
+            &MainWindows::Main,
+            &event("ctrl-e"),
+            Action::OpenEnvironments
+        ));
+        assert!(keymap.is(
+            &MainWindows::Collections,
+            &event("ctrl-e"),
+            Action::ExportCollection
+        ));
+        assert_eq!(
+            keymap.action(&MainWindows::Environments, &event("ctrl-p")),
+            None
+        );
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = load(
+            r#"
+            save_request = ["ctrl-s", "alt-s"]
+            send_request = "ctrl-g"
+            [collections]
+            delete = "ctrl-b"
+            "#,
+        );
+        assert!(keymap.problems().is_empty(), "{:?}", keymap.problems());
+        assert_eq!(
+            keymap.keys(&MainWindows::Main, Action::SaveRequest),
+            vec!["alt-s", "ctrl-s"]
+        );
+        assert_eq!(keymap.action(&MainWindows::Main, &event("ctrl-p")), None);
+        assert_eq!(
+            keymap.keys(&MainWindows::Collections, Action::Delete),
+            vec!["ctrl-b"]
+        );
+        assert_eq!(
+            keymap.keys(&MainWindows::Environments, Action::Delete),
+            vec!["ctrl-d"]
+        );
+    }
+
+    #[test]
+    fn swapping_keys_is_not_a_conflict() {
+        let keymap = load("next_request_tab = \"ctrl-r\"\nnext_response_tab = \"ctrl-t\"");
+        assert!(keymap.problems().is_empty(), "{:?}", keymap.problems());
+        assert!(keymap.is(&MainWindows::Main, &event("ctrl-r"), Action::NextRequestTab));
+    }
+
+    #[test]
+    fn reports_problems() {
+        let keymap = load(
+            r#"
+            send_request = "alt-s"
+            show_history = "ctrl-f"
+            import = "x"
+            explode = "ctrl-b"
+            quit = "none"
+            [environments]
+            send_request = "ctrl-g"
+            "#,
+        );
+        let problems = keymap.problems();
+        assert!(problems.contains(&"unknown action explode".to_string()));
+        assert!(problems
+            .contains(&"send_request is not available in the environments window".to_string()));
+        assert!(problems.contains(
+            &"main: alt-s was bound to save_request and now is send_request".to_string()
+        ));
+        assert!(problems.contains(&"main: save_request has no key left".to_string()));
+        assert!(problems.contains(&"main: ctrl-f of show_history hides search".to_string()));
+        assert!(problems.contains(
+            &"main: x of import hides a key used for typing or moving around".to_string()
+        ));
+        // unbinding on purpose is not reported
+        assert!(!problems.iter().any(|p| p.contains("quit")));
+        assert_eq!(
+            keymap.keys(&MainWindows::Main, Action::Quit),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn defaults_leave_the_search_keys_alone() {
+        let keymap = load("");
+        for (key, _) in RESERVED {
+            assert_eq!(
+                keymap.action(&MainWindows::Main, &event(key)),
+                None,
+                "{}",
+                key
+            );
+        }
+        assert_eq!(
+            load("open_collections = \"alt-c\"").problems(),
+            &["main: alt-c of open_collections hides search case toggle".to_string()]
+        );
+    }
+}
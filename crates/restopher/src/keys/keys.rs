@@ -1,12 +1,13 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum Modifier {
     Control,
     Shift,
     Alt,
 }
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub enum Key {
     Char(char),
     Up,
@@ -19,40 +20,11 @@ pub enum Key {
     Tab,
     Space,
 }
-#[derive(PartialEq, Eq, Debug)]
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
 pub struct Event {
     pub modifier: Option<Modifier>,
     pub key: Key,
 }
-pub const OPEN_COLLECTIONS: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('c'),
-};
-pub const CLOSE_COLLECTIONS: &Event = &Event {
-    modifier: None,
-    key: Key::Esc,
-};
-pub const OPEN_ENVIRONMENTS: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('e'),
-};
-pub const NAV_UP: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('k'),
-};
-pub const NAV_DOWN: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('j'),
-};
-pub const NAV_LEFT: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('h'),
-};
-pub const NAV_RIGHT: &Event = &Event {
-    modifier: Some(Modifier::Control),
-    key: Key::Char('l'),
-};
-
 pub fn transform(key: KeyEvent) -> Event {
     let mut modi: Option<Modifier> = None;
     let k: Key;
@@ -98,6 +70,33 @@ pub fn transform(key: KeyEvent) -> Event {
         key: k,
     }
 }
+/// Lines moved per turn of the mouse wheel.
+pub const SCROLL_LINES: usize = 3;
+
+/// A mouse event at a terminal cell.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Mouse {
+    Click(Position),
+    ScrollUp(Position),
+    ScrollDown(Position),
+}
+impl Mouse {
+    pub fn position(&self) -> Position {
+        match self {
+            Mouse::Click(p) | Mouse::ScrollUp(p) | Mouse::ScrollDown(p) => *p,
+        }
+    }
+}
+/// Left clicks and the wheel; other buttons, drags and releases are ignored.
+pub fn transform_mouse(event: MouseEvent) -> Option<Mouse> {
+    let position = Position::new(event.column, event.row);
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => Some(Mouse::Click(position)),
+        MouseEventKind::ScrollUp => Some(Mouse::ScrollUp(position)),
+        MouseEventKind::ScrollDown => Some(Mouse::ScrollDown(position)),
+        _ => None,
+    }
+}
 fn is_modifier(e: &Event, ms: Vec<Modifier>) -> bool {
     if let Some(em) = &e.modifier {
         for m in ms {
@@ -117,26 +116,6 @@ fn is_key(e: &Event, ks: Vec<Key>) -> bool {
     false
 }
 
-pub fn is_quit(e: &Event) -> bool {
-    if let Some(modi) = &e.modifier {
-        match modi {
-            Modifier::Alt => match e.key {
-                Key::Char(x) => {
-                    if x == 'q' {
-                        return true;
-                    }
-                }
-                _ => (),
-            },
-            _ => (),
-        };
-    }
-    false
-}
-pub fn is_navigation(e: &Event) -> bool {
-    e == NAV_UP || e == NAV_DOWN || e == NAV_LEFT || e == NAV_RIGHT
-}
-
 pub fn is_ctrl_v(e: &Event) -> bool {
     is_modifier(e, vec![Modifier::Control]) && is_key(e, vec![Key::Char('v')])
 }
@@ -1,4 +1,4 @@
-use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Position, Rect};
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -26,6 +26,14 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+#[derive(Clone, Copy)]
+pub enum Pane {
+    Requests,
+    AddressVerb,
+    Request,
+    Response,
+}
+
 pub struct AppLayout {
     pub requests: Rect,
     pub address_verb: Rect,
@@ -52,4 +60,14 @@ impl AppLayout {
             response: chunks[3],
         }
     }
+    pub fn pane_at(&self, position: Position) -> Option<(Pane, Rect)> {
+        [
+            (Pane::Requests, self.requests),
+            (Pane::AddressVerb, self.address_verb),
+            (Pane::Request, self.request),
+            (Pane::Response, self.response),
+        ]
+        .into_iter()
+        .find(|(_, rect)| rect.contains(position))
+    }
 }
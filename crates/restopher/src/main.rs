@@ -1,9 +1,18 @@
 mod app;
 mod collection;
 mod components;
+mod config;
+mod defaults;
 mod env_replacer;
 mod environments;
+mod export;
+mod filter;
+mod fuzzy;
 mod graphql;
+mod highlight;
+mod history;
+mod http_file;
+mod import;
 mod keys;
 mod layout;
 mod logger;
@@ -11,6 +20,9 @@ mod main_windows;
 mod models;
 mod request;
 mod styles;
+mod theme;
+mod variables;
+mod xml;
 
 use request::Request;
 use std::io;
@@ -27,12 +39,22 @@ pub(crate) const START_ENV_TOKEN: &str = "{{";
 pub(crate) const END_ENV_TOKEN: &str = "}}";
 pub(crate) const ENV_PATH: &str = "environments";
 pub(crate) const COLLECTION_PATH: &str = "collections";
-pub(crate) const DATA_DIRECTORY: &str = "/home/babak/.config/restopher";
+pub(crate) const HISTORY_PATH: &str = "history";
+pub(crate) const EXPORT_PATH: &str = "exports";
+pub(crate) const THEME_PATH: &str = "themes";
 
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
     //    setup_terminal()?;
     //    env::set_var("RUST_BACKTRACE", "1");
+    let args = config::parse_args(std::env::args().skip(1)).unwrap_or_else(|e| exit_with(&e));
+    if args.help {
+        println!("{}", config::USAGE);
+        return Ok(());
+    }
+    let config = config::init(args.data_dir).unwrap_or_else(|e| exit_with(&e));
+    keys::keymap::init(&config.preferences.keys);
+    theme::init(&config.data_dir, &config.preferences.theme).unwrap_or_else(|e| exit_with(&e));
     logger::initialize_logging().unwrap();
     let mut stdout = io::stdout();
     enable_raw_mode()?;
@@ -50,3 +72,9 @@ async fn main() -> Result<(), io::Error> {
     terminal.show_cursor()?;
     Ok(())
 }
+
+/// Reports a startup error before the terminal is taken over.
+fn exit_with(message: &str) -> ! {
+    eprintln!("restopher: {}", message);
+    std::process::exit(1);
+}
@@ -1,6 +1,7 @@
-use crate::keys::keys::{Event, Key, Modifier};
+use crate::keys::keymap::{self, Action};
+use crate::keys::keys::Event;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MainWindows {
     Main,
     Environments,
@@ -15,56 +16,40 @@ pub enum ChangeEvent {
     PreRequest,
     NextRequest,
     CallRequest,
+    ShowHistory,
+    ExportRequest,
+    Import,
+    CommandPalette,
+    QuickOpen,
+    ShowVariables,
+    NextEnvironment,
+    ToggleBodyKind,
     NoChange,
 }
 
 pub fn key_registry(event: &Event, main_window: &MainWindows) -> ChangeEvent {
-    match main_window {
-        MainWindows::Main => match event {
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Char('t'),
-            } => {
-                return ChangeEvent::ChangeRequestTab;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Char('p'),
-            } => {
-                return ChangeEvent::CallRequest;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Char('r'),
-            } => {
-                return ChangeEvent::ChangeResponseTab;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Char('s'),
-            } => {
-                return ChangeEvent::SaveRequest;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Right,
-            } => {
-                return ChangeEvent::NextRequest;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Left,
-            } => {
-                return ChangeEvent::PreRequest;
-            }
-            Event {
-                modifier: Some(Modifier::Control),
-                key: Key::Char('w'),
-            } => {
-                return ChangeEvent::NewRequest;
-            }
-            _ => return ChangeEvent::NoChange,
-        },
-        _ => return ChangeEvent::NoChange,
+    change_event(keymap::get().action(main_window, event))
+}
+
+/// The change an action makes to the main window, whether it came from a key
+/// or from the command palette.
+pub fn change_event(action: Option<Action>) -> ChangeEvent {
+    match action {
+        Some(Action::NextRequestTab) => ChangeEvent::ChangeRequestTab,
+        Some(Action::NextResponseTab) => ChangeEvent::ChangeResponseTab,
+        Some(Action::SaveRequest) => ChangeEvent::SaveRequest,
+        Some(Action::NewRequest) => ChangeEvent::NewRequest,
+        Some(Action::PreviousRequest) => ChangeEvent::PreRequest,
+        Some(Action::NextRequest) => ChangeEvent::NextRequest,
+        Some(Action::SendRequest) => ChangeEvent::CallRequest,
+        Some(Action::ShowHistory) => ChangeEvent::ShowHistory,
+        Some(Action::ExportRequest) => ChangeEvent::ExportRequest,
+        Some(Action::Import) => ChangeEvent::Import,
+        Some(Action::CommandPalette) => ChangeEvent::CommandPalette,
+        Some(Action::QuickOpen) => ChangeEvent::QuickOpen,
+        Some(Action::ShowVariables) => ChangeEvent::ShowVariables,
+        Some(Action::NextEnvironment) => ChangeEvent::NextEnvironment,
+        Some(Action::ToggleBodyKind) => ChangeEvent::ToggleBodyKind,
+        _ => ChangeEvent::NoChange,
     }
 }
@@ -13,3 +13,37 @@ impl ToString for SaveOptions {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub enum ImportOptions {
+    Curl,
+    PostmanCollection,
+    PostmanEnvironment,
+    OpenApi,
+    Har,
+}
+impl ToString for ImportOptions {
+    fn to_string(&self) -> String {
+        match self {
+            ImportOptions::Curl => "cURL command".to_string(),
+            ImportOptions::PostmanCollection => "Postman collection (v2.1)".to_string(),
+            ImportOptions::PostmanEnvironment => "Postman environment".to_string(),
+            ImportOptions::OpenApi => "OpenAPI 3 / Swagger 2 spec".to_string(),
+            ImportOptions::Har => "HAR file".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ExportOptions {
+    Postman,
+    OpenApi,
+}
+impl ToString for ExportOptions {
+    fn to_string(&self) -> String {
+        match self {
+            ExportOptions::Postman => "Postman collection (v2.1)".to_string(),
+            ExportOptions::OpenApi => "OpenAPI 3 skeleton".to_string(),
+        }
+    }
+}
@@ -1,8 +1,16 @@
 use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum BodyKind {
     JSON,
     TEXT,
+    XML,
+    GRAPHQL,
+    /// `application/x-www-form-urlencoded`, one `key=value` pair per line.
+    FORM,
+    /// `multipart/form-data`, one `key=value` pair per line; a value starting
+    /// with `@` is the path of a file to upload.
+    MULTIPART,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,3 +18,19 @@ pub struct Body {
     pub kind: BodyKind,
     pub payload: Option<String>,
 }
+
+impl Body {
+    /// The `key=value` lines of a form or multipart payload.
+    pub fn pairs(&self) -> Vec<(String, String)> {
+        self.payload
+            .as_deref()
+            .unwrap_or_default()
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| match l.split_once('=') {
+                Some((k, v)) => (k.trim().to_string(), v.to_string()),
+                None => (l.trim().to_string(), String::new()),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,399 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use super::{Body, BodyKind, HttpVerb, Mode, Request, Response};
+use crate::app::Error;
+
+/// Version of the `.rph` format written by this build. Files of older
+/// versions are upgraded through `MIGRATIONS` when they load.
+pub const RPH_VERSION: u64 = 2;
+
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// `MIGRATIONS[n]` upgrades a file of version `n + 1` to version `n + 2`.
+const MIGRATIONS: &[Migration] = &[from_json];
+
+/// A request as saved in a `.rph` file: YAML with a fixed key order, one
+/// entry per header and param, and the body as a literal block, so changes
+/// to a collection read well in a diff. The last response is not part of the
+/// file; it is restored from the request's history.
+#[derive(Serialize, Deserialize)]
+struct RequestFile {
+    #[serde(default = "current_version")]
+    version: u64,
+    name: String,
+    #[serde(default = "default_mode")]
+    mode: Mode,
+    method: HttpVerb,
+    url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    params: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    headers: Vec<Pair>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<FileBody>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response_filter: Option<String>,
+    /// Request variables, missing from files written before they existed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variables: Vec<Pair>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Pair {
+    name: String,
+    value: String,
+    #[serde(default = "enabled", skip_serializing_if = "is_enabled")]
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileBody {
+    kind: BodyKind,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    text: String,
+}
+
+fn current_version() -> u64 {
+    RPH_VERSION
+}
+
+fn default_mode() -> Mode {
+    Mode::REST
+}
+
+fn enabled() -> bool {
+    true
+}
+
+fn is_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+fn pairs(items: Option<Vec<(String, String, bool)>>) -> Vec<Pair> {
+    items
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value, enabled)| Pair {
+            name,
+            value,
+            enabled,
+        })
+        .collect()
+}
+
+/// The version a file was written with. Version 1 is the single line JSON
+/// object of the serialized `Request`, written before the format had a
+/// version field; YAML files without one are version 2.
+fn version(value: &Value) -> Result<u64, String> {
+    match value.get("version") {
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| format!("version {} is not a number", version)),
+        None if value.get("verb").is_some() => Ok(1),
+        None => Ok(2),
+    }
+}
+
+/// Version 1 to 2: headers and params become named entries, the body payload
+/// moves to `text` and `address`/`verb` are renamed. The embedded response is
+/// dropped here; `from_rph` keeps it in memory.
+fn from_json(value: Value) -> Result<Value, String> {
+    let Value::Object(mut old) = value else {
+        return Err("the file is not an object".to_string());
+    };
+    let mut file = Map::new();
+    file.insert("version".to_string(), json!(2));
+    file.insert(
+        "name".to_string(),
+        old.remove("name").unwrap_or_else(|| json!("")),
+    );
+    file.insert(
+        "mode".to_string(),
+        old.remove("mode").unwrap_or_else(|| json!("REST")),
+    );
+    file.insert(
+        "method".to_string(),
+        old.remove("verb").unwrap_or_else(|| json!("GET")),
+    );
+    file.insert(
+        "url".to_string(),
+        old.remove("address").unwrap_or_else(|| json!("")),
+    );
+    file.insert(
+        "params".to_string(),
+        Value::Array(json_pairs(old.remove("params"), "params")?),
+    );
+    file.insert(
+        "headers".to_string(),
+        Value::Array(json_pairs(old.remove("headers"), "headers")?),
+    );
+    match old.remove("body") {
+        None | Some(Value::Null) => (),
+        Some(Value::Object(mut body)) => {
+            let text = match body.remove("payload") {
+                None | Some(Value::Null) => json!(""),
+                Some(text) => text,
+            };
+            let kind = body.remove("kind").unwrap_or_else(|| json!("JSON"));
+            file.insert("body".to_string(), json!({"kind": kind, "text": text}));
+        }
+        Some(_) => return Err("body is not an object".to_string()),
+    }
+    if let Some(filter) = old.remove("response_filter") {
+        file.insert("response_filter".to_string(), filter);
+    }
+    Ok(Value::Object(file))
+}
+
+/// Version 1 stored headers and params as `[key, value, active]` arrays;
+/// older files have no active flag.
+fn json_pairs(items: Option<Value>, field: &str) -> Result<Vec<Value>, String> {
+    let items = match items {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items,
+        Some(_) => return Err(format!("{} is not a list", field)),
+    };
+    items
+        .into_iter()
+        .map(|item| match item.as_array().map(|i| i.as_slice()) {
+            Some([name, value]) => Ok(json!({"name": name, "value": value})),
+            Some([name, value, enabled]) => {
+                Ok(json!({"name": name, "value": value, "enabled": enabled}))
+            }
+            _ => Err(format!("{} entry {} is not a key/value pair", field, item)),
+        })
+        .collect()
+}
+
+impl Request {
+    /// Reads a `.rph` file of any known version.
+    pub fn from_rph(content: &str) -> Result<Request, Error> {
+        Ok(Self::migrate_rph(content)?.0)
+    }
+    /// Reads a `.rph` file, upgrading it to the current version, and returns
+    /// the version it was written with.
+    pub fn migrate_rph(content: &str) -> Result<(Request, u64), Error> {
+        let mut value: Value = if content.trim_start().starts_with('{') {
+            serde_json::from_str(content)?
+        } else {
+            serde_yaml::from_str(content)?
+        };
+        let written = version(&value).map_err(Error::MigrationErr)?;
+        if written > RPH_VERSION {
+            return Err(Error::MigrationErr(format!(
+                "written with format version {}, this build reads up to version {}",
+                written, RPH_VERSION
+            )));
+        }
+        if written == 0 {
+            return Err(Error::MigrationErr("unknown format version 0".to_string()));
+        }
+        // version 1 files carry the last response, which now lives in the history
+        let response = match written {
+            1 => value
+                .get("response")
+                .cloned()
+                .and_then(|r| serde_json::from_value::<Response>(r).ok()),
+            _ => None,
+        };
+        for (idx, migrate) in MIGRATIONS.iter().enumerate().skip(written as usize - 1) {
+            value = migrate(value).map_err(|e| {
+                Error::MigrationErr(format!(
+                    "cannot upgrade from version {} to {}: {}",
+                    idx + 1,
+                    idx + 2,
+                    e
+                ))
+            })?;
+        }
+        let file: RequestFile = serde_json::from_value(value).map_err(|e| {
+            Error::MigrationErr(format!(
+                "not a valid version {} request: {}",
+                RPH_VERSION, e
+            ))
+        })?;
+        let mut req = Request::new(file.mode);
+        req.set_name(file.name);
+        req.set_verb(file.method);
+        req.set_address(file.url);
+        for pair in file.params {
+            req.add_to_param(pair.name, pair.value, pair.enabled);
+        }
+        for pair in file.headers {
+            req.add_to_header(pair.name, pair.value, pair.enabled);
+        }
+        if let Some(body) = file.body {
+            req.set_body(Body {
+                kind: body.kind,
+                payload: Some(body.text),
+            });
+        }
+        req.set_response_filter(file.response_filter);
+        req.set_variables(
+            file.variables
+                .into_iter()
+                .map(|pair| (pair.name, pair.value))
+                .collect(),
+        );
+        req.set_response(response);
+        Ok((req, written))
+    }
+    pub fn to_rph(&self) -> Result<String, Error> {
+        let body = self.body();
+        let text = body.payload.unwrap_or_default();
+        let file = RequestFile {
+            version: RPH_VERSION,
+            name: self.name(),
+            mode: self.mode.clone(),
+            method: self.verb(),
+            url: self.address(),
+            params: pairs(self.params()),
+            headers: pairs(self.headers()),
+            body: match (&body.kind, text.is_empty()) {
+                (BodyKind::JSON, true) => None,
+                _ => Some(FileBody {
+                    kind: body.kind,
+                    text,
+                }),
+            },
+            response_filter: self.response_filter(),
+            variables: self
+                .variables()
+                .iter()
+                .map(|(name, value)| Pair {
+                    name: name.clone(),
+                    value: value.clone(),
+                    enabled: true,
+                })
+                .collect(),
+        };
+        Ok(serde_yaml::to_string(&file)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A request saved by the versions before the `.rph` format had a version,
+    /// as serde wrote `Request`.
+    const BASELINE: &str = r#"{"mode":"REST","name":"users","headers":[["Accept","application/json",true],["X-Old","1",false]],"params":[["page","2",true]],"body":{"kind":"JSON","payload":"{\"a\": 1}"},"address":"https://example.com/users","verb":"POST","response":null,"response_filter":".a"}"#;
+
+    const V2: &str = "\
+version: 2
+name: users
+mode: REST
+method: PUT
+url: https://example.com/users
+params:
+- name: page
+  value: '2'
+headers:
+- name: X-Old
+  value: '1'
+  enabled: false
+body:
+  kind: TEXT
+  text: |-
+    line one
+    line two
+variables:
+- name: id
+  value: '7'
+";
+
+    #[test]
+    fn reads_baseline_json_without_response() {
+        let (req, written) = Request::migrate_rph(BASELINE).unwrap();
+        assert_eq!(written, 1);
+        assert_eq!(req.name(), "users");
+        assert_eq!(req.verb().to_string(), "POST");
+        assert_eq!(req.address(), "https://example.com/users");
+        assert_eq!(
+            req.headers(),
+            Some(vec![
+                ("Accept".to_string(), "application/json".to_string(), true),
+                ("X-Old".to_string(), "1".to_string(), false),
+            ])
+        );
+        assert_eq!(
+            req.params(),
+            Some(vec![("page".to_string(), "2".to_string(), true)])
+        );
+        assert!(matches!(req.body().kind, BodyKind::JSON));
+        assert_eq!(req.body().payload.as_deref(), Some("{\"a\": 1}"));
+        assert_eq!(req.response_filter().as_deref(), Some(".a"));
+        assert!(req.response().is_none());
+    }
+
+    #[test]
+    fn reads_baseline_json_with_response() {
+        let content = BASELINE.replace(
+            r#""response":null"#,
+            r#""response":{"headers":{"content-type":"text/plain"},"body":"ok","status_code":201}"#,
+        );
+        let req = Request::from_rph(&content).unwrap();
+        let response = req.response().unwrap();
+        assert_eq!(response.status_code, 201);
+        assert_eq!(response.body.as_deref(), Some("ok"));
+        // the response is not written back into the file
+        assert!(!req.to_rph().unwrap().contains("status_code"));
+    }
+
+    #[test]
+    fn reads_baseline_json_with_two_item_pairs() {
+        let content = BASELINE.replace(r#"["page","2",true]"#, r#"["page","2"]"#);
+        let req = Request::from_rph(&content).unwrap();
+        assert_eq!(
+            req.params(),
+            Some(vec![("page".to_string(), "2".to_string(), true)])
+        );
+    }
+
+    #[test]
+    fn reads_and_writes_v2_yaml() {
+        let (req, written) = Request::migrate_rph(V2).unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(req.verb().to_string(), "PUT");
+        assert_eq!(
+            req.headers(),
+            Some(vec![("X-Old".to_string(), "1".to_string(), false)])
+        );
+        assert!(matches!(req.body().kind, BodyKind::TEXT));
+        assert_eq!(req.body().payload.as_deref(), Some("line one\nline two"));
+        assert_eq!(req.variables(), &[("id".to_string(), "7".to_string())]);
+        assert_eq!(req.to_rph().unwrap(), V2);
+    }
+
+    #[test]
+    fn upgrades_baseline_json_to_v2_yaml() {
+        let req = Request::from_rph(BASELINE).unwrap();
+        let written = req.to_rph().unwrap();
+        assert!(written.starts_with("version: 2\n"));
+        let (again, version) = Request::migrate_rph(&written).unwrap();
+        assert_eq!(version, 2);
+        assert_eq!(again.headers(), req.headers());
+        assert_eq!(again.body().payload, req.body().payload);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let content = V2.replace("version: 2", "version: 3");
+        match Request::from_rph(&content) {
+            Err(Error::MigrationErr(e)) => assert!(e.contains("version 3"), "{}", e),
+            other => panic!(
+                "expected a migration error, got {:?}",
+                other.map(|r| r.name())
+            ),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(Request::from_rph("version: 0\nname: x\n").is_err());
+        assert!(Request::from_rph("version: two\n").is_err());
+        assert!(Request::from_rph("version: 2\nname: x\n").is_err());
+        assert!(Request::from_rph(r#"{"verb":"GET","headers":"x"}"#).is_err());
+    }
+}
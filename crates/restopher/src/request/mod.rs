@@ -1,6 +1,8 @@
 mod body;
+mod file;
 mod request;
 
 pub use body::{Body, BodyKind};
+pub use file::RPH_VERSION;
 pub use request::{all_modes, Mode};
-pub use request::{HttpVerb, Request};
+pub use request::{HttpVerb, Request, Response};
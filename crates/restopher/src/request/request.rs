@@ -1,15 +1,14 @@
+use crate::history::{History, HistoryEntry};
 use crate::request::body::{Body, BodyKind};
+use crate::theme;
 use crate::trace_dbg;
-use ratatui::style::{Color, Style};
+use crate::xml;
+use chrono::Local;
+use ratatui::style::Style;
 use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
-enum Error {
-    NotParsable,
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Mode {
     REST,
@@ -29,11 +28,15 @@ pub fn all_modes<'a>() -> &'a [Mode] {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum HttpVerb {
     GET,
     POST,
     PUT,
     DELETE,
+    PATCH,
+    HEAD,
+    OPTIONS,
 }
 impl HttpVerb {
     pub fn to_string(&self) -> String {
@@ -42,31 +45,70 @@ impl HttpVerb {
             HttpVerb::POST => "POST".to_string(),
             HttpVerb::DELETE => "DELETE".to_string(),
             HttpVerb::PUT => "PUT".to_string(),
+            HttpVerb::PATCH => "PATCH".to_string(),
+            HttpVerb::HEAD => "HEAD".to_string(),
+            HttpVerb::OPTIONS => "OPTIONS".to_string(),
+        }
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_uppercase().as_str() {
+            "GET" => Some(HttpVerb::GET),
+            "POST" => Some(HttpVerb::POST),
+            "PUT" => Some(HttpVerb::PUT),
+            "DELETE" => Some(HttpVerb::DELETE),
+            "PATCH" => Some(HttpVerb::PATCH),
+            "HEAD" => Some(HttpVerb::HEAD),
+            "OPTIONS" => Some(HttpVerb::OPTIONS),
+            _ => None,
+        }
+    }
+    pub fn method(&self) -> reqwest::Method {
+        match self {
+            HttpVerb::GET => reqwest::Method::GET,
+            HttpVerb::POST => reqwest::Method::POST,
+            HttpVerb::PUT => reqwest::Method::PUT,
+            HttpVerb::DELETE => reqwest::Method::DELETE,
+            HttpVerb::PATCH => reqwest::Method::PATCH,
+            HttpVerb::HEAD => reqwest::Method::HEAD,
+            HttpVerb::OPTIONS => reqwest::Method::OPTIONS,
         }
     }
     pub fn down(&self) -> Self {
         match self {
             HttpVerb::GET => HttpVerb::POST,
             HttpVerb::POST => HttpVerb::PUT,
-            HttpVerb::PUT => HttpVerb::DELETE,
-            HttpVerb::DELETE => HttpVerb::GET,
+            HttpVerb::PUT => HttpVerb::PATCH,
+            HttpVerb::PATCH => HttpVerb::DELETE,
+            HttpVerb::DELETE => HttpVerb::HEAD,
+            HttpVerb::HEAD => HttpVerb::OPTIONS,
+            HttpVerb::OPTIONS => HttpVerb::GET,
         }
     }
     pub fn up(&self) -> Self {
         match self {
-            HttpVerb::GET => HttpVerb::DELETE,
+            HttpVerb::GET => HttpVerb::OPTIONS,
             HttpVerb::POST => HttpVerb::GET,
             HttpVerb::PUT => HttpVerb::POST,
-            HttpVerb::DELETE => HttpVerb::PUT,
+            HttpVerb::PATCH => HttpVerb::PUT,
+            HttpVerb::DELETE => HttpVerb::PATCH,
+            HttpVerb::HEAD => HttpVerb::DELETE,
+            HttpVerb::OPTIONS => HttpVerb::HEAD,
         }
     }
+    /// Whether the request body is sent with this verb.
+    pub fn sends_body(&self) -> bool {
+        !matches!(self, HttpVerb::GET | HttpVerb::HEAD | HttpVerb::OPTIONS)
+    }
     pub fn style(&self) -> Style {
         let style = Style::default();
+        let verbs = &theme::get().verbs;
         match self {
-            HttpVerb::GET => style.fg(Color::Green),
-            HttpVerb::POST => style.fg(Color::Yellow),
-            HttpVerb::PUT => style.fg(Color::Blue),
-            HttpVerb::DELETE => style.fg(Color::Red),
+            HttpVerb::GET => style.fg(verbs.get),
+            HttpVerb::POST => style.fg(verbs.post),
+            HttpVerb::PUT => style.fg(verbs.put),
+            HttpVerb::DELETE => style.fg(verbs.delete),
+            HttpVerb::PATCH => style.fg(verbs.patch),
+            HttpVerb::HEAD | HttpVerb::OPTIONS => style.fg(verbs.other),
         }
     }
 }
@@ -106,6 +148,14 @@ pub struct Request {
     address: String,
     verb: HttpVerb,
     response: Option<Response>,
+    #[serde(default)]
+    response_filter: Option<String>,
+    /// Variables defined next to the request, such as the `@var = value`
+    /// lines of a `.http` file. They take precedence over the environment.
+    #[serde(skip)]
+    variables: Vec<(String, String)>,
+    #[serde(skip)]
+    history: History,
 }
 
 impl Request {
@@ -123,11 +173,18 @@ impl Request {
             address: "".to_string(),
             verb: HttpVerb::GET,
             response: None,
+            response_filter: None,
+            variables: Vec::new(),
+            history: History::default(),
         }
     }
     pub fn set_collection_path<'a>(&'a mut self, path: String) {
         self.from_collection_path = Some(path);
     }
+    /// Detaches the request from a deleted file, it is saved as a new one.
+    pub fn clear_collection_path(&mut self) {
+        self.from_collection_path = None;
+    }
     pub fn collection_path(&self) -> Option<String> {
         self.from_collection_path.clone()
     }
@@ -151,15 +208,51 @@ impl Request {
     pub fn response(&self) -> Option<Response> {
         self.response.clone()
     }
+    pub fn variables(&self) -> &[(String, String)] {
+        &self.variables
+    }
+    pub fn set_variables(&mut self, variables: Vec<(String, String)>) {
+        self.variables = variables;
+    }
+    pub fn set_response(&mut self, response: Option<Response>) {
+        self.response = response;
+    }
+    pub fn response_filter(&self) -> Option<String> {
+        self.response_filter.clone()
+    }
+    pub fn set_response_filter(&mut self, filter: Option<String>) {
+        self.response_filter = filter.filter(|f| !f.is_empty());
+    }
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+    pub fn set_history(&mut self, history: History) {
+        self.history = history;
+    }
+    /// Records the current response in the history, and persists the history
+    /// when the request belongs to a collection.
+    pub fn push_history(&mut self, environment: String) -> Result<(), crate::app::Error> {
+        if let Some(resp) = &self.response {
+            self.history.push(HistoryEntry {
+                timestamp: Local::now(),
+                environment,
+                response: resp.clone(),
+            });
+        }
+        if let Some(path) = &self.from_collection_path {
+            self.history.save(path)?;
+        }
+        Ok(())
+    }
     pub fn add_to_header(&mut self, key: String, value: String, active: bool) {
-        if !key.is_empty() && !value.is_empty() {
+        if !key.is_empty() {
             self.headers
                 .get_or_insert_with(|| Vec::new())
                 .push((key, value, active));
         }
     }
     pub fn add_to_param(&mut self, key: String, value: String, active: bool) {
-        if !key.is_empty() && !value.is_empty() {
+        if !key.is_empty() {
             self.params
                 .get_or_insert_with(|| Vec::new())
                 .push((key, value, active));
@@ -168,6 +261,9 @@ impl Request {
     pub fn set_address(&mut self, a: String) {
         self.address = a;
     }
+    pub fn set_verb(&mut self, verb: HttpVerb) {
+        self.verb = verb;
+    }
     pub fn verb_up(&mut self) {
         self.verb = self.verb.up();
     }
@@ -177,43 +273,32 @@ impl Request {
     pub fn verb(&self) -> HttpVerb {
         self.verb.clone()
     }
+    pub fn response_content_type(&self) -> Option<String> {
+        self.response
+            .as_ref()?
+            .headers
+            .as_ref()?
+            .iter()
+            .filter(|item| item.0.eq_ignore_ascii_case("content-type"))
+            .last()
+            .map(|item| item.1.clone())
+    }
     pub fn resp_body_formatted(&self) -> String {
-        if let Some(resp) = &self.response {
-            if let Some(body) = &resp.body {
-                let mut ct: Option<(&String, &String)> = None;
-                if let Some(headers) = &resp.headers {
-                    ct = headers
-                        .iter()
-                        .filter(|item| item.0 == "content-type")
-                        .last();
-                };
-                //trace_dbg!(level:tracing::Level::INFO, &ct);
-                match ct {
-                    Some(content_type) => {
-                        match content_type {
-                            _ if content_type.1.contains("application/json") => {
-                                match &serde_json::from_str::<serde_json::Value>(&body.clone()) {
-                                    Ok(data) => {
-                                        return serde_json::to_string_pretty(data)
-                                            .unwrap_or_else(|_| body.clone())
-                                    }
-                                    Err(_) => return body.clone(),
-                                }
-                            }
-                            _ if content_type.1.contains("text/html") => {
-                                match deserialize_xml(body.clone()) {
-                                    Ok(data) => return data,
-                                    Err(_) => return body.clone(),
-                                }
-                            }
-                            _ => return body.clone(),
-                        };
-                    }
-                    None => return body.clone(),
-                };
-            };
+        let Some(body) = self.response.as_ref().and_then(|r| r.body.as_ref()) else {
+            return String::from("");
         };
-        String::from("")
+        //trace_dbg!(level:tracing::Level::INFO, &ct);
+        match self.response_content_type() {
+            Some(ct) if ct.contains("application/json") => {
+                match &serde_json::from_str::<serde_json::Value>(body) {
+                    Ok(data) => serde_json::to_string_pretty(data).unwrap_or_else(|_| body.clone()),
+                    Err(_) => body.clone(),
+                }
+            }
+            Some(ct) if xml::is_xml_content_type(&ct) => xml::format(body, false),
+            Some(ct) if xml::is_html_content_type(&ct) => xml::format(body, true),
+            _ => body.clone(),
+        }
     }
     pub fn headers(&self) -> Option<Vec<(String, String, bool)>> {
         self.headers.clone()
@@ -249,17 +334,6 @@ impl Request {
     pub fn body(&self) -> Body {
         self.body.clone()
     }
-    pub fn handle_json_body(&self) -> Result<Option<serde_json::Value>, crate::app::Error> {
-        match &self.body.payload {
-            Some(data) => {
-                if data.is_empty() {
-                    return Ok(None);
-                }
-                serde_json::from_str(&*data.clone()).map_err(|e| crate::app::Error::JsonErr(e))
-            }
-            None => Ok(None),
-        }
-    }
     pub fn params(&self) -> Option<Vec<(String, String, bool)>> {
         self.params.clone()
     }
@@ -317,9 +391,16 @@ impl Request {
             params.remove(param_idx);
         }
     }
-}
-
-fn deserialize_xml(content: String) -> Result<String, Error> {
-    let data = serde_xml_rs::from_str(&content).map_err(|_| Error::NotParsable)?;
-    data
+    /// Sets the variable, replacing any of the same name.
+    pub fn add_variable(&mut self, name: String, value: String) {
+        if !name.is_empty() {
+            self.variables.retain(|(n, _)| *n != name);
+            self.variables.push((name, value));
+        }
+    }
+    pub fn remove_variable(&mut self, idx: usize) {
+        if idx < self.variables.len() {
+            self.variables.remove(idx);
+        }
+    }
 }
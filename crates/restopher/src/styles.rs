@@ -1,3 +1,5 @@
+use crate::highlight::TokenKind;
+use crate::theme;
 use ratatui::{
     style::{Color, Modifier, Style},
     text::Span,
@@ -8,12 +10,47 @@ pub fn cursor_like_span<'a>(c: char) -> Span<'a> {
     Span::raw(c.to_string()).style(
         Style::default()
             .add_modifier(Modifier::UNDERLINED)
-            .underline_color(Color::Blue),
+            .underline_color(theme::get().cursor),
     )
 }
 
 pub fn selected_box<'a>() -> Block<'a> {
     Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::LightGreen))
+        .border_style(Style::default().fg(theme::get().highlight_bg))
+}
+
+pub fn search_match_style(is_current: bool) -> Style {
+    let search = &theme::get().search;
+    let style = Style::default().fg(search.text);
+    if is_current {
+        style.bg(search.current)
+    } else {
+        style.bg(search.matched)
+    }
+}
+
+pub fn status_color(status_code: i32) -> Color {
+    let status = &theme::get().status;
+    if (200..300).contains(&status_code) {
+        status.success
+    } else if (300..400).contains(&status_code) {
+        status.redirect
+    } else {
+        status.error
+    }
+}
+
+pub fn syntax_style(kind: TokenKind) -> Style {
+    let syntax = &theme::get().syntax;
+    let color = match kind {
+        TokenKind::Key | TokenKind::Tag => syntax.key,
+        TokenKind::String => syntax.string,
+        TokenKind::Number => syntax.number,
+        TokenKind::Boolean | TokenKind::Keyword => syntax.keyword,
+        TokenKind::Punctuation => syntax.punctuation,
+        TokenKind::Attribute | TokenKind::Variable => syntax.attribute,
+        TokenKind::Comment => syntax.comment,
+    };
+    Style::default().fg(color)
 }
@@ -0,0 +1,474 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use ratatui::style::Color;
+
+use crate::THEME_PATH;
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Themes shipped with restopher, selected with `theme` in `config.toml`.
+pub const BUILT_IN: [&str; 3] = ["dark", "light", "high-contrast"];
+
+pub struct VerbColors {
+    pub get: Color,
+    pub post: Color,
+    pub put: Color,
+    pub delete: Color,
+    pub patch: Color,
+    /// HEAD and OPTIONS.
+    pub other: Color,
+}
+
+pub struct StatusColors {
+    /// 2xx
+    pub success: Color,
+    /// 3xx
+    pub redirect: Color,
+    /// Everything else.
+    pub error: Color,
+}
+
+pub struct SyntaxColors {
+    /// Object keys and tags.
+    pub key: Color,
+    pub string: Color,
+    pub number: Color,
+    /// Booleans, null and keywords.
+    pub keyword: Color,
+    pub punctuation: Color,
+    /// Attributes and variables.
+    pub attribute: Color,
+    pub comment: Color,
+}
+
+pub struct SearchColors {
+    pub text: Color,
+    pub matched: Color,
+    pub current: Color,
+}
+
+pub struct DiffColors {
+    pub added: Color,
+    pub removed: Color,
+    pub modified: Color,
+    /// Filler on the side a line is missing from.
+    pub missing: Color,
+}
+
+pub struct PopupColors {
+    pub border: Color,
+    pub title: Color,
+    pub error: Color,
+}
+
+/// Every color the interface draws with.
+pub struct Theme {
+    pub text: Color,
+    pub border: Color,
+    /// Border of the focused block.
+    pub focus: Color,
+    pub title: Color,
+    /// Secondary text, such as summaries and hints.
+    pub muted: Color,
+    /// Keys of tables and trees.
+    pub label: Color,
+    /// Selected tab, list entry or GraphQL pane.
+    pub selected: Color,
+    /// Highlighted table row.
+    pub row_fg: Color,
+    pub row_bg: Color,
+    /// Highlighted tree node, collection entry or button.
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    pub cursor: Color,
+    pub success: Color,
+    pub error: Color,
+    pub verbs: VerbColors,
+    pub status: StatusColors,
+    pub syntax: SyntaxColors,
+    pub search: SearchColors,
+    pub diff: DiffColors,
+    pub popup: PopupColors,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            text: Color::White,
+            border: Color::White,
+            focus: Color::Red,
+            title: Color::White,
+            muted: Color::DarkGray,
+            label: Color::LightBlue,
+            selected: Color::Yellow,
+            row_fg: Color::White,
+            row_bg: Color::Blue,
+            highlight_fg: Color::Black,
+            highlight_bg: Color::LightGreen,
+            cursor: Color::Blue,
+            success: Color::Green,
+            error: Color::Red,
+            verbs: VerbColors {
+                get: Color::Green,
+                post: Color::Yellow,
+                put: Color::Blue,
+                delete: Color::Red,
+                patch: Color::Magenta,
+                other: Color::Cyan,
+            },
+            status: StatusColors {
+                success: Color::Green,
+                redirect: Color::Yellow,
+                error: Color::Red,
+            },
+            syntax: SyntaxColors {
+                key: Color::Cyan,
+                string: Color::Green,
+                number: Color::Magenta,
+                keyword: Color::Yellow,
+                punctuation: Color::DarkGray,
+                attribute: Color::LightBlue,
+                comment: Color::Gray,
+            },
+            search: SearchColors {
+                text: Color::Black,
+                matched: Color::Yellow,
+                current: Color::LightRed,
+            },
+            diff: DiffColors {
+                added: Color::Green,
+                removed: Color::Red,
+                modified: Color::Yellow,
+                missing: Color::DarkGray,
+            },
+            popup: PopupColors {
+                border: Color::White,
+                title: Color::White,
+                error: Color::Red,
+            },
+        }
+    }
+    /// For terminals with a light background: no white or yellow text.
+    pub fn light() -> Self {
+        Theme {
+            text: Color::Black,
+            border: Color::DarkGray,
+            focus: Color::Red,
+            title: Color::Black,
+            muted: Color::Gray,
+            label: Color::Blue,
+            selected: Color::Magenta,
+            row_fg: Color::White,
+            row_bg: Color::Blue,
+            highlight_fg: Color::White,
+            highlight_bg: Color::Green,
+            cursor: Color::Blue,
+            success: Color::Green,
+            error: Color::Red,
+            verbs: VerbColors {
+                get: Color::Green,
+                post: Color::Rgb(0xb0, 0x60, 0x00),
+                put: Color::Blue,
+                delete: Color::Red,
+                patch: Color::Magenta,
+                other: Color::Cyan,
+            },
+            status: StatusColors {
+                success: Color::Green,
+                redirect: Color::Rgb(0xb0, 0x60, 0x00),
+                error: Color::Red,
+            },
+            syntax: SyntaxColors {
+                key: Color::Blue,
+                string: Color::Green,
+                number: Color::Magenta,
+                keyword: Color::Rgb(0xb0, 0x60, 0x00),
+                punctuation: Color::DarkGray,
+                attribute: Color::Cyan,
+                comment: Color::Gray,
+            },
+            search: SearchColors {
+                text: Color::Black,
+                matched: Color::LightYellow,
+                current: Color::LightRed,
+            },
+            diff: DiffColors {
+                added: Color::Green,
+                removed: Color::Red,
+                modified: Color::Rgb(0xb0, 0x60, 0x00),
+                missing: Color::Gray,
+            },
+            popup: PopupColors {
+                border: Color::Black,
+                title: Color::Black,
+                error: Color::Red,
+            },
+        }
+    }
+    /// Pure black and white with the Okabe-Ito palette, which stays apart for
+    /// the common kinds of color blindness: orange and blue take the place of
+    /// red and green.
+    pub fn high_contrast() -> Self {
+        const ORANGE: Color = Color::Rgb(0xe6, 0x9f, 0x00);
+        const SKY_BLUE: Color = Color::Rgb(0x56, 0xb4, 0xe9);
+        const GREEN: Color = Color::Rgb(0x00, 0x9e, 0x73);
+        const YELLOW: Color = Color::Rgb(0xf0, 0xe4, 0x42);
+        const BLUE: Color = Color::Rgb(0x00, 0x72, 0xb2);
+        const VERMILLION: Color = Color::Rgb(0xd5, 0x5e, 0x00);
+        const PURPLE: Color = Color::Rgb(0xcc, 0x79, 0xa7);
+        const WHITE: Color = Color::Rgb(0xff, 0xff, 0xff);
+        const BLACK: Color = Color::Rgb(0x00, 0x00, 0x00);
+        const GRAY: Color = Color::Rgb(0xbb, 0xbb, 0xbb);
+        Theme {
+            text: WHITE,
+            border: GRAY,
+            focus: YELLOW,
+            title: WHITE,
+            muted: GRAY,
+            label: SKY_BLUE,
+            selected: YELLOW,
+            row_fg: BLACK,
+            row_bg: YELLOW,
+            highlight_fg: BLACK,
+            highlight_bg: YELLOW,
+            cursor: YELLOW,
+            success: SKY_BLUE,
+            error: ORANGE,
+            verbs: VerbColors {
+                get: SKY_BLUE,
+                post: YELLOW,
+                put: BLUE,
+                delete: VERMILLION,
+                patch: PURPLE,
+                other: GREEN,
+            },
+            status: StatusColors {
+                success: SKY_BLUE,
+                redirect: YELLOW,
+                error: ORANGE,
+            },
+            syntax: SyntaxColors {
+                key: SKY_BLUE,
+                string: GREEN,
+                number: PURPLE,
+                keyword: YELLOW,
+                punctuation: GRAY,
+                attribute: ORANGE,
+                comment: GRAY,
+            },
+            search: SearchColors {
+                text: BLACK,
+                matched: SKY_BLUE,
+                current: ORANGE,
+            },
+            diff: DiffColors {
+                added: SKY_BLUE,
+                removed: ORANGE,
+                modified: YELLOW,
+                missing: GRAY,
+            },
+            popup: PopupColors {
+                border: WHITE,
+                title: WHITE,
+                error: ORANGE,
+            },
+        }
+    }
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+    /// The color named `key` in a theme file, inside the `[section]` table
+    /// when there is one.
+    fn color_mut(&mut self, section: Option<&str>, key: &str) -> Option<&mut Color> {
+        Some(match (section, key) {
+            (None, "text") => &mut self.text,
+            (None, "border") => &mut self.border,
+            (None, "focus") => &mut self.focus,
+            (None, "title") => &mut self.title,
+            (None, "muted") => &mut self.muted,
+            (None, "label") => &mut self.label,
+            (None, "selected") => &mut self.selected,
+            (None, "row_fg") => &mut self.row_fg,
+            (None, "row_bg") => &mut self.row_bg,
+            (None, "highlight_fg") => &mut self.highlight_fg,
+            (None, "highlight_bg") => &mut self.highlight_bg,
+            (None, "cursor") => &mut self.cursor,
+            (None, "success") => &mut self.success,
+            (None, "error") => &mut self.error,
+            (Some("verbs"), "get") => &mut self.verbs.get,
+            (Some("verbs"), "post") => &mut self.verbs.post,
+            (Some("verbs"), "put") => &mut self.verbs.put,
+            (Some("verbs"), "delete") => &mut self.verbs.delete,
+            (Some("verbs"), "patch") => &mut self.verbs.patch,
+            (Some("verbs"), "other") => &mut self.verbs.other,
+            (Some("status"), "success") => &mut self.status.success,
+            (Some("status"), "redirect") => &mut self.status.redirect,
+            (Some("status"), "error") => &mut self.status.error,
+            (Some("syntax"), "key") => &mut self.syntax.key,
+            (Some("syntax"), "string") => &mut self.syntax.string,
+            (Some("syntax"), "number") => &mut self.syntax.number,
+            (Some("syntax"), "keyword") => &mut self.syntax.keyword,
+            (Some("syntax"), "punctuation") => &mut self.syntax.punctuation,
+            (Some("syntax"), "attribute") => &mut self.syntax.attribute,
+            (Some("syntax"), "comment") => &mut self.syntax.comment,
+            (Some("search"), "text") => &mut self.search.text,
+            (Some("search"), "matched") => &mut self.search.matched,
+            (Some("search"), "current") => &mut self.search.current,
+            (Some("diff"), "added") => &mut self.diff.added,
+            (Some("diff"), "removed") => &mut self.diff.removed,
+            (Some("diff"), "modified") => &mut self.diff.modified,
+            (Some("diff"), "missing") => &mut self.diff.missing,
+            (Some("popup"), "border") => &mut self.popup.border,
+            (Some("popup"), "title") => &mut self.popup.title,
+            (Some("popup"), "error") => &mut self.popup.error,
+            _ => return None,
+        })
+    }
+    /// Reads a user theme. The file starts from the built-in theme named by
+    /// `base`, dark by default, and overrides the colors it lists:
+    ///
+    /// ```toml
+    /// base = "light"
+    /// focus = "#d55e00"
+    ///
+    /// [verbs]
+    /// get = "green"
+    /// ```
+    ///
+    /// Colors are names such as `red` or `light-blue`, `#rrggbb` or an index
+    /// of the 256 color palette.
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+        let mut theme = match table.get("base") {
+            None => Theme::dark(),
+            Some(toml::Value::String(base)) => Theme::built_in(base).ok_or_else(|| {
+                format!(
+                    "base {} is not a built-in theme ({})",
+                    base,
+                    BUILT_IN.join(", ")
+                )
+            })?,
+            Some(_) => return Err("base is not a string".to_string()),
+        };
+        for (key, value) in &table {
+            match value {
+                toml::Value::Table(section) => {
+                    for (name, value) in section {
+                        theme.set(Some(key), name, value)?;
+                    }
+                }
+                _ if key == "base" => (),
+                _ => theme.set(None, key, value)?,
+            }
+        }
+        Ok(theme)
+    }
+    fn set(&mut self, section: Option<&str>, key: &str, value: &toml::Value) -> Result<(), String> {
+        let name = match section {
+            Some(section) => format!("{}.{}", section, key),
+            None => key.to_string(),
+        };
+        let color = self
+            .color_mut(section, key)
+            .ok_or_else(|| format!("unknown color {}", name))?;
+        *color = value
+            .as_str()
+            .and_then(|value| Color::from_str(value).ok())
+            .ok_or_else(|| format!("{}: {} is not a color", name, value))?;
+        Ok(())
+    }
+}
+
+/// Loads the theme called `name`: a file `<name>.toml` in the themes
+/// directory, or else a built-in theme. Called once at startup.
+pub fn init(data_dir: &str, name: &str) -> Result<&'static Theme, String> {
+    let path = Path::new(data_dir)
+        .join(THEME_PATH)
+        .join(format!("{}.toml", name));
+    let theme = match fs::read_to_string(&path) {
+        Ok(content) => {
+            Theme::from_toml(&content).map_err(|e| format!("{}: {}", path.display(), e))?
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Theme::built_in(name).ok_or_else(|| {
+                format!(
+                    "theme {} is neither built in ({}) nor a file in {}",
+                    name,
+                    BUILT_IN.join(", "),
+                    path.parent().unwrap_or(&path).display()
+                )
+            })?
+        }
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    Ok(THEME.get_or_init(|| theme))
+}
+
+pub fn get() -> &'static Theme {
+    THEME.get().expect("the theme is loaded at startup")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_built_in_theme_exists() {
+        for name in BUILT_IN {
+            assert!(Theme::built_in(name).is_some(), "{}", name);
+        }
+        assert!(Theme::built_in("solarized").is_none());
+    }
+
+    #[test]
+    fn theme_files_override_their_base() {
+        let theme = Theme::from_toml(
+            r##"
+            base = "light"
+            focus = "#d55e00"
+            cursor = "light-blue"
+
+            [verbs]
+            get = "208"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(theme.focus, Color::Rgb(0xd5, 0x5e, 0x00));
+        assert_eq!(theme.cursor, Color::LightBlue);
+        assert_eq!(theme.verbs.get, Color::Indexed(208));
+        // the rest comes from the base
+        assert_eq!(theme.text, Theme::light().text);
+        assert_eq!(theme.verbs.post, Theme::light().verbs.post);
+        assert_eq!(Theme::from_toml("").unwrap().text, Theme::dark().text);
+    }
+
+    #[test]
+    fn rejects_unknown_bases_and_colors() {
+        for (content, error) in [
+            (
+                "base = \"solarized\"",
+                "base solarized is not a built-in theme",
+            ),
+            ("base = 1", "base is not a string"),
+            ("focuss = \"red\"", "unknown color focuss"),
+            (
+                "[verbs]\nget = \"nope\"",
+                "verbs.get: \"nope\" is not a color",
+            ),
+            ("[verbs]\nget = 2", "verbs.get: 2 is not a color"),
+        ] {
+            match Theme::from_toml(content) {
+                Ok(_) => panic!("{} was accepted", content),
+                Err(e) => assert!(e.starts_with(error), "{}", e),
+            }
+        }
+    }
+}
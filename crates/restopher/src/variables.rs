@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Where a variable is defined. Later scopes take precedence: global, then
+/// collection and folders from the root down, then the environment, then the
+/// request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    /// The `[variables]` table of `config.toml`, whatever the environment.
+    Global,
+    /// The `defaults.yaml` of a top level folder of the collections.
+    Collection(String),
+    /// The `defaults.yaml` of the collections root or of a nested folder.
+    Folder(String),
+    Environment(String),
+    Request,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Global => write!(f, "global"),
+            Scope::Collection(name) => write!(f, "collection {}", name),
+            Scope::Folder(path) => write!(f, "folder {}", path),
+            Scope::Environment(name) => write!(f, "environment {}", name),
+            Scope::Request => write!(f, "request"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+    pub scope: Scope,
+    /// The weaker scopes defining it as well, nearest first.
+    pub overrides: Vec<Scope>,
+}
+
+/// The variables of a request, built layer by layer from the weakest scope.
+#[derive(Debug, Default)]
+pub struct Variables {
+    variables: Vec<Variable>,
+}
+
+impl Variables {
+    /// Adds the variables of `scope`, which take precedence over the ones
+    /// added before.
+    pub fn push(&mut self, scope: Scope, layer: impl IntoIterator<Item = (String, String)>) {
+        for (name, value) in layer {
+            match self.variables.iter_mut().find(|v| v.name == name) {
+                Some(variable) => {
+                    if variable.scope != scope {
+                        let weaker = std::mem::replace(&mut variable.scope, scope.clone());
+                        variable.overrides.insert(0, weaker);
+                    }
+                    variable.value = value;
+                }
+                None => self.variables.push(Variable {
+                    name,
+                    value,
+                    scope: scope.clone(),
+                    overrides: Vec::new(),
+                }),
+            }
+        }
+    }
+    /// The value of every variable, as the replacer takes them.
+    pub fn values(&self) -> HashMap<String, String> {
+        self.variables
+            .iter()
+            .map(|v| (v.name.clone(), v.value.clone()))
+            .collect()
+    }
+    /// Every variable, sorted by name.
+    pub fn resolved(&self) -> Vec<Variable> {
+        let mut variables = self.variables.clone();
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+        variables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn stronger_scopes_override_weaker_ones() {
+        let mut variables = Variables::default();
+        variables.push(Scope::Global, layer(&[("host", "global"), ("user", "ada")]));
+        variables.push(
+            Scope::Collection("shop".to_string()),
+            layer(&[("host", "shop"), ("token", "abc")]),
+        );
+        variables.push(
+            Scope::Environment("prod".to_string()),
+            layer(&[("host", "prod")]),
+        );
+        variables.push(Scope::Request, layer(&[("token", "request")]));
+
+        let resolved = variables.resolved();
+        let names = resolved
+            .iter()
+            .map(|v| v.name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(names, vec!["host", "token", "user"]);
+        assert_eq!(resolved[0].value, "prod");
+        assert_eq!(resolved[0].scope, Scope::Environment("prod".to_string()));
+        assert_eq!(
+            resolved[0].overrides,
+            vec![Scope::Collection("shop".to_string()), Scope::Global]
+        );
+        assert_eq!(resolved[1].scope, Scope::Request);
+        assert!(resolved[2].overrides.is_empty());
+        assert_eq!(
+            variables.values().get("token").map(String::as_str),
+            Some("request")
+        );
+    }
+
+    #[test]
+    fn redefining_in_the_same_scope_is_not_an_override() {
+        let mut variables = Variables::default();
+        variables.push(Scope::Request, layer(&[("id", "1"), ("id", "2")]));
+        let resolved = variables.resolved();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].value, "2");
+        assert!(resolved[0].overrides.is_empty());
+    }
+
+    #[test]
+    fn scopes_are_described() {
+        assert_eq!(Scope::Folder("/".to_string()).to_string(), "folder /");
+        assert_eq!(
+            Scope::Collection("shop".to_string()).to_string(),
+            "collection shop"
+        );
+    }
+}
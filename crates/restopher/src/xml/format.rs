@@ -0,0 +1,90 @@
+use super::tokenizer::{tokenize, Token};
+use super::{closes_implicitly, VOID_ELEMENTS};
+
+const INDENT: &str = "  ";
+
+/// Pretty prints an XML or HTML document. The formatter never fails: unknown or
+/// unbalanced markup is indented as well as possible and kept otherwise intact.
+pub fn format(input: &str, html: bool) -> String {
+    let tokens = tokenize(input, html);
+    let mut lines: Vec<String> = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        if let Token::Open { name, .. } = &tokens[idx] {
+            while html && stack.last().is_some_and(|top| closes_implicitly(top, name)) {
+                stack.pop();
+            }
+        }
+        let depth = stack.len();
+        match &tokens[idx] {
+            Token::Open {
+                name,
+                raw,
+                self_closing,
+            } => {
+                if *self_closing || (html && VOID_ELEMENTS.contains(&name.as_str())) {
+                    push_line(&mut lines, depth, raw);
+                } else if let Some((text, close)) = inline_text(&tokens[idx + 1..], name) {
+                    push_line(&mut lines, depth, &format!("{}{}{}", raw, text, close));
+                    idx += 2;
+                } else if let [Token::Close {
+                    name: closing,
+                    raw: close,
+                }, ..] = &tokens[idx + 1..]
+                {
+                    if closing == name {
+                        push_line(&mut lines, depth, &format!("{}{}", raw, close));
+                        idx += 1;
+                    } else {
+                        push_line(&mut lines, depth, raw);
+                        stack.push(name.clone());
+                    }
+                } else {
+                    push_line(&mut lines, depth, raw);
+                    stack.push(name.clone());
+                }
+            }
+            Token::Close { name, raw } => {
+                if let Some(pos) = stack.iter().rposition(|n| n == name) {
+                    stack.truncate(pos);
+                }
+                push_line(&mut lines, stack.len(), raw);
+            }
+            Token::Text(text) => {
+                for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    push_line(&mut lines, depth, line);
+                }
+            }
+            Token::RawText(text) => {
+                let text = text.trim_matches(|c| c == '\n' || c == '\r');
+                if !text.trim().is_empty() {
+                    lines.extend(text.lines().map(String::from));
+                }
+            }
+            Token::Comment(text) | Token::CData(text) | Token::Declaration(text) => {
+                for line in text.lines() {
+                    push_line(&mut lines, depth, line.trim_end());
+                }
+            }
+        }
+        idx += 1;
+    }
+    lines.join("\n")
+}
+
+/// Short text directly wrapped by its element stays on one line: `<a>text</a>`.
+fn inline_text<'a>(rest: &[Token<'a>], name: &str) -> Option<(&'a str, &'a str)> {
+    match rest {
+        [Token::Text(text), Token::Close { name: closing, raw }, ..]
+            if closing == name && !text.trim().contains('\n') =>
+        {
+            Some((text.trim(), raw))
+        }
+        _ => None,
+    }
+}
+
+fn push_line(lines: &mut Vec<String>, depth: usize, content: &str) {
+    lines.push(format!("{}{}", INDENT.repeat(depth), content.trim_start()));
+}
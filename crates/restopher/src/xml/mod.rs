@@ -0,0 +1,67 @@
+mod format;
+mod tokenizer;
+mod tree;
+mod xpath;
+
+pub use format::format;
+pub use xpath::apply_to_body;
+
+/// HTML elements that never have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// HTML elements whose content is kept verbatim.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "pre", "textarea"];
+
+/// HTML elements closed implicitly when one of the listed elements is opened
+/// right after them, e.g. `<li>a<li>b`.
+const IMPLIED_END: &[(&str, &[&str])] = &[
+    ("li", &["li"]),
+    ("dt", &["dt", "dd"]),
+    ("dd", &["dt", "dd"]),
+    ("option", &["option"]),
+    ("tr", &["tr"]),
+    ("td", &["td", "th", "tr"]),
+    ("th", &["td", "th", "tr"]),
+    (
+        "p",
+        &[
+            "p",
+            "div",
+            "ul",
+            "ol",
+            "dl",
+            "table",
+            "form",
+            "pre",
+            "blockquote",
+            "section",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+        ],
+    ),
+];
+
+/// Whether opening `name` implicitly closes the still open `current` element.
+fn closes_implicitly(current: &str, name: &str) -> bool {
+    IMPLIED_END
+        .iter()
+        .any(|(open, closers)| *open == current && closers.contains(&name))
+}
+
+pub fn is_xml_content_type(content_type: &str) -> bool {
+    let content_type = content_type.to_lowercase();
+    content_type.contains("application/xml")
+        || content_type.contains("text/xml")
+        || content_type.contains("+xml")
+}
+
+pub fn is_html_content_type(content_type: &str) -> bool {
+    content_type.to_lowercase().contains("text/html")
+}
@@ -0,0 +1,290 @@
+use super::RAW_TEXT_ELEMENTS;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token<'a> {
+    /// `<?xml ...?>` processing instructions and `<!DOCTYPE ...>`.
+    Declaration(&'a str),
+    Comment(&'a str),
+    CData(&'a str),
+    Open {
+        name: String,
+        raw: &'a str,
+        self_closing: bool,
+    },
+    Close {
+        name: String,
+        raw: &'a str,
+    },
+    Text(&'a str),
+    /// Content of an HTML raw text element such as `<script>`, kept verbatim.
+    RawText(&'a str),
+}
+
+/// Splits a document into tokens without validating it. Anything that does not
+/// look like markup is returned as text, so broken HTML still round-trips.
+pub fn tokenize(input: &str, html: bool) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < input.len() {
+        let rest = &input[pos..];
+        let Some(start) = markup_start(rest) else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+            pos += start;
+            continue;
+        }
+        let (token, len) = if rest.starts_with("<!--") {
+            let end = find_end(rest, "-->");
+            (Token::Comment(&rest[..end]), end)
+        } else if rest.starts_with("<![CDATA[") {
+            let end = find_end(rest, "]]>");
+            (Token::CData(&rest[..end]), end)
+        } else if rest.starts_with("<?") {
+            let end = find_end(rest, "?>");
+            (Token::Declaration(&rest[..end]), end)
+        } else if rest.starts_with("<!") {
+            let end = find_end(rest, ">");
+            (Token::Declaration(&rest[..end]), end)
+        } else if rest.starts_with("</") {
+            let end = tag_end(rest);
+            let name = tag_name(&rest[2..end], html);
+            (
+                Token::Close {
+                    name,
+                    raw: &rest[..end],
+                },
+                end,
+            )
+        } else {
+            let end = tag_end(rest);
+            let raw = &rest[..end];
+            let name = tag_name(&rest[1..end], html);
+            let self_closing = raw.trim_end_matches('>').trim_end().ends_with('/');
+            (
+                Token::Open {
+                    name,
+                    raw,
+                    self_closing,
+                },
+                end,
+            )
+        };
+        pos += len;
+        if let Token::Open {
+            name,
+            self_closing: false,
+            ..
+        } = &token
+        {
+            if html && RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                let content = &input[pos..];
+                // byte offsets of the original, lowercasing may change them
+                let closing = format!("</{}", name);
+                let close = content
+                    .as_bytes()
+                    .windows(closing.len())
+                    .position(|w| w.eq_ignore_ascii_case(closing.as_bytes()))
+                    .unwrap_or(content.len());
+                tokens.push(token);
+                if close > 0 {
+                    tokens.push(Token::RawText(&content[..close]));
+                }
+                pos += close;
+                continue;
+            }
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Position of the first `<` that starts markup, a lone `<` being plain text.
+fn markup_start(rest: &str) -> Option<usize> {
+    rest.match_indices('<').map(|(idx, _)| idx).find(|idx| {
+        let next = &rest[idx + 1..];
+        next.starts_with(['!', '?'])
+            || next
+                .trim_start_matches('/')
+                .starts_with(char::is_alphabetic)
+    })
+}
+
+fn find_end(rest: &str, terminator: &str) -> usize {
+    rest.find(terminator)
+        .map_or(rest.len(), |idx| idx + terminator.len())
+}
+
+/// Finds the `>` closing a tag, skipping over quoted attribute values.
+fn tag_end(rest: &str) -> usize {
+    let mut quote: Option<char> = None;
+    for (idx, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => (),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return idx + 1,
+            _ => (),
+        }
+    }
+    rest.len()
+}
+
+fn tag_name(tag: &str, html: bool) -> String {
+    let name = tag
+        .trim_start()
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .unwrap_or_default();
+    if html {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Parses the attributes of an opening tag's raw text.
+pub fn attributes(raw: &str) -> Vec<(String, String)> {
+    let inner = raw
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    let mut chars = inner.char_indices().peekable();
+    // skip the tag name
+    while chars.next_if(|(_, c)| !c.is_whitespace()).is_some() {}
+    let mut result = Vec::new();
+    loop {
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let Some(&(start, _)) = chars.peek() else {
+            return result;
+        };
+        let mut end = start;
+        while let Some((idx, _)) = chars.next_if(|(_, c)| !c.is_whitespace() && *c != '=') {
+            end = idx + 1;
+        }
+        let name = inner[start..end].to_string();
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        if chars.next_if(|(_, c)| *c == '=').is_none() {
+            if name.is_empty() {
+                chars.next();
+                continue;
+            }
+            result.push((name, String::new()));
+            continue;
+        }
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let value = match chars.peek().copied() {
+            Some((idx, q @ ('"' | '\''))) => {
+                chars.next();
+                let mut value_end = inner.len();
+                for (i, c) in chars.by_ref() {
+                    if c == q {
+                        value_end = i;
+                        break;
+                    }
+                }
+                inner[idx + 1..value_end].to_string()
+            }
+            Some((idx, _)) => {
+                let mut value_end = inner.len();
+                while let Some((i, _)) = chars.next_if(|(_, c)| !c.is_whitespace()) {
+                    value_end = i + 1;
+                }
+                inner[idx..value_end].to_string()
+            }
+            None => String::new(),
+        };
+        result.push((name, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_markup() {
+        let tokens = tokenize(
+            "<?xml version=\"1.0\"?><a x='1>2'><!-- c --><b/>t<![CDATA[<raw>]]></a>",
+            false,
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Declaration("<?xml version=\"1.0\"?>"),
+                Token::Open {
+                    name: "a".to_string(),
+                    raw: "<a x='1>2'>",
+                    self_closing: false,
+                },
+                Token::Comment("<!-- c -->"),
+                Token::Open {
+                    name: "b".to_string(),
+                    raw: "<b/>",
+                    self_closing: true,
+                },
+                Token::Text("t"),
+                Token::CData("<![CDATA[<raw>]]>"),
+                Token::Close {
+                    name: "a".to_string(),
+                    raw: "</a>",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lone_angle_brackets_are_text() {
+        assert_eq!(tokenize("1 < 2 <3", true), vec![Token::Text("1 < 2 <3")]);
+    }
+
+    #[test]
+    fn raw_text_ends_at_its_closing_tag() {
+        let tokens = tokenize("<SCRIPT>if (a<b) x = '</p>'</Script><p>", true);
+        assert_eq!(tokens[1], Token::RawText("if (a<b) x = '</p>'"));
+        assert_eq!(
+            tokens[2],
+            Token::Close {
+                name: "script".to_string(),
+                raw: "</Script>",
+            }
+        );
+    }
+
+    #[test]
+    fn reads_attributes() {
+        assert_eq!(
+            attributes("<input type=\"text\" disabled value='a b' size=3/>"),
+            vec![
+                ("type".to_string(), "text".to_string()),
+                ("disabled".to_string(), String::new()),
+                ("value".to_string(), "a b".to_string()),
+                ("size".to_string(), "3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn raw_text_with_characters_changing_length_when_lowercased() {
+        // 'İ' and the Kelvin sign lowercase to a different number of bytes
+        let tokens = tokenize("<style>İ K {}</style><b>", true);
+        assert_eq!(tokens[1], Token::RawText("İ K {}"));
+        assert_eq!(
+            tokens[2],
+            Token::Close {
+                name: "style".to_string(),
+                raw: "</style>",
+            }
+        );
+        assert_eq!(
+            tokens[3],
+            Token::Open {
+                name: "b".to_string(),
+                raw: "<b>",
+                self_closing: false,
+            }
+        );
+    }
+}
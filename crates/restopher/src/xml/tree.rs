@@ -0,0 +1,123 @@
+use super::tokenizer::{attributes, tokenize, Token};
+use super::{closes_implicitly, VOID_ELEMENTS};
+
+#[derive(Debug, Clone)]
+pub enum Node {
+    Element(Element),
+    Text(String),
+    /// Comments, CDATA sections and declarations, kept as written.
+    Other(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Element {
+    pub name: String,
+    pub raw_open: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+    pub fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|c| match c {
+            Node::Element(e) => Some(e),
+            _ => None,
+        })
+    }
+    /// Concatenated text of the element and all its descendants.
+    pub fn text(&self) -> String {
+        let mut result = String::new();
+        for child in &self.children {
+            match child {
+                Node::Text(t) => result.push_str(t),
+                Node::Element(e) => result.push_str(&e.text()),
+                Node::Other(t) if t.starts_with("<![CDATA[") => {
+                    result.push_str(t.trim_start_matches("<![CDATA[").trim_end_matches("]]>"))
+                }
+                Node::Other(_) => (),
+            }
+        }
+        result
+    }
+    /// Writes the element back as markup, without any added whitespace.
+    pub fn to_markup(&self) -> String {
+        let mut result = self.raw_open.clone();
+        for child in &self.children {
+            match child {
+                Node::Element(e) => result.push_str(&e.to_markup()),
+                Node::Text(t) | Node::Other(t) => result.push_str(t),
+            }
+        }
+        if !self.raw_open.trim_end_matches('>').ends_with('/') {
+            result.push_str(&format!("</{}>", self.name));
+        }
+        result
+    }
+}
+
+/// Builds a document tree. The returned element is a nameless root holding the
+/// top level nodes; unclosed elements are closed at the end of their parent.
+pub fn parse(input: &str, html: bool) -> Element {
+    let mut stack = vec![Element::default()];
+    for token in tokenize(input, html) {
+        match token {
+            Token::Open {
+                name,
+                raw,
+                self_closing,
+            } => {
+                while html
+                    && stack.len() > 1
+                    && closes_implicitly(&stack[stack.len() - 1].name, &name)
+                {
+                    let element = stack.pop().unwrap();
+                    push_child(&mut stack, Node::Element(element));
+                }
+                let element = Element {
+                    attributes: attributes(raw),
+                    raw_open: raw.to_string(),
+                    name,
+                    children: Vec::new(),
+                };
+                if self_closing || (html && VOID_ELEMENTS.contains(&element.name.as_str())) {
+                    push_child(&mut stack, Node::Element(element));
+                } else {
+                    stack.push(element);
+                }
+            }
+            Token::Close { name, .. } => {
+                if let Some(pos) = stack.iter().skip(1).rposition(|e| e.name == name) {
+                    while stack.len() > pos + 1 {
+                        let element = stack.pop().unwrap();
+                        push_child(&mut stack, Node::Element(element));
+                    }
+                }
+            }
+            Token::Text(t) | Token::RawText(t) => {
+                if !t.trim().is_empty() {
+                    push_child(&mut stack, Node::Text(t.to_string()))
+                }
+            }
+            Token::Comment(t) | Token::CData(t) | Token::Declaration(t) => {
+                push_child(&mut stack, Node::Other(t.to_string()))
+            }
+        }
+    }
+    while stack.len() > 1 {
+        let element = stack.pop().unwrap();
+        push_child(&mut stack, Node::Element(element));
+    }
+    stack.pop().unwrap()
+}
+
+fn push_child(stack: &mut [Element], node: Node) {
+    if let Some(parent) = stack.last_mut() {
+        parent.children.push(node);
+    }
+}
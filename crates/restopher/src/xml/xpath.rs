@@ -0,0 +1,294 @@
+use super::format;
+use super::tree::{parse, Element};
+use crate::filter::Cursor;
+
+/// The supported XPath subset: child (`/`) and descendant (`//`) steps over
+/// element names or `*`, a final `@attr` or `text()` step, and predicates of
+/// the form `[n]`, `[last()]`, `[@attr]`, `[@attr='v']` and `[child='v']`.
+#[derive(Debug)]
+struct Step {
+    descendant: bool,
+    test: Test,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug)]
+enum Test {
+    Name(String),
+    Any,
+    Text,
+    Attribute(String),
+}
+
+#[derive(Debug)]
+enum Predicate {
+    Index(usize),
+    Last,
+    HasAttribute(String),
+    AttributeEquals(String, String),
+    ChildEquals(String, String),
+}
+
+fn parse_path(expression: &str) -> Result<Vec<Step>, String> {
+    let mut cursor = Cursor::new(expression.trim());
+    let mut steps = Vec::new();
+    let mut first = true;
+    while !cursor.is_done() {
+        let descendant = if cursor.eat_str("//") {
+            true
+        } else if cursor.eat('/') || first {
+            false
+        } else {
+            return Err(cursor.error("expected '/'"));
+        };
+        first = false;
+        if matches!(
+            steps.last(),
+            Some(Step {
+                test: Test::Text | Test::Attribute(_),
+                ..
+            })
+        ) {
+            return Err(cursor.error("attribute and text() must be the last step"));
+        }
+        let test = if cursor.eat('@') {
+            if cursor.eat('*') {
+                Test::Attribute("*".to_string())
+            } else {
+                Test::Attribute(name(&mut cursor)?)
+            }
+        } else if cursor.eat_str("text()") {
+            Test::Text
+        } else if cursor.eat('*') {
+            Test::Any
+        } else {
+            Test::Name(name(&mut cursor)?)
+        };
+        let mut predicates = Vec::new();
+        while cursor.eat('[') {
+            predicates.push(predicate(&mut cursor)?);
+            cursor.expect(']')?;
+        }
+        steps.push(Step {
+            descendant,
+            test,
+            predicates,
+        });
+    }
+    if steps.is_empty() {
+        return Err(cursor.error("expected a path"));
+    }
+    Ok(steps)
+}
+
+/// Element and attribute names, which may carry a namespace prefix.
+fn name(cursor: &mut Cursor) -> Result<String, String> {
+    let mut result = cursor
+        .identifier()
+        .ok_or_else(|| cursor.error("expected a name"))?;
+    while let Some(separator) = [':', '.'].into_iter().find(|c| cursor.eat(*c)) {
+        result.push(separator);
+        result.push_str(&cursor.identifier().unwrap_or_default());
+    }
+    Ok(result)
+}
+
+fn predicate(cursor: &mut Cursor) -> Result<Predicate, String> {
+    cursor.skip_whitespace();
+    if cursor.eat_str("last()") {
+        return Ok(Predicate::Last);
+    }
+    if let Some(n) = cursor.integer() {
+        return match usize::try_from(n) {
+            Ok(n) if n > 0 => Ok(Predicate::Index(n)),
+            _ => Err(cursor.error("positions start at 1")),
+        };
+    }
+    let attribute = cursor.eat('@');
+    let key = name(cursor)?;
+    cursor.skip_whitespace();
+    if !cursor.eat('=') {
+        return if attribute {
+            Ok(Predicate::HasAttribute(key))
+        } else {
+            Err(cursor.error("expected '='"))
+        };
+    }
+    cursor.skip_whitespace();
+    let value = if cursor.eat('\'') {
+        cursor.string('\'')?
+    } else if cursor.eat('"') {
+        cursor.string('"')?
+    } else {
+        return Err(cursor.error("expected a quoted string"));
+    };
+    cursor.skip_whitespace();
+    Ok(if attribute {
+        Predicate::AttributeEquals(key, value)
+    } else {
+        Predicate::ChildEquals(key, value)
+    })
+}
+
+fn matches_test(element: &Element, test: &Test) -> bool {
+    match test {
+        Test::Name(name) => element.name == *name,
+        Test::Any => true,
+        Test::Text | Test::Attribute(_) => false,
+    }
+}
+
+/// Keeps the candidates of one parent that satisfy all predicates, positions
+/// being counted within that parent as XPath does.
+fn filter_group<'a>(mut group: Vec<&'a Element>, predicates: &[Predicate]) -> Vec<&'a Element> {
+    for predicate in predicates {
+        group = match predicate {
+            Predicate::Index(n) => group.get(n - 1).copied().into_iter().collect(),
+            Predicate::Last => group.last().copied().into_iter().collect(),
+            Predicate::HasAttribute(key) => group
+                .into_iter()
+                .filter(|e| e.attribute(key).is_some())
+                .collect(),
+            Predicate::AttributeEquals(key, value) => group
+                .into_iter()
+                .filter(|e| e.attribute(key) == Some(value))
+                .collect(),
+            Predicate::ChildEquals(key, value) => group
+                .into_iter()
+                .filter(|e| {
+                    e.elements()
+                        .any(|c| c.name == *key && c.text().trim() == value)
+                })
+                .collect(),
+        }
+    }
+    group
+}
+
+fn self_and_descendants<'a>(element: &'a Element, result: &mut Vec<&'a Element>) {
+    result.push(element);
+    for child in element.elements() {
+        self_and_descendants(child, result);
+    }
+}
+
+fn evaluate(steps: &[Step], root: &Element) -> Vec<String> {
+    let mut context = vec![root];
+    for step in steps {
+        let parents = if step.descendant {
+            let mut all = Vec::new();
+            for element in &context {
+                self_and_descendants(element, &mut all);
+            }
+            all
+        } else {
+            context
+        };
+        match &step.test {
+            Test::Text => {
+                return parents
+                    .iter()
+                    .map(|e| e.text().trim().to_string())
+                    .filter(|t| !t.is_empty())
+                    .collect()
+            }
+            Test::Attribute(key) => {
+                return parents
+                    .iter()
+                    .flat_map(|e| e.attributes.iter())
+                    .filter(|(k, _)| key == "*" || k == key)
+                    .map(|(_, v)| v.clone())
+                    .collect()
+            }
+            Test::Name(_) | Test::Any => (),
+        }
+        context = parents
+            .into_iter()
+            .flat_map(|parent| {
+                let group = parent
+                    .elements()
+                    .filter(|e| matches_test(e, &step.test))
+                    .collect();
+                filter_group(group, &step.predicates)
+            })
+            .collect();
+    }
+    context.iter().map(|e| e.to_markup()).collect()
+}
+
+/// Runs an XPath expression against an XML or HTML body. Matched elements are
+/// returned as formatted markup, attributes and text as plain strings.
+pub fn apply_to_body(expression: &str, body: &str, html: bool) -> Result<Vec<String>, String> {
+    let steps = parse_path(expression)?;
+    let root = parse(body, html);
+    Ok(evaluate(&steps, &root)
+        .into_iter()
+        .map(|result| {
+            if result.starts_with('<') {
+                format(&result, html)
+            } else {
+                result
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = "<?xml version=\"1.0\"?>\
+        <catalog>\
+          <book id=\"1\" lang=\"en\"><title>Dune</title><price>9</price></book>\
+          <book id=\"2\"><title>Emma</title><price>12</price></book>\
+          <shelf><book id=\"3\" lang=\"de\"><title>Ubik</title></book></shelf>\
+        </catalog>";
+
+    fn run(expression: &str) -> Vec<String> {
+        apply_to_body(expression, CATALOG, false).unwrap()
+    }
+
+    #[test]
+    fn child_and_descendant_steps() {
+        assert_eq!(run("/catalog/book/title/text()"), vec!["Dune", "Emma"]);
+        assert_eq!(run("//title/text()"), vec!["Dune", "Emma", "Ubik"]);
+        assert_eq!(run("/catalog/*/book/@id"), vec!["3"]);
+        assert_eq!(run("//book/@*").len(), 5);
+    }
+
+    #[test]
+    fn predicates() {
+        assert_eq!(run("//book[2]/@id"), vec!["2"]);
+        assert_eq!(run("/catalog/book[last()]/@id"), vec!["2"]);
+        assert_eq!(run("//book[@lang]/@id"), vec!["1", "3"]);
+        assert_eq!(run("//book[@lang='de']/title/text()"), vec!["Ubik"]);
+        assert_eq!(run("//book[title='Emma']/price/text()"), vec!["12"]);
+        // positions count within each parent
+        assert_eq!(run("//book[1]/@id"), vec!["1", "3"]);
+    }
+
+    #[test]
+    fn elements_are_returned_as_markup() {
+        assert_eq!(
+            run("//book[@id='2']/title"),
+            vec![format("<title>Emma</title>", false)]
+        );
+    }
+
+    #[test]
+    fn html_names_ignore_case() {
+        let body = "<HTML><Body><P class=a>one<p>two</BODY></html>";
+        assert_eq!(
+            apply_to_body("//p/text()", body, true).unwrap(),
+            vec!["one", "two"]
+        );
+        assert_eq!(apply_to_body("//p/@class", body, true).unwrap(), vec!["a"]);
+    }
+
+    #[test]
+    fn reports_invalid_paths() {
+        for expression in ["", "//", "/a[0]", "/a[@b=c]", "/a/text()/b", "/a[1"] {
+            assert!(parse_path(expression).is_err(), "{}", expression);
+        }
+    }
+}
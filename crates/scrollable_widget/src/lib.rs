@@ -16,6 +16,7 @@ pub struct ScrollableList<'a, T> {
     /// Items to render inside the list
     pub items: Vec<T>,
     block: Block<'a>,
+    highlight_style: Style,
     formatter: Option<Box<dyn Fn(&T) -> ListItem>>,
 }
 
@@ -26,6 +27,7 @@ impl<'a, T: ToString + Clone> ScrollableList<'a, T> {
             items,
             formatter: None,
             block: Block::default(),
+            highlight_style: Style::default().fg(Color::Green),
         }
     }
 
@@ -46,6 +48,11 @@ impl<'a, T: ToString + Clone> ScrollableList<'a, T> {
         self.block = block;
         self
     }
+    /// Style of the selected item, green text by default.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
 }
 
 impl<'a, T: ToString + Clone> StatefulWidget for ScrollableList<'a, T> {
@@ -78,7 +85,7 @@ impl<'a, T: ToString + Clone> StatefulWidget for ScrollableList<'a, T> {
 
         let list = List::new(items)
             .block(Block::new().title(title).borders(Borders::ALL))
-            .highlight_style(Style::default().fg(Color::Green))
+            .highlight_style(self.highlight_style)
             .highlight_symbol(">>");
 
         StatefulWidget::render(list, list_area, buf, state);